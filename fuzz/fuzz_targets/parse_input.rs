@@ -0,0 +1,8 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+// any bytes at all have to come back as companies or an error, never a panic
+fuzz_target!(|data: &[u8]| {
+    let _ = fair_renderer::parse_input(data);
+});
@@ -0,0 +1,108 @@
+use sha2::{Digest, Sha256};
+
+use crate::{Error, social};
+
+// names are replaced by a fake built from a hash of the real one so the same company gets
+// the same fake name everywhere it shows up in the file
+const COMPANY_KEYS: &[&str] = &["employer_name", "career_fair_name"];
+const PERSON_KEYS: &[&str] = &["schedule_owner_name", "first_name", "last_name", "full_name", "contact_name"];
+const TEXT_KEYS: &[&str] = &["company_description", "description", "event_description", "job_titles"];
+const URL_KEYS: &[&str] = &["website", "logo_url", "url", "apply_url", "entity_path"];
+// arrays of people whose "name" is a person's rather than a company's
+const PERSON_PARENTS: &[&str] = &["contact", "contacts", "recruiter", "recruiters", "representatives", "employer_representatives", "employer_reps"];
+const LOCATION_KEYS: &[&str] = &["location_name", "address"];
+
+const WORDS: &[&str] = &[
+    "lorem", "ipsum", "dolor", "sit", "amet", "consectetur", "adipiscing", "elit", "sed", "do", "eiusmod",
+    "tempor", "incididunt", "ut", "labore", "et", "dolore", "magna", "aliqua", "enim", "minim", "veniam",
+];
+
+// replaces anything identifying in a payload but keeps its shape, ids, dates, majors and sessions
+// so the anonymized file still reproduces whatever went wrong with the real one
+pub fn anonymize(input_data: &[u8]) -> Result<String, Error> {
+    let mut json_data: serde_json::Value = match serde_json::from_slice(input_data) {
+        Ok(data) => data,
+        Err(source) => return Err(Error::Json { what: "input data".to_string(), source }),
+    };
+
+    anonymize_value(&mut json_data, "");
+
+    match serde_json::to_string_pretty(&json_data) {
+        Ok(text) => Ok(text + "\n"),
+        Err(e) => Err(Error::Other(format!("failed writing anonymized json: {}", e))),
+    }
+}
+
+fn anonymize_value(value: &mut serde_json::Value, parent_key: &str) {
+    match value {
+        serde_json::Value::Object(map) => {
+            for (key, child) in map.iter_mut() {
+                if let serde_json::Value::String(text) = child {
+                    if let Some(fake) = fake_value(key, parent_key, text) {
+                        *text = fake;
+                    }
+                    continue;
+                }
+                anonymize_value(child, key);
+            }
+        },
+        serde_json::Value::Array(items) => {
+            for item in items {
+                anonymize_value(item, parent_key);
+            }
+        },
+        _ => {},
+    }
+}
+
+fn fake_value(key: &str, parent_key: &str, real: &str) -> Option<String> {
+    if real.is_empty() {
+        return None;
+    }
+    let hash = short_hash(real);
+
+    // "name" is also used for majors and job types which are fine to keep
+    let is_company = COMPANY_KEYS.contains(&key) || (key == "name" && parent_key == "employer");
+    let is_person = PERSON_KEYS.contains(&key) || (key == "name" && PERSON_PARENTS.contains(&parent_key));
+
+    if is_company {
+        Some(format!("Company {}", hash))
+    } else if is_person {
+        Some(format!("Person {}", hash))
+    } else if TEXT_KEYS.contains(&key) {
+        Some(fake_text(real, &hash))
+    } else if URL_KEYS.contains(&key) || social::URL_KEYS.contains(&key) {
+        Some(format!("https://example.com/{}", hash))
+    } else if LOCATION_KEYS.contains(&key) {
+        Some(format!("{} Main Street, Springfield", u32::from_str_radix(&hash[..4], 16).unwrap_or(0) % 9000 + 100))
+    } else if key.contains("email") {
+        Some(format!("user-{}@example.com", hash))
+    } else if key.contains("phone") {
+        Some("555-0100".to_string())
+    } else {
+        None
+    }
+}
+
+fn short_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().take(4).map(|b| format!("{:02x}", b)).collect()
+}
+
+// same number of lines and words so anything length related still shows up
+fn fake_text(real: &str, hash: &str) -> String {
+    let seed = u32::from_str_radix(hash, 16).unwrap_or(0) as usize;
+    let mut word_index = 0;
+
+    real.lines()
+        .map(|line| {
+            let words: Vec<&str> = line.split_whitespace()
+                .map(|_| {
+                    word_index += 1;
+                    WORDS[(seed + word_index * 7) % WORDS.len()]
+                })
+                .collect();
+            words.join(" ")
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
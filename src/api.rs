@@ -0,0 +1,133 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::fields::{FieldFormat, FieldFormats};
+use crate::hooks::Hooks;
+use crate::oversize::Oversized;
+use crate::policy::Policy;
+use crate::report::RenderReport;
+use crate::{CliArgs, CompanyEntry, DEFAULT_TEMPLATE, Error, LINK_FIELDS, check_class_name, check_key_prefix, encoding, fetch, inputs, naming, render_run, render_to_memory_with, term, views};
+
+// an export read and parsed once, to look through its companies or hand to a VaultRenderer
+pub struct Fair {
+    pub companies: Vec<CompanyEntry>,
+    // what the render reads, decoded and with its pages merged
+    pub(crate) input_data: Vec<u8>,
+}
+
+impl Fair {
+    // any export the command line takes as --input, a career fair or a job search, saved pages pasted
+    // back to back are merged, strict about missing fields like validate
+    pub fn parse(input_data: &[u8]) -> Result<Fair, Error> {
+        let input_data = encoding::to_utf8(inputs::decompress(input_data.to_vec())?, None)?;
+        let input_data = inputs::normalize(fetch::merge_pages(input_data), None, &[])?;
+        let companies = crate::parse_input(&input_data)?;
+        Ok(Fair { companies, input_data })
+    }
+
+    pub fn read(path: &Path) -> Result<Fair, Error> {
+        match fs::read(path) {
+            Ok(bytes) => Fair::parse(&bytes),
+            Err(source) => Err(Error::Input { path: path.to_string_lossy().to_string(), source }),
+        }
+    }
+}
+
+// renders a Fair into a vault the way fair_renderer render or update would, without printing anything,
+// what it wrote and skipped is in the report it returns, neither fair_renderer.toml nor the
+// FAIR_RENDERER_<NAME> variables are read, the options are all there is
+pub struct VaultRenderer {
+    // the default template when left out
+    pub template: Option<PathBuf>,
+    // refreshes the vault that's there instead of rendering a new one
+    pub update: bool,
+    // --lang, the labels.<lang>.yaml in the template to use
+    pub lang: Option<String>,
+    // --note-name, ex. "Fair - {{name}}"
+    pub note_name: Option<String>,
+    // --class-name, "company" by default
+    pub class_name: String,
+    // --key-prefix
+    pub key_prefix: String,
+    // --link-fields
+    pub link_fields: bool,
+    // --mini
+    pub mini: bool,
+    // --summarize
+    pub summarize: bool,
+    // --strict, the first note that won't write is an error instead of a skip in the report
+    pub strict: bool,
+    // off is --no-build-info
+    pub build_info: bool,
+    // run on every company and note, ex. renderer.hooks.push(Arc::new(MySection))
+    pub hooks: Hooks,
+}
+
+impl Default for VaultRenderer {
+    fn default() -> Self {
+        VaultRenderer {
+            template: None,
+            update: false,
+            lang: None,
+            note_name: None,
+            class_name: "company".to_string(),
+            key_prefix: String::new(),
+            link_fields: false,
+            mini: false,
+            summarize: false,
+            strict: false,
+            build_info: true,
+            hooks: Vec::new(),
+        }
+    }
+}
+
+impl VaultRenderer {
+    pub fn render(&self, fair: &Fair, vault_path: &Path) -> Result<RenderReport, Error> {
+        let cli_args = self.cli_args(vault_path)?;
+        let mut report = RenderReport::default();
+        let rendered = render_run(&cli_args, fair.input_data.clone(), &mut report);
+        term::done();
+        rendered.map(|()| report)
+    }
+
+    // the options checked the same way the command line checks its flags
+    fn cli_args(&self, vault_path: &Path) -> Result<CliArgs, Error> {
+        let template = self.template.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_TEMPLATE));
+        if let Some(scheme) = &self.note_name {
+            naming::check_scheme(scheme)?;
+        }
+        let mut field_formats = FieldFormats::default();
+        if self.link_fields {
+            for field in LINK_FIELDS {
+                field_formats.set(field, FieldFormat::Link);
+            }
+        }
+        Ok(CliArgs {
+            update: self.update,
+            output_path: Some(vault_path.to_string_lossy().to_string()),
+            template_path: Some(template.to_string_lossy().to_string()),
+            views: views::load(&template)?,
+            lang: self.lang.clone(),
+            note_name: self.note_name.clone(),
+            class_name: check_class_name(self.class_name.clone())?,
+            key_prefix: check_key_prefix(self.key_prefix.clone())?,
+            field_formats,
+            mini: self.mini,
+            oversized: if self.mini { Oversized::Truncate } else { Oversized::Move },
+            summarize: self.summarize,
+            policy: if self.strict { Policy::Strict } else { Policy::Warn },
+            build_info: self.build_info,
+            hooks: self.hooks.clone(),
+            ..CliArgs::default()
+        })
+    }
+
+    // the notes a new vault would get, by their path inside it, with the default labels and field formats,
+    // nothing is written and the other options aren't used
+    pub fn render_to_memory(&self, fair: &Fair) -> Result<BTreeMap<String, String>, Error> {
+        let template = self.template.clone().unwrap_or_else(|| PathBuf::from(DEFAULT_TEMPLATE));
+        render_to_memory_with(&fair.input_data, &template, &self.hooks)
+    }
+}
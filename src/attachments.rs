@@ -0,0 +1,236 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use base64::Engine;
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use sha2::{Digest, Sha256};
+
+use crate::term::{self, Status};
+use crate::folders::Folders;
+use crate::limits::Downloader;
+use crate::{CompanyEntry, Error, links};
+
+// obsidian's own trash folder, anything archived can still be pulled back out of it
+const ARCHIVE_DIR: &str = ".trash";
+
+#[derive(Clone, Copy)]
+pub enum PruneMode {
+    Archive,
+    Delete,
+}
+
+impl PruneMode {
+    pub fn parse(value: &str) -> Result<PruneMode, Error> {
+        match value {
+            "archive" => Ok(PruneMode::Archive),
+            "delete" => Ok(PruneMode::Delete),
+            _ => Err(Error::Usage(format!("unknown prune mode {}, expected archive or delete", value))),
+        }
+    }
+}
+
+// signed urls from s3, cloudfront, azure and google storage stop working after a while, their query
+// has one of these
+const EXPIRING_PARAMS: &[&str] = &[
+    "x-amz-expires", "x-amz-signature", "expires", "signature", "key-pair-id", "x-goog-expires", "x-goog-signature", "sig",
+];
+
+pub fn is_data_uri(url: &str) -> bool {
+    url.get(..5).is_some_and(|scheme| scheme.eq_ignore_ascii_case("data:"))
+}
+
+pub fn expires(url: &str) -> bool {
+    let Some((_, query)) = url.split_once('?') else { return false };
+    query.split('&')
+        .filter_map(|param| param.split('=').next())
+        .any(|key| EXPIRING_PARAMS.contains(&key.to_ascii_lowercase().as_str()))
+}
+
+// logos that can't stay a link in the note, a data uri would put the whole image in it and a signed
+// url 403s once it expires, so they're saved into the vault even without --download-logos
+pub fn keeps_locally(company: &CompanyEntry) -> bool {
+    is_data_uri(&company.logo_url) || expires(&company.logo_url)
+}
+
+fn percent_decode(text: &str) -> Vec<u8> {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        let hex = bytes.get(i + 1..i + 3).and_then(|h| std::str::from_utf8(h).ok()).and_then(|h| u8::from_str_radix(h, 16).ok());
+        match (bytes[i], hex) {
+            (b'%', Some(byte)) => {
+                decoded.push(byte);
+                i += 3;
+            },
+            (byte, _) => {
+                decoded.push(byte);
+                i += 1;
+            },
+        }
+    }
+    decoded
+}
+
+// data:image/png;base64,iVBOR... or data:image/svg+xml,<svg ...> with percent escapes, the content
+// type and the image
+fn decode_data_uri(url: &str) -> Result<(Option<String>, Vec<u8>), Error> {
+    let Some((header, data)) = url[5..].split_once(',') else {
+        return Err(Error::Other("data uri has no , before its data".to_string()));
+    };
+    let mut params = header.split(';');
+    let content_type = params.next().map(|t| t.trim().to_ascii_lowercase()).filter(|t| !t.is_empty());
+    if content_type.as_deref().is_some_and(|t| !t.starts_with("image/")) {
+        return Err(Error::Other(format!("data uri is {} rather than an image", content_type.unwrap_or_default())));
+    }
+    if !params.any(|p| p.trim().eq_ignore_ascii_case("base64")) {
+        return Ok((content_type, percent_decode(data)));
+    }
+    // exports wrap long lines and drop the padding now and then
+    let engine = GeneralPurpose::new(&alphabet::STANDARD, GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent));
+    let cleaned: Vec<u8> = percent_decode(data).into_iter().filter(|b| !b.is_ascii_whitespace()).collect();
+    match engine.decode(cleaned) {
+        Ok(bytes) if !bytes.is_empty() => Ok((content_type, bytes)),
+        Ok(_) => Err(Error::Other("data uri is empty".to_string())),
+        Err(e) => Err(Error::Other(format!("data uri isn't valid base64: {}", e))),
+    }
+}
+
+// handshake logo urls often have no usable extension so the content type is the better guess
+fn extension(content_type: Option<&str>) -> &'static str {
+    match content_type {
+        Some(t) if t.starts_with("image/jpeg") => "jpg",
+        Some(t) if t.starts_with("image/gif") => "gif",
+        Some(t) if t.starts_with("image/svg") => "svg",
+        Some(t) if t.starts_with("image/webp") => "webp",
+        _ => "png",
+    }
+}
+
+// the url is part of the name so a changed logo gets a new file and the old one becomes an orphan,
+// a signed url gets a new signature every export so only the part before its query counts
+fn logo_stem(company: &CompanyEntry) -> String {
+    let url = match expires(&company.logo_url) {
+        true => company.logo_url.split('?').next().unwrap_or_default(),
+        false => &company.logo_url,
+    };
+    let hash = Sha256::digest(url.as_bytes());
+    let hash: String = hash.iter().take(4).map(|b| format!("{:02x}", b)).collect();
+    format!("{}-{}", links::slug(&company.name), hash)
+}
+
+// the logo an earlier render already downloaded for the company's current logo url
+pub fn existing_logo(vault_path: &Path, company: &CompanyEntry, folders: &Folders) -> Option<String> {
+    let stem = logo_stem(company);
+    fs::read_dir(vault_path.join(folders.logos())).ok()?
+        .flatten()
+        .map(|e| e.file_name().to_string_lossy().to_string())
+        .find(|f| f.starts_with(&format!("{}.", stem)))
+}
+
+// returns the file name inside the logos folder, logos already downloaded by an earlier render are reused
+pub fn download_logo(vault_path: &Path, company: &CompanyEntry, folders: &Folders, downloader: &mut Downloader) -> Result<Option<String>, Error> {
+    if company.logo_url.is_empty() {
+        return Ok(None);
+    }
+    if let Some(existing) = existing_logo(vault_path, company, folders) {
+        return Ok(Some(existing));
+    }
+
+    let logo_dir = vault_path.join(folders.logos());
+    let stem = logo_stem(company);
+
+    let (content_type, bytes) = match is_data_uri(&company.logo_url) {
+        true => match decode_data_uri(&company.logo_url) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                downloader.policy.fail(&format!("logo for {}: {}", company.name, e))?;
+                return Ok(None);
+            },
+        },
+        false => {
+            term::trace(&format!("downloading logo for {}", company.name));
+            match downloader.get(&company.logo_url, &format!("logo for {}", company.name))? {
+                Some(download) => download,
+                None if expires(&company.logo_url) => {
+                    term::warn(&format!("the logo link for {} will stop working once its signed url expires", company.name));
+                    return Ok(None);
+                },
+                None => return Ok(None),
+            }
+        },
+    };
+
+    let file_name = format!("{}.{}", stem, extension(content_type.as_deref()));
+    let written = fs::create_dir_all(&logo_dir).and_then(|_| fs::write(logo_dir.join(&file_name), bytes));
+    match written {
+        Ok(()) => {
+            term::status(Status::Written, &logo_dir.join(&file_name).to_string_lossy());
+            Ok(Some(file_name))
+        },
+        Err(e) => {
+            downloader.policy.fail(&format!("logo for {}: {}", company.name, e))?;
+            Ok(None)
+        },
+    }
+}
+
+// removes logos no note embeds anymore, from companies that left or logos that changed, one the user
+// embedded in a note of their own stays
+pub fn prune_logos(vault_path: &Path, mode: PruneMode, folders: &Folders) -> Result<usize, Error> {
+    let logo_dir = vault_path.join(folders.logos());
+    let Ok(entries) = fs::read_dir(&logo_dir) else { return Ok(0) };
+
+    let mut linked = BTreeSet::new();
+    linked_files(vault_path, &mut linked);
+
+    let mut pruned = 0;
+    for entry in entries.flatten() {
+        let file_name = entry.file_name().to_string_lossy().to_string();
+        if !entry.path().is_file() || linked.contains(&file_name.to_lowercase()) {
+            continue;
+        }
+
+        match mode {
+            PruneMode::Delete => fs::remove_file(entry.path())?,
+            PruneMode::Archive => {
+                let archive_dir: PathBuf = vault_path.join(ARCHIVE_DIR).join(folders.logos());
+                let failed = |source| Error::Output { path: archive_dir.join(&file_name).to_string_lossy().to_string(), source };
+                fs::create_dir_all(&archive_dir).map_err(failed)?;
+                fs::rename(entry.path(), archive_dir.join(&file_name)).map_err(failed)?;
+            },
+        }
+        term::trace(&format!("pruned {}", file_name));
+        pruned += 1;
+    }
+
+    Ok(pruned)
+}
+
+// the file names the vault's notes link to or embed, as ![[logo.png|80]] or ![](attachments/logos/logo.png),
+// lowercased since obsidian finds them either way, notes in dot folders like .trash don't count
+fn linked_files(dir: &Path, linked: &mut BTreeSet<String>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if path.is_dir() {
+            linked_files(&path, linked);
+            continue;
+        }
+        if path.extension().is_none_or(|e| e != "md") {
+            continue;
+        }
+        let text = fs::read_to_string(&path).unwrap_or_default();
+        let markdown = text.split("](").skip(1)
+            .filter_map(|rest| rest.split_once(')'))
+            .map(|(target, _)| target.trim_matches(['<', '>']).replace("%20", " "));
+        for target in links::wikilink_targets(&text).into_iter().chain(markdown) {
+            linked.insert(target.rsplit('/').next().unwrap_or(&target).to_lowercase());
+        }
+    }
+}
@@ -0,0 +1,69 @@
+#[cfg(feature = "keyring")]
+use std::io::{self, BufRead, Write};
+
+use crate::Error;
+
+#[cfg(feature = "keyring")]
+const SERVICE: &str = "fair_renderer";
+#[cfg(feature = "keyring")]
+const ACCOUNT: &str = "handshake_session";
+
+#[cfg(feature = "keyring")]
+fn entry() -> Result<keyring::Entry, Error> {
+    keyring::Entry::new(SERVICE, ACCOUNT).map_err(|e| Error::Other(format!("could not open the os keyring: {}", e)))
+}
+
+// reads the token from stdin instead of an argument so it never lands in shell history,
+// piping works too: pbpaste | fair_renderer auth login
+#[cfg(feature = "keyring")]
+pub fn login() -> Result<(), Error> {
+    print!("Paste your handshake session token: ");
+    io::stdout().flush()?;
+
+    let mut token = String::new();
+    io::stdin().lock().read_line(&mut token)?;
+    let token = token.trim();
+    if token.is_empty() {
+        return Err(Error::Other("no token given, nothing was stored".to_string()));
+    }
+
+    match entry()?.set_password(token) {
+        Ok(()) => {
+            println!("Token saved to the os keyring");
+            Ok(())
+        },
+        Err(e) => Err(Error::Other(format!("failed saving token to the keyring: {}", e))),
+    }
+}
+
+#[cfg(feature = "keyring")]
+pub fn logout() -> Result<(), Error> {
+    match entry()?.delete_credential() {
+        Ok(()) => println!("Token removed from the os keyring"),
+        Err(keyring::Error::NoEntry) => println!("No token was stored"),
+        Err(e) => return Err(Error::Other(format!("failed removing token from the keyring: {}", e))),
+    }
+    Ok(())
+}
+
+// a missing or unreachable keyring just means there is no stored token
+#[cfg(feature = "keyring")]
+pub fn stored_token() -> Option<String> {
+    entry().ok()?.get_password().ok()
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn login() -> Result<(), Error> {
+    Err(crate::missing_feature("auth login", "keyring"))
+}
+
+#[cfg(not(feature = "keyring"))]
+pub fn logout() -> Result<(), Error> {
+    Err(crate::missing_feature("auth logout", "keyring"))
+}
+
+// without a keyring --token or FAIR_RENDERER_TOKEN is the only way to pass one
+#[cfg(not(feature = "keyring"))]
+pub fn stored_token() -> Option<String> {
+    None
+}
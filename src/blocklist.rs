@@ -0,0 +1,100 @@
+use std::fs;
+
+use crate::labels::Labels;
+use crate::{CompanyEntry, Error, update};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum BlockMode {
+    // rendered like any other company, with flagged: true and a banner saying why
+    Flag,
+    // left out of the vault altogether
+    Skip,
+}
+
+impl BlockMode {
+    pub fn parse(mode: &str) -> Result<BlockMode, Error> {
+        match mode {
+            "flag" => Ok(BlockMode::Flag),
+            "skip" => Ok(BlockMode::Skip),
+            _ => Err(Error::Usage(format!("unknown blocklist mode: {}, expected flag or skip", mode))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            BlockMode::Flag => "flag",
+            BlockMode::Skip => "skip",
+        }
+    }
+}
+
+// employers to stay away from, a line per company as "Acme: rescinded offers 2024" or just "Acme",
+// names match ignoring case and lines starting with # are comments
+pub struct Blocklist {
+    pub path: String,
+    pub mode: BlockMode,
+    entries: Vec<(String, String)>,
+}
+
+impl Blocklist {
+    pub fn load(path: &str, mode: BlockMode) -> Result<Blocklist, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => return Err(Error::Usage(format!("could not read blocklist {}: {}", path, e))),
+        };
+        let entries = text.lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| match line.split_once(':') {
+                Some((name, reason)) => (name.trim().to_lowercase(), reason.trim().to_string()),
+                None => (line.to_lowercase(), String::new()),
+            })
+            .collect();
+        Ok(Blocklist { path: path.to_string(), mode, entries })
+    }
+
+    pub fn reason(&self, company: &CompanyEntry) -> Option<&str> {
+        let name = company.name.to_lowercase();
+        self.entries.iter().find(|(blocked, _)| *blocked == name).map(|(_, reason)| reason.as_str())
+    }
+
+    // with --blocklist-mode skip, returns the companies it left out as (name, reason)
+    pub fn remove_blocked(&self, companies: &mut Vec<CompanyEntry>) -> Vec<(String, String)> {
+        if self.mode != BlockMode::Skip {
+            return Vec::new();
+        }
+        let mut removed = Vec::new();
+        companies.retain(|company| match self.reason(company) {
+            Some(reason) => {
+                removed.push((company.name.clone(), reason.to_string()));
+                false
+            },
+            None => true,
+        });
+        removed
+    }
+
+    // every company gets flagged and the banner section, so one taken off the list loses them on the next refresh
+    pub fn flag_frontmatter(&self, company: &CompanyEntry, generated: &mut Vec<(String, String)>, key_prefix: &str) {
+        let flagged = self.reason(company).is_some();
+        generated.push((format!("{}flagged", key_prefix), flagged.to_string()));
+    }
+
+    pub fn banner(&self, company: &CompanyEntry, sections: &mut Vec<(&'static str, String)>, labels: &Labels) {
+        let banner = match self.reason(company) {
+            Some("") => format!("> [!danger] {}\n", labels.blocklisted),
+            Some(reason) => format!("> [!danger] {}\n> {}\n", labels.blocklisted, reason),
+            None => String::new(),
+        };
+        sections.insert(0, ("flag", banner));
+    }
+
+    // notes written before the company was on the list have no place for the banner yet, it goes
+    // right under the frontmatter
+    pub fn add_banner_section(&self, company: &CompanyEntry, existing: &str) -> String {
+        match self.reason(company) {
+            Some(_) => update::insert_section(existing, "flag", None),
+            None => existing.to_string(),
+        }
+    }
+}
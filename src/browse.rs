@@ -0,0 +1,200 @@
+use std::collections::BTreeSet;
+use std::io::IsTerminal;
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, List, ListItem, ListState, Paragraph, Wrap};
+use ratatui::{DefaultTerminal, Frame};
+
+use crate::{CompanyEntry, Error};
+
+// how far page up and down move the cursor
+const PAGE: usize = 20;
+
+struct Browser<'a> {
+    companies: &'a [CompanyEntry],
+    // indexes into companies of the ones matching the search, in input order
+    shown: Vec<usize>,
+    picked: BTreeSet<usize>,
+    query: String,
+    searching: bool,
+    list: ListState,
+}
+
+// a list of the input's companies to search and tick off before rendering, the indexes of the picked
+// ones, or None when it was left without rendering
+pub fn browse(companies: &[CompanyEntry]) -> Result<Option<Vec<usize>>, Error> {
+    if !std::io::stdout().is_terminal() {
+        return Err(Error::Other("browse needs a terminal to draw in".to_string()));
+    }
+    if companies.is_empty() {
+        return Err(Error::Other("the input has no companies to browse".to_string()));
+    }
+    let mut browser = Browser {
+        companies,
+        shown: (0..companies.len()).collect(),
+        picked: BTreeSet::new(),
+        query: String::new(),
+        searching: false,
+        list: ListState::default().with_selected(Some(0)),
+    };
+    let mut terminal = ratatui::try_init()?;
+    let picked = browser.run(&mut terminal);
+    ratatui::restore();
+    picked
+}
+
+impl Browser<'_> {
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> Result<Option<Vec<usize>>, Error> {
+        loop {
+            terminal.draw(|frame| self.draw(frame))?;
+            let Event::Key(key) = event::read()? else { continue };
+            // windows also reports releases
+            if key.kind != KeyEventKind::Press {
+                continue;
+            }
+
+            if self.searching {
+                match key.code {
+                    KeyCode::Enter | KeyCode::Esc => self.searching = false,
+                    KeyCode::Backspace => {
+                        self.query.pop();
+                        self.filter();
+                    },
+                    KeyCode::Char(c) => {
+                        self.query.push(c);
+                        self.filter();
+                    },
+                    KeyCode::Up | KeyCode::Down => {
+                        self.searching = false;
+                        self.move_cursor(if key.code == KeyCode::Up { -1 } else { 1 });
+                    },
+                    _ => {},
+                }
+                continue;
+            }
+
+            match key.code {
+                KeyCode::Char('q') => return Ok(None),
+                KeyCode::Esc if !self.query.is_empty() => {
+                    self.query.clear();
+                    self.filter();
+                },
+                KeyCode::Esc => return Ok(None),
+                KeyCode::Enter => return Ok(Some(self.picked.iter().copied().collect())),
+                KeyCode::Char('/') => self.searching = true,
+                KeyCode::Up | KeyCode::Char('k') => self.move_cursor(-1),
+                KeyCode::Down | KeyCode::Char('j') => self.move_cursor(1),
+                KeyCode::PageUp => self.move_cursor(-(PAGE as isize)),
+                KeyCode::PageDown => self.move_cursor(PAGE as isize),
+                KeyCode::Home | KeyCode::Char('g') => self.list.select(Some(0)),
+                KeyCode::End | KeyCode::Char('G') => self.list.select(Some(self.shown.len().saturating_sub(1))),
+                KeyCode::Char(' ') | KeyCode::Char('x') => {
+                    if let Some(index) = self.current() && !self.picked.remove(&index) {
+                        self.picked.insert(index);
+                    }
+                    self.move_cursor(1);
+                },
+                // everything the search shows, or unticks them when they already all are
+                KeyCode::Char('a') => match self.shown.iter().all(|i| self.picked.contains(i)) {
+                    true => self.shown.iter().for_each(|i| { self.picked.remove(i); }),
+                    false => self.picked.extend(self.shown.iter().copied()),
+                },
+                _ => {},
+            }
+        }
+    }
+
+    fn current(&self) -> Option<usize> {
+        self.list.selected().and_then(|row| self.shown.get(row).copied())
+    }
+
+    fn move_cursor(&mut self, by: isize) {
+        if self.shown.is_empty() {
+            return;
+        }
+        let row = self.list.selected().unwrap_or(0).saturating_add_signed(by).min(self.shown.len() - 1);
+        self.list.select(Some(row));
+    }
+
+    // matches the name, location, industry, majors and job types, every word of the search has to match one
+    fn filter(&mut self) {
+        let words: Vec<String> = self.query.to_lowercase().split_whitespace().map(str::to_string).collect();
+        self.shown = self.companies.iter().enumerate()
+            .filter(|(_, company)| {
+                let text = searchable(company);
+                words.iter().all(|word| text.contains(word))
+            })
+            .map(|(i, _)| i)
+            .collect();
+        self.list.select(if self.shown.is_empty() { None } else { Some(0) });
+    }
+
+    fn draw(&mut self, frame: &mut Frame) {
+        let [search_area, main_area, help_area] = Layout::vertical([Constraint::Length(3), Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        let [list_area, detail_area] = Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)]).areas(main_area);
+
+        let search_style = match self.searching {
+            true => Style::new().add_modifier(Modifier::BOLD),
+            false => Style::new(),
+        };
+        let search_title = format!("search, {} of {} companies, {} picked", self.shown.len(), self.companies.len(), self.picked.len());
+        frame.render_widget(Paragraph::new(format!("/{}", self.query)).style(search_style).block(Block::bordered().title(search_title)), search_area);
+
+        let items: Vec<ListItem> = self.shown.iter()
+            .map(|i| {
+                let mark = if self.picked.contains(i) { "[x] " } else { "[ ] " };
+                ListItem::new(format!("{}{}", mark, self.companies[*i].name))
+            })
+            .collect();
+        let list = List::new(items)
+            .block(Block::bordered().title("companies"))
+            .highlight_style(Style::new().add_modifier(Modifier::REVERSED));
+        frame.render_stateful_widget(list, list_area, &mut self.list);
+
+        let details = match self.current() {
+            Some(index) => details(&self.companies[index]),
+            None => vec![Line::from("no company matches the search")],
+        };
+        frame.render_widget(Paragraph::new(details).wrap(Wrap { trim: false }).block(Block::bordered().title("details")), detail_area);
+
+        let help = match self.searching {
+            true => "type to search  enter/esc done",
+            false => "up/down move  space pick  a pick all shown  / search  enter render picked  q quit",
+        };
+        frame.render_widget(Paragraph::new(help).style(Style::new().add_modifier(Modifier::DIM)), help_area);
+    }
+}
+
+fn searchable(company: &CompanyEntry) -> String {
+    let mut text = vec![company.name.as_str(), company.location.as_str(), company.industry.as_deref().unwrap_or_default()];
+    text.extend(company.majors.iter().map(String::as_str));
+    text.extend(company.job_types.iter().map(String::as_str));
+    text.join("\n").to_lowercase()
+}
+
+fn details(company: &CompanyEntry) -> Vec<Line<'static>> {
+    let field = |label: &str, value: String| Line::from(vec![
+        Span::styled(format!("{}: ", label), Style::new().add_modifier(Modifier::BOLD)),
+        Span::raw(if value.is_empty() { "-".to_string() } else { value }),
+    ]);
+    let sessions: Vec<&str> = company.attending_sessions.iter().map(|s| s.display_name.as_str()).collect();
+    let mut lines = vec![
+        Line::from(Span::styled(company.name.clone(), Style::new().add_modifier(Modifier::BOLD))),
+        Line::default(),
+        field("location", company.location.clone()),
+        field("industry", company.industry.clone().unwrap_or_default()),
+        field("job types", company.job_types.join(", ")),
+        field("majors", company.majors.join(", ")),
+        field("sessions", sessions.join(", ")),
+        field("work authorization", company.work_authorization.clone()),
+    ];
+    if let Some(booth) = &company.booth {
+        lines.push(field("booth", booth.clone()));
+    }
+    lines.push(Line::default());
+    lines.extend(company.description.lines().map(|line| Line::from(line.to_string())));
+    lines
+}
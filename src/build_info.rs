@@ -0,0 +1,23 @@
+pub const VERSION: &str = env!("CARGO_PKG_VERSION");
+pub const COMMIT: &str = env!("FAIR_RENDERER_COMMIT");
+
+// which build wrote a note and from what input, so a shared vault can be traced back and rendered
+// again the same way, an html comment so it doesn't show in reading view
+pub fn stamp(input_hash: &str) -> String {
+    format!("<!-- fair_renderer {} ({}), input sha256 {} -->\n", VERSION, COMMIT, input_hash)
+}
+
+// the build stamp goes under everything else the renderer wrote into the note
+pub fn with_stamp(mut sections: Vec<(&'static str, String)>, stamp: Option<&str>) -> Vec<(&'static str, String)> {
+    if let Some(stamp) = stamp {
+        sections.push(("build", stamp.to_string()));
+    }
+    sections
+}
+
+pub fn stamp_text(text: String, stamp: Option<&str>) -> String {
+    match stamp {
+        Some(stamp) => format!("{}\n{}", text, stamp),
+        None => text,
+    }
+}
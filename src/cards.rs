@@ -0,0 +1,111 @@
+use std::fs;
+use std::process::Command;
+
+use crate::{CompanyEntry, Error};
+
+// long major lists don't fit on a table card, the rest is summarized as a count
+const MAX_MAJORS: usize = 8;
+
+// browsers that can print a page to pdf without a window, tried in order
+const PDF_COMMANDS: &[&str] = &["chromium", "chromium-browser", "google-chrome", "microsoft-edge"];
+
+// handshake fills in identifiers like "A12" once booths are assigned and only numbers for some fairs
+pub fn find_booth(json_entry: &serde_json::Value) -> Option<String> {
+    let booths: Vec<String> = ["assigned_booth_identifiers", "assigned_booth_numbers"].iter()
+        .filter_map(|key| json_entry[key].as_array())
+        .flat_map(|values| values.iter().filter_map(|v| match v {
+            serde_json::Value::String(s) => Some(s.clone()),
+            serde_json::Value::Number(n) => Some(n.to_string()),
+            _ => None,
+        }))
+        .collect();
+
+    let mut unique: Vec<String> = Vec::new();
+    for booth in booths {
+        if !unique.contains(&booth) {
+            unique.push(booth);
+        }
+    }
+    (!unique.is_empty()).then(|| unique.join(", "))
+}
+
+fn html_escape(text: &str) -> String {
+    text.replace('&', "&amp;").replace('<', "&lt;").replace('>', "&gt;").replace('"', "&quot;")
+}
+
+// one card per company sorted by booth so the career center can print and lay them out in order
+pub fn render_cards(companies: &[CompanyEntry]) -> String {
+    let mut sorted: Vec<&CompanyEntry> = companies.iter().collect();
+    sorted.sort_by(|a, b| a.booth.is_none().cmp(&b.booth.is_none()).then(a.booth.cmp(&b.booth)).then(a.name.cmp(&b.name)));
+
+    let mut html = String::from(concat!(
+        "<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n<title>Table cards</title>\n<style>\n",
+        "body { font-family: sans-serif; margin: 0; }\n",
+        ".cards { display: grid; grid-template-columns: 1fr 1fr; gap: 0.25in; padding: 0.25in; }\n",
+        ".card { border: 1px solid #999; padding: 0.25in; height: 3.5in; box-sizing: border-box; break-inside: avoid; }\n",
+        ".card img { max-height: 0.9in; max-width: 2in; }\n",
+        ".card h2 { margin: 0.1in 0; font-size: 22pt; }\n",
+        ".booth { font-size: 16pt; font-weight: bold; }\n",
+        ".summary { font-size: 11pt; }\n",
+        ".majors { font-size: 10pt; color: #333; }\n",
+        "</style>\n</head>\n<body>\n<div class=\"cards\">\n",
+    ));
+
+    for company in sorted {
+        html.push_str("<div class=\"card\">\n");
+        if !company.logo_url.is_empty() {
+            html.push_str(&format!("<img src=\"{}\" alt=\"\">\n", html_escape(&company.logo_url)));
+        }
+        html.push_str(&format!("<h2>{}</h2>\n", html_escape(&company.name)));
+        if let Some(booth) = &company.booth {
+            html.push_str(&format!("<div class=\"booth\">Booth {}</div>\n", html_escape(booth)));
+        }
+        if let Some(summary) = &company.summary {
+            html.push_str(&format!("<p class=\"summary\">{}</p>\n", html_escape(summary)));
+        }
+        if !company.majors.is_empty() {
+            let mut majors: Vec<String> = company.majors.iter().take(MAX_MAJORS).map(|m| html_escape(m)).collect();
+            if company.majors.len() > MAX_MAJORS {
+                majors.push(format!("+{} more", company.majors.len() - MAX_MAJORS));
+            }
+            html.push_str(&format!("<p class=\"majors\">{}</p>\n", majors.join(" · ")));
+        }
+        html.push_str("</div>\n");
+    }
+
+    html.push_str("</div>\n</body>\n</html>\n");
+    html
+}
+
+// there's no pdf writer in here, a headless browser prints the html the same way a user would
+pub fn write_cards(path: &str, companies: &[CompanyEntry]) -> Result<(), Error> {
+    let html = render_cards(companies);
+
+    if !path.ends_with(".pdf") {
+        return match fs::write(path, html) {
+            Ok(()) => Ok(()),
+            Err(source) => Err(Error::Output { path: path.to_string(), source }),
+        };
+    }
+    if !cfg!(feature = "pdf") {
+        return Err(crate::missing_feature(&format!("printing {}", path), "pdf"));
+    }
+
+    let html_path = std::env::temp_dir().join(format!("fair_renderer_cards_{}.html", std::process::id()));
+    fs::write(&html_path, html).map_err(|source| Error::Output { path: html_path.to_string_lossy().to_string(), source })?;
+
+    let pdf_arg = format!("--print-to-pdf={}", path);
+    let printed = PDF_COMMANDS.iter().any(|browser| {
+        Command::new(browser)
+            .args(["--headless", "--disable-gpu", "--no-pdf-header-footer", &pdf_arg])
+            .arg(&html_path)
+            .output()
+            .is_ok_and(|output| output.status.success())
+    });
+    let _ = fs::remove_file(&html_path);
+
+    match printed {
+        true => Ok(()),
+        false => Err(Error::Other(format!("printing {} needs chromium or chrome installed, export to .html and print it instead", path))),
+    }
+}
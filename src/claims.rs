@@ -0,0 +1,78 @@
+use std::path::Path;
+
+use crate::CompanyEntry;
+use crate::labels::Labels;
+use crate::{links, prep};
+
+// a user field rather than a generated one so the renderer never writes over it, each member
+// fills in their own name and notes merged back from their copies don't conflict on it
+pub const CLAIM_FIELD: &str = "claimed_by";
+
+// claimed_by: alice, claimed_by: [alice, bob] and claimed_by: "[[alice]]" all count
+fn claimants(value: &str) -> Vec<String> {
+    value.trim_start_matches('[').trim_end_matches(']')
+        .split(',')
+        .map(|name| name.trim().trim_matches(['"', '\'', '[', ']']).trim().to_string())
+        .filter(|name| !name.is_empty())
+        .collect()
+}
+
+fn company_line(company: &CompanyEntry, labels: &Labels) -> String {
+    match &company.booth {
+        Some(booth) => format!("- {} ({} {})\n", links::company_link(company), labels.booth, booth),
+        None => format!("- {}\n", links::company_link(company)),
+    }
+}
+
+// who has claimed which company going by the notes as they are now, companies nobody has taken
+// yet first so they're easy to hand out, then one list per member and any claimed twice
+pub fn render_board(companies_dir: &Path, companies: &[CompanyEntry], enabled: bool, labels: &Labels) -> Option<String> {
+    if !enabled {
+        return None;
+    }
+
+    let mut unclaimed: Vec<&CompanyEntry> = Vec::new();
+    let mut members: Vec<(String, Vec<&CompanyEntry>)> = Vec::new();
+    let mut claimed_twice: Vec<(&CompanyEntry, Vec<String>)> = Vec::new();
+    for company in companies {
+        let names = prep::note_field(companies_dir, company, CLAIM_FIELD).map(|v| claimants(&v)).unwrap_or_default();
+        if names.is_empty() {
+            unclaimed.push(company);
+            continue;
+        }
+        for name in &names {
+            // case is ignored so Alice and alice from two people's copies end up on one list
+            match members.iter_mut().find(|(member, _)| member.eq_ignore_ascii_case(name)) {
+                Some((_, claimed)) => claimed.push(company),
+                None => members.push((name.clone(), vec![company])),
+            }
+        }
+        if names.len() > 1 {
+            claimed_twice.push((company, names));
+        }
+    }
+    members.sort_by_key(|(member, _)| member.to_lowercase());
+
+    let mut text = format!("# {}\n", labels.board);
+
+    text.push_str(&format!("\n## {} ({})\n\n", labels.unclaimed, unclaimed.len()));
+    for company in &unclaimed {
+        text.push_str(&company_line(company, labels));
+    }
+
+    for (member, claimed) in &members {
+        text.push_str(&format!("\n## {} ({})\n\n", member, claimed.len()));
+        for company in claimed {
+            text.push_str(&company_line(company, labels));
+        }
+    }
+
+    if !claimed_twice.is_empty() {
+        text.push_str(&format!("\n## {}\n\n", labels.claimed_twice));
+        for (company, names) in &claimed_twice {
+            text.push_str(&format!("- {}: {}\n", links::company_link(company), names.join(", ")));
+        }
+    }
+
+    Some(text)
+}
@@ -0,0 +1,396 @@
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint};
+
+use crate::{Error, config};
+
+// a flag's long name is also its id, its key in the config and its FAIR_RENDERER_<NAME> variable
+fn value(long: &'static str, value_name: &'static str, help: &'static str) -> Arg {
+    Arg::new(long).long(long).value_name(value_name).help(help)
+}
+
+fn path(long: &'static str, value_name: &'static str, help: &'static str) -> Arg {
+    value(long, value_name, help).value_hint(ValueHint::AnyPath)
+}
+
+fn switch(long: &'static str, help: &'static str) -> Arg {
+    Arg::new(long).long(long).action(ArgAction::SetTrue).help(help)
+}
+
+// every flag the render takes, the subcommands pick theirs out by name, the short help is for -h and
+// completions, the long one for --help
+fn flags() -> Vec<Arg> {
+    vec![
+        path("input", "PATH", "path to the json that contains the data to render")
+            .short('i').num_args(1..).action(ArgAction::Append)
+            .long_help("path to the json that contains the data to render, either a career fair or a job search export, \
+                clipboard to read the json straight from the clipboard or - for stdin, saved pages pasted back to back or \
+                in a json array are merged, or an .xlsx/.xls/.ods spreadsheet with a row per company, several inputs or a \
+                glob are merged with companies in more than one combined, gzipped input like .json.gz is decompressed"),
+        path("mapping", "PATH.yaml", "yaml file mapping fields to paths in the input json")
+            .long_help("where each field is in the input json, for other portals or a changed handshake export, ex. \
+                name: /company/title and majors: programs[].label, results: /data points at the entries, unmapped fields \
+                are read as usual"),
+        value("format", "NAME,...", "platform and layout of the input, ex. handshake or csv")
+            .long_help("which platform the input is from, handshake or handshake-jobs, and how it's laid out, json for \
+                the api response, results for just its results array, ndjson for an entry per line, csv or yaml, ex. \
+                --format csv,handshake, both are picked from the input itself when left out"),
+        value("encoding", "NAME", "what the input was saved as, ex. windows-1252")
+            .long_help("what the input was saved as, utf-8, utf-16le, utf-16be, windows-1252 or latin1, picked from the \
+                input itself when left out"),
+        value("column", "FIELD=HEADER,...", "spreadsheet column for a field as field=header")
+            .action(ArgAction::Append)
+            .long_help("which spreadsheet column fills which field, columns are otherwise matched by header, fields are \
+                name, location, website, description, majors, job_titles, job_types, school_years, work_authorization, \
+                sessions, booth, deadline and logo_url, can be repeated"),
+        value("fetch", "URL", "download the json from the handshake api instead of --input")
+            .long_help("alternative to --input, downloads the json from the handshake api, following its pages and \
+                merging them"),
+        value("token", "SESSION_TOKEN", "handshake session cookie used by --fetch")
+            .long_help("handshake session cookie used by --fetch, defaults to the token saved with auth login"),
+        path("jobs", "PATH", "job search export to list open positions from")
+            .long_help("a job search export whose postings are listed under Open positions in the note of the fair \
+                company with the same employer, postings a fair entry carries itself under jobs are listed without it"),
+        value("interval", "30m|6h|1d", "keep running and re-render on this interval")
+            .long_help("keeps running and re-renders on this interval, every run that changes the roster adds to \
+                Roster History.md and .csv"),
+        value("webhook", "URL", "webhook pinged with roster changes")
+            .long_help("discord/slack webhook pinged with added, removed and rescheduled companies whenever an update \
+                finds roster changes"),
+        switch("resume", "pick up a render that died midway")
+            .long_help("picks up a render that died midway instead of starting over, companies it already wrote aren't \
+                rendered or downloaded again"),
+        switch("check", "fail if the vault has drifted from the input")
+            .long_help("exits with an error if the vault has drifted from what the input would render, nothing is written"),
+        path("output", "PATH", "path to put the generated vault").short('o').alias("out"),
+        path("template", "PATH", "path to the template vault").short('t')
+            .long_help("path to the template vault or will use a default, a views.yaml in it adds index notes of its \
+                own, each a name with a filter list like export-subset's, a field to sort by (-Priority for highest \
+                first), one to group by and the fields to show next to each company"),
+        path("followup-template", "PATH", "email template for per company follow-up notes").short('f')
+            .long_help("email template, renders a follow-up note per company, placeholders: {{company}} {{website}} \
+                {{recruiter_name}} {{recruiter_email}} {{position}}, the recruiter is the first contact the export lists"),
+        path("note-stub", "PATH", "block appended to every company note").short('s')
+            .long_help("block appended to every company note, left untouched so Templater can expand it (defaults to \
+                stubs/company.md in the template)"),
+        value("export", "KIND:PATH", "extra artifact to write as kind:path").short('e').action(ArgAction::Append)
+            .long_help("extra artifact to write, can be repeated, kinds: csv (one row per company), ics (fair sessions \
+                calendar), cards (printable table cards, .html or .pdf with chromium installed)"),
+        value("upload", "TARGET", "upload the vault to webdav:<url> or s3://<bucket>/<prefix>").short('u')
+            .long_help("uploads the rendered vault, target is webdav:<url> or s3://<bucket>/<prefix>, credentials are \
+                read from FAIR_RENDERER_WEBDAV_USER/PASSWORD or the AWS_* variables"),
+        value("lang", "CODE", "language of the labels.<lang>.yaml file in the template")
+            .long_help("reads labels.<code>.yaml from the template to translate generated headings (description, \
+                deadlines, apply_by, apply_task, company, recruiter_marker, position_marker, schedule, session_full, \
+                registered, capacity, waitlisted, checklist, booth, links, team, recruits_all, recruits_any, coverage, \
+                board, unclaimed, claimed_twice, summary, pending_followups, roster_history, blocklisted, \
+                open_positions, industries, contacts, starred, starred_missing, ungrouped, full_description, cut_short)"),
+        value("translate-cmd", "COMMAND", "command that translates descriptions")
+            .long_help("command that translates descriptions not in the --translate-to language, gets the text on stdin \
+                and FAIR_RENDERER_FROM/TO in its env"),
+        value("translate-to", "CODE", "language to translate descriptions into, defaults to en"),
+        switch("summarize", "add a short summary of each description")
+            .long_help("writes a summary field with the first sentences of each description, shown in the schedule, \
+                starred list and table cards"),
+        value("summary-sentences", "N", "how many sentences a summary keeps, defaults to 2"),
+        value("summarize-cmd", "COMMAND", "command that summarizes descriptions")
+            .long_help("command that summarizes descriptions instead, gets the text on stdin and FAIR_RENDERER_SENTENCES \
+                in its env, implies --summarize"),
+        value("select-fields", "FIELD,...", "fields offered as a dropdown of the input's values")
+            .action(ArgAction::Append)
+            .long_help("adds majors, job_types, school_years, sessions, industry or international to the fileClass as \
+                Select or Multi fields with the values in the input as options, ones already Select or Multi always get them"),
+        switch("link-fields", "write location, majors and sessions as wikilinks")
+            .long_help("writes location, majors and sessions as wikilinks so the graph connects companies through them"),
+        value("field-format", "FIELD=TYPE,...", "per field output type as field=string|list|link|tag")
+            .action(ArgAction::Append)
+            .long_help("per field output type, string (default), list, link or tag, ex. \
+                majors=list,location=link,job_types=tag, can be repeated"),
+        value("class-name", "NAME", "fileClass name for company notes, defaults to company"),
+        switch("class-per-fair", "give every fair its own fileClass")
+            .long_help("adds the fair id to the class name so each fair gets its own class"),
+        value("note-name", "SCHEME", "naming scheme for company and index notes")
+            .long_help("how notes are named, ex. \"{{date}} {{fair}} - {{name}}\", used for company notes, session links \
+                and the index notes like Deadlines, placeholders are name, fair, date, month and year (of the fair's \
+                first session) and id (the employer id, or a short hash), renders with a new scheme rename the notes"),
+        value("extension", "md|txt|mdx", "write the notes as md, txt or mdx into a plain folder")
+            .long_help("writes the notes with this extension as a plain folder for a static site or printing instead \
+                of a vault, wikilinks become relative links or text"),
+        switch("no-frontmatter", "write the fields into the note body instead of frontmatter")
+            .long_help("writes the fields as a list at the top of each note instead of frontmatter, also a plain folder \
+                rather than a vault"),
+        switch("flat", "put every note straight in the output folder")
+            .long_help("puts every note straight in the output folder, also a plain folder, which only gets the company \
+                notes, Deadlines, Schedule and Team"),
+        value("folder", "NAME=PATH,...", "vault folder to use as name=path").action(ArgAction::Append)
+            .long_help("moves a vault folder, names are companies, classes, attachments, events, jobs, prep, follow_ups \
+                and comparisons, ex. companies=Employers, can be repeated"),
+        value("key-prefix", "PREFIX", "prefix for generated frontmatter keys and fileClass fields")
+            .long_help("put in front of every generated frontmatter key and fileClass field, ex. fair_ so they don't \
+                collide with properties used elsewhere"),
+        switch("tasks-format", "write deadlines in the Tasks plugin format"),
+        switch("download-logos", "save logos into the vault instead of linking them")
+            .long_help("saves logos into attachments/logos and embeds them instead of linking handshake, logos no note \
+                uses anymore are pruned after each render, logos given as data uris or signed urls that expire are \
+                saved either way"),
+        value("prune-logos", "archive|delete", "what to do with unused logos")
+            .long_help("what to do with unused logos, archive (default) moves them to .trash"),
+        value("max-downloads", "COUNT", "stop downloading logos after this many"),
+        value("max-file-size", "SIZE", "skip downloads bigger than this, ex. 500KB, defaults to 16MB"),
+        value("max-field-size", "SIZE", "cut descriptions longer than this, defaults to 32KB")
+            .long_help("descriptions longer than this are cut short in the note, ex. 64KB, defaults to 32KB, long job \
+                titles and locations are always cut at 4KB"),
+        value("oversized", "move|truncate", "move or truncate the rest of a long description")
+            .long_help("what happens to the rest of a long description, move (default) writes it whole to \
+                attachments/descriptions and links it from the note, truncate leaves it out with a notice, a --mini \
+                vault always truncates"),
+        value("timeout", "10s|2m", "how long an enrichment may take before it's skipped")
+            .long_help("how long logo downloads, translations and the webhook may take before they're skipped, defaults \
+                to 30s, one timed out download skips the rest, --upload waits this long for a server to connect and answer"),
+        switch("prep", "write prep notes for companies with a Priority")
+            .long_help("writes a prep note for every company with a Priority of 1 or more, with its description, \
+                skills, positions, match score and questions"),
+        value("my-majors", "MAJOR,...", "your majors, or a teammate's as name=major,...").action(ArgAction::Append)
+            .long_help("your majors, used for the match score in prep notes, can be repeated as name=major,... for \
+                teammates to get a Team note showing who each company recruits and a split of the booths between you"),
+        switch("claims", "add claimed_by to notes and a Board note of who claimed what")
+            .long_help("adds a claimed_by field to company notes and a Board note listing who claimed which company, \
+                for splitting a fair between members"),
+        path("blocklist", "PATH", "companies to flag or skip, with optional reasons")
+            .long_help("employers to avoid, a line per company as \"Acme: rescinded offers\" or just the name, their \
+                notes get flagged: true and a warning with the reason"),
+        value("blocklist-mode", "flag|skip", "flag or skip the companies on the blocklist")
+            .long_help("skip leaves companies on the blocklist out instead of flagging them"),
+        value("session", "NAME", "only render the companies at this session")
+            .long_help("only renders the companies at this session, matched by its name or part of it, ex. --session \
+                \"Day 1 Morning\""),
+        switch("mini", "trimmed vault of company notes, schedule and checklist")
+            .long_help("a trimmed vault to sync to a phone, only the company notes, the schedule and a checklist of \
+                every company, without logos or other notes"),
+        path("starred", "PATH", "companies starred in handshake to mark and list")
+            .long_help("companies starred in handshake, its favorites json or a name per line, their notes get \
+                starred: true and they're listed in a Starred note"),
+        path("industry-map", "PATH.yaml", "keywords for guessing industries the export leaves out")
+            .long_help("industry: [keyword, ...] lines for guessing the industry of companies the export has none for, \
+                replacing the built in keywords for an industry it lists, an empty list drops that industry"),
+        switch("register", "add the vault to obsidian's vault switcher")
+            .long_help("adds the vault to obsidian's vault switcher, obsidian should be closed since it rewrites its \
+                vault list when it quits"),
+        switch("open", "open the vault in obsidian after rendering"),
+        switch("strict", "fail on missing fields and failed enrichments")
+            .long_help("fails on any missing or odd field and on any logo, translation, webhook or note that couldn't \
+                be done, instead of warning and carrying on"),
+        switch("lenient", "quietly fill in missing fields and skip failed enrichments")
+            .long_help("fills in missing fields and skips failed enrichments without mentioning it, -v still shows them"),
+        switch("no-build-info", "leave the version and input hash comment out of the notes")
+            .long_help("leaves out the comment at the end of each note with the version, commit and input hash that \
+                rendered it, the manifest still records them"),
+        path("report", "PATH.json", "write a json summary of the run")
+            .long_help("writes a json summary of the run for scripts, the companies rendered, the files written, the \
+                entries and files skipped and the notes written under a fallback name, how long it took and the error \
+                if it failed, rewritten after every --interval run"),
+        switch("skip-invalid", "leave out entries with missing or mistyped fields")
+            .long_help("leaves out entries with a missing or mistyped field, even with --strict, instead of rendering \
+                them with empty values, and lists them at the end"),
+    ]
+}
+
+// the ones every command takes, before or after its name, listed after a command's own
+fn global_flags() -> Vec<Arg> {
+    let flags = vec![
+        switch("no-color", "disable colored output, also off when not printing to a terminal").global(true),
+        path("config", "PATH.toml", "toml file of defaults, ./fair_renderer.toml when there is one").global(true)
+            .long_help("defaults for any option, keyed by its long name like output = \"vault\" or input = [\"a.json\", \
+                \"b.json\"], switches as true or false and field=value options as a [column] or [field_format] table, \
+                flags on the command line and their environment variables win, read from ./fair_renderer.toml when \
+                there is one"),
+        Arg::new("verbose").short('v').long("verbose").action(ArgAction::Count).global(true)
+            .help("print more debug info, -vv also prints trace info like which json each frontmatter value came from"),
+        Arg::new("quiet").short('q').long("quiet").action(ArgAction::SetTrue).global(true).help("only print errors"),
+    ];
+    flags.into_iter().map(|f| f.display_order(1000)).collect()
+}
+
+fn pick(names: &[&str]) -> Vec<Arg> {
+    let flags = flags();
+    names.iter()
+        .map(|name| flags.iter().find(|f| f.get_id() == name).cloned().unwrap_or_else(|| panic!("no flag {}", name)))
+        .collect()
+}
+
+// where an input comes from and how to read it, shared by the render and the subcommands reading one
+const INPUT: &[&str] = &["input", "mapping", "format", "encoding", "column"];
+
+const RENDER: &[&str] = &[
+    "input", "mapping", "format", "encoding", "column", "fetch", "token", "jobs", "output", "template",
+    "followup-template", "note-stub", "export", "upload", "lang", "translate-cmd", "translate-to", "summarize",
+    "summary-sentences", "summarize-cmd", "select-fields", "link-fields", "field-format", "class-name", "note-name",
+    "extension", "no-frontmatter", "flat", "class-per-fair", "folder", "key-prefix", "tasks-format", "download-logos",
+    "prune-logos", "max-downloads", "max-file-size", "max-field-size", "oversized", "timeout", "prep", "my-majors",
+    "claims", "blocklist", "blocklist-mode", "session", "mini", "starred", "industry-map", "register", "open", "strict",
+    "lenient", "no-build-info", "skip-invalid", "report",
+];
+
+// what only makes sense for a vault that's already there
+const UPDATE: &[&str] = &["interval", "webhook", "resume", "check"];
+
+fn input_path(what: &'static str) -> Arg {
+    Arg::new("input-path").value_name("INPUT_PATH").value_hint(ValueHint::FilePath).help(what)
+}
+
+fn vault_path() -> Arg {
+    Arg::new("vault-path").value_name("VAULT_PATH").value_hint(ValueHint::DirPath).required(true).help("the rendered vault")
+}
+
+pub fn command() -> Command {
+    Command::new("fair_renderer")
+        .about("This tool generates an obsidian vault based on career fair data")
+        .version(env!("CARGO_PKG_VERSION"))
+        .subcommand_required(true)
+        .arg_required_else_help(true)
+        .args(global_flags())
+        .after_long_help("Running without a subcommand renders, or updates with --update, --resume, --check or \
+            --interval, so fair_renderer -i fair.json -o vault is fair_renderer render -i fair.json -o vault.\n\n\
+            Value flags fall back to FAIR_RENDERER_<NAME> when not passed, ex. FAIR_RENDERER_INPUT, FAIR_RENDERER_OUTPUT, \
+            FAIR_RENDERER_TEMPLATE, FAIR_RENDERER_FETCH, FAIR_RENDERER_TOKEN, FAIR_RENDERER_INTERVAL, \
+            FAIR_RENDERER_UPLOAD, FAIR_RENDERER_WEBHOOK.\n\n\
+            Exits with 1 when validate, lint or --check find problems or something else fails, 2 for a mistake in the \
+            flags or config, 3 when the input can't be read or parsed, 4 for a broken template and 5 when the output \
+            can't be written")
+        .subcommand(Command::new("render")
+            .about("render the input into a new vault")
+            .args(pick(RENDER)))
+        .subcommand(Command::new("update")
+            .about("refresh an existing vault, leaving your own notes alone")
+            .long_about("refreshes an existing vault, adding new companies and updating generated fields and the \
+                sections between fair_renderer marker comments without touching your own notes, a renamed company's \
+                note and follow-up are moved to the new name")
+            .args(pick(RENDER))
+            .args(pick(UPDATE)))
+        .subcommand(Command::new("fetch")
+            .about("download the json from the handshake api, following its pages and merging them")
+            .arg(Arg::new("url").value_name("URL").help("the api url, defaults to FAIR_RENDERER_FETCH"))
+            .args(pick(&["token"]))
+            .arg(path("output", "PATH", "file to write the json to, printed otherwise").short('o').alias("out")))
+        .subcommand(Command::new("validate")
+            .about("check the input for missing fields and wrong types without rendering")
+            .long_about("checks the input against the format the render expects and prints every missing field, wrong \
+                type and empty list with the entry's index and company, without writing anything")
+            .arg(input_path("the input to check, or --input"))
+            .args(pick(INPUT)))
+        .subcommand(Command::new("stats")
+            .about("print how many companies the input has by session, industry, sponsorship and major")
+            .arg(input_path("the input to count, or --input"))
+            .args(pick(INPUT)))
+        .subcommand(Command::new("browse")
+            .about("search the input's companies and pick the ones to render")
+            .long_about("lists the input's companies with their majors and job types to search through and tick off, \
+                enter renders the picked ones into a new vault with the render's flags, or prints their names without -o")
+            .args(pick(RENDER)))
+        .subcommand(Command::new("completions")
+            .about("print a shell completion script")
+            .arg(Arg::new("shell").required(true).value_parser(["bash", "zsh", "fish", "powershell", "elvish"])))
+        .subcommand(Command::new("auth")
+            .about("save or remove the handshake token in the os keyring")
+            .subcommand_required(true)
+            .subcommand(Command::new("login").about("save a handshake session token to the os keyring for --fetch"))
+            .subcommand(Command::new("logout").about("remove the saved token")))
+        .subcommand(Command::new("anonymize")
+            .about("replace identifying data in an input json with fake values")
+            .long_about("replaces names, descriptions and urls in an input json with consistent fake values so it can \
+                be attached to a bug report")
+            .arg(input_path("the input to anonymize").required(true))
+            .args(pick(&["encoding"]))
+            .arg(path("output", "PATH", "file to write the anonymized json to, printed otherwise").short('o').alias("out")))
+        .subcommand(Command::new("compare")
+            .about("side by side comparison note for shortlisted companies")
+            .long_about("side by side table of majors, job types, sponsorship, sessions and match score (with \
+                --my-majors), written to comparisons/ when -o points at the vault or printed otherwise")
+            .arg(Arg::new("names").value_name("NAME").num_args(2..).required(true).help("the companies to compare"))
+            .args(pick(INPUT))
+            .args(pick(&["note-name", "folder", "my-majors"]))
+            .arg(path("output", "PATH", "the vault to write the comparison into, printed otherwise").short('o').alias("out")))
+        .subcommand(Command::new("export-subset")
+            .about("write the input with only the companies matching --filter")
+            .long_about("writes the input with only the companies that match, to share a shortlist others can render")
+            .args(pick(INPUT))
+            .arg(value("filter", "FIELD=VALUE", "what a company needs to be kept").action(ArgAction::Append)
+                .long_help("what a company needs to be kept, fields are name, location, major, job_type, job_title, \
+                    school_year, session, booth, international and industry, or with --vault any note field like \
+                    Priority>=2 or claimed_by=alice, filters on one field are alternatives, different fields all have \
+                    to match"))
+            .arg(path("vault", "PATH", "the rendered vault, to filter on the fields you filled in"))
+            .args(pick(&["note-name", "folder"]))
+            .arg(path("output", "PATH", "file to write the subset to, printed otherwise").short('o').alias("out")))
+        .subcommand(Command::new("extract")
+            .about("read a vault's company notes back out as json or csv")
+            .long_about("reads the company notes back out of a vault, with the fields you filled in like Priority, as \
+                json or csv")
+            .arg(vault_path())
+            .args(pick(&["folder", "key-prefix"]))
+            .arg(value("format", "json|csv", "json or csv, picked from the output's extension when left out"))
+            .arg(path("output", "PATH", "file to write to, json is printed otherwise").short('o').alias("out")))
+        .subcommand(Command::new("summary")
+            .about("write a Summary note of your tracker fields after the fair")
+            .long_about("writes Summary.md after the fair, counting checkboxes like Viewed, averaging numbers like \
+                Priority, grouping statuses and industries and listing follow-ups that still have their markers")
+            .arg(vault_path())
+            .args(pick(INPUT))
+            .args(pick(&["folder", "key-prefix", "lang", "template", "note-name"])))
+        .subcommand(Command::new("lint")
+            .about("check a generated vault for problems, --fix repairs the safe ones")
+            .long_about("checks a vault for broken links, unparseable frontmatter, notes missing fileClass and unused files in \
+                the attachments folder, link fields are expected to point at notes that don't exist yet")
+            .arg(vault_path())
+            .arg(switch("fix", "quote bad values, add fileClass (--class-name) and move orphans to .trash"))
+            .args(pick(&["class-name", "folder"])))
+}
+
+// what needs an update rather than a render when there's no subcommand, --update itself only says so
+const IMPLIES_UPDATE: &[&str] = &["--update", "--resume", "--check", "--interval", "--webhook"];
+
+// how many of the leading arguments are global flags and their values
+fn global_flags_before(args: &[String]) -> usize {
+    let globals = global_flags();
+    let takes_value = |flag: &Arg| flag.get_action().takes_values();
+    let mut at = 0;
+    while let Some(arg) = args.get(at) {
+        let long = arg.strip_prefix("--").map(|a| a.split_once('=').map_or(a, |(name, _)| name));
+        let step = match (long, arg.strip_prefix('-')) {
+            (Some(long), _) => match globals.iter().find(|f| f.get_long() == Some(long)) {
+                Some(flag) if takes_value(flag) && !arg.contains('=') => 2,
+                Some(_) => 1,
+                None => break,
+            },
+            // a cluster like -qv, none of the short global flags take a value
+            (None, Some(shorts)) if !shorts.is_empty() && shorts.chars().all(|c| globals.iter().any(|f| f.get_short() == Some(c) && !takes_value(f))) => 1,
+            _ => break,
+        };
+        at += step;
+    }
+    at
+}
+
+// a run without a subcommand is a render like before there were any, the config's defaults are
+// added for whichever command it is, clap's help and usage errors come back for the caller to print
+pub fn parse(mut args: Vec<String>) -> Result<Result<ArgMatches, clap::Error>, Error> {
+    let command = command();
+    // -v render or --config f.toml validate, the name goes first so it's found here and in the config,
+    // the global flags are taken after it just the same
+    let at = global_flags_before(&args);
+    let named = args.get(at).is_some_and(|a| command.find_subcommand(a).is_some());
+    if named {
+        let name = args.remove(at);
+        args.insert(0, name);
+    }
+    // with the input coming from the environment a bare run is a real run, not a request for help
+    let has_env_input = ["FAIR_RENDERER_INPUT", "FAIR_RENDERER_FETCH"].iter().any(|name| std::env::var(name).is_ok_and(|v| !v.is_empty()));
+    let asks_for_help = args.get(at).is_some_and(|a| ["-h", "--help", "-V", "--version"].contains(&a.as_str()));
+    if !named && !asks_for_help && (!args.is_empty() || has_env_input) {
+        // options can be given as --interval=10m too
+        let update = args.iter().any(|a| a.split('=').next().is_some_and(|flag| IMPLIES_UPDATE.contains(&flag)));
+        args.retain(|a| a != "--update");
+        args.insert(0, if update { "update" } else { "render" }.to_string());
+    }
+
+    let args = config::with_config(args, &command)?;
+    Ok(command.try_get_matches_from(std::iter::once("fair_renderer".to_string()).chain(args)))
+}
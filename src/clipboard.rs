@@ -0,0 +1,36 @@
+use std::process::Command;
+
+use crate::Error;
+
+// shells out to whatever clipboard tool the platform has rather than linking a gui toolkit,
+// on linux that means wl-paste, xclip or xsel has to be installed
+pub fn read_clipboard() -> Result<Vec<u8>, Error> {
+    let candidates: &[(&str, &[&str])] = if cfg!(target_os = "macos") {
+        &[("pbpaste", &[])]
+    } else if cfg!(target_os = "windows") {
+        &[("powershell", &["-NoProfile", "-Command", "Get-Clipboard -Raw"])]
+    } else if std::env::var_os("WAYLAND_DISPLAY").is_some() {
+        &[("wl-paste", &["--no-newline"]), ("xclip", &["-selection", "clipboard", "-o"]), ("xsel", &["--clipboard", "--output"])]
+    } else {
+        &[("xclip", &["-selection", "clipboard", "-o"]), ("xsel", &["--clipboard", "--output"]), ("wl-paste", &["--no-newline"])]
+    };
+
+    for (program, args) in candidates {
+        let output = match Command::new(program).args(*args).output() {
+            Ok(output) => output,
+            // not installed, try the next one
+            Err(_) => continue,
+        };
+
+        if !output.status.success() {
+            return Err(Error::InvalidInput(format!("{} failed reading the clipboard: {}", program, String::from_utf8_lossy(&output.stderr).trim())));
+        }
+        if output.stdout.iter().all(u8::is_ascii_whitespace) {
+            return Err(Error::InvalidInput("the clipboard is empty".to_string()));
+        }
+        return Ok(output.stdout);
+    }
+
+    let tried: Vec<&str> = candidates.iter().map(|(program, _)| *program).collect();
+    Err(Error::InvalidInput(format!("no clipboard tool found, tried: {}", tried.join(", "))))
+}
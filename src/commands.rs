@@ -0,0 +1,743 @@
+use std::collections::BTreeSet;
+use std::fs;
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+use std::time::Instant;
+
+use clap::ArgMatches;
+
+use crate::{
+    CliArgs, DEFAULT_TEMPLATE, Error, LINK_FIELDS, anonymize, attachments, auth, blocklist, check_class_name, check_key_prefix, cli,
+    clipboard, compare, completions, encoding, exit, export, extract, fetch, fields, folders, industry, inputs, labels, limits, links,
+    lint, mapping, naming, open, oversize, parse_input, plain, policy, prep, render_run, report, sources, spreadsheet, starred, state,
+    stats, subset, summary, team, term, upload, validate, views, write_file,
+};
+#[cfg(feature = "tui")]
+use crate::browse;
+
+fn anonymize_command(args: &ArgMatches) -> Result<(), Error> {
+    let input_path = flag_value(args, "input-path").unwrap_or_default();
+    let anonymized = anonymize::anonymize(&encoding::to_utf8(inputs::decompress(fs::read(input_path)?)?, encoding_arg(args)?)?)?;
+    match flag_value(args, "output") {
+        Some(path) => write_file(path, anonymized)?,
+        None => print!("{}", anonymized),
+    }
+    Ok(())
+}
+
+fn compare_command(args: &ArgMatches) -> Result<(), Error> {
+    let names = flag_values(args, "names");
+    let Some(input_path) = flag_or_env(args, "input", "FAIR_RENDERER_INPUT") else {
+        return Err(Error::Usage("compare needs the input data: --input [path_to_input]".to_string()));
+    };
+    let input_data = fetch::merge_pages(subcommand_input(args, &input_path)?);
+    let mut companies = parse_input(&input_data)?;
+    naming::Naming::new(note_name_arg(args)?.as_deref(), &input_data, &companies)?.apply(&mut companies);
+    let picked = names.iter().map(|name| compare::find_company(&companies, name)).collect::<Result<Vec<_>, _>>()?;
+
+    let vault_path = flag_or_env(args, "output", "FAIR_RENDERER_OUTPUT").map(PathBuf::from);
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let priorities: Vec<Option<String>> = picked.iter()
+        .map(|c| vault_path.as_ref().and_then(|v| prep::note_field(&v.join(&folders.companies), c, "Priority")))
+        .collect();
+    let text = compare::render_comparison(&picked, &priorities, &my_majors_arg(args));
+
+    match vault_path {
+        Some(vault_path) => {
+            let names: Vec<&str> = picked.iter().map(|c| c.name.as_str()).collect();
+            let file_path = vault_path.join(&folders.comparisons).join(format!("{}.md", links::note_name(&names.join(" vs "))));
+            fs::create_dir_all(vault_path.join(&folders.comparisons))?;
+            write_file(&file_path, text)?;
+            println!("{}", file_path.to_string_lossy());
+        },
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+fn export_subset_command(args: &ArgMatches) -> Result<(), Error> {
+    let Some(input_path) = flag_or_env(args, "input", "FAIR_RENDERER_INPUT") else {
+        return Err(Error::Usage("export-subset needs the input data: --input [path_to_input]".to_string()));
+    };
+    let vault_path = flag_value(args, "vault").map(PathBuf::from);
+    let filters = subset::parse_filters(&flag_values(args, "filter"), vault_path.is_some())?;
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let companies_dir = vault_path.map(|v| v.join(&folders.companies));
+
+    let input_data = fetch::merge_pages(subcommand_input(args, &input_path)?);
+    // notes are only looked up by name, so they have to be named the way the render named them
+    let naming = naming::Naming::new(note_name_arg(args)?.as_deref(), &input_data, &parse_input(&input_data).unwrap_or_default())?;
+    let (text, kept, total) = subset::export_subset(&input_data, &filters, companies_dir.as_deref(), &naming)?;
+    match flag_value(args, "output") {
+        Some(path) => {
+            write_file(&path, text)?;
+            term::info(&format!("kept {} of {} companies in {}", kept, total, path));
+        },
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+fn extract_command(args: &ArgMatches) -> Result<(), Error> {
+    let vault_path = PathBuf::from(flag_value(args, "vault-path").unwrap_or_default());
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let key_prefix = flag_or_env(args, "key-prefix", "FAIR_RENDERER_KEY_PREFIX").unwrap_or_default();
+    let out_path = flag_value(args, "output");
+
+    // picked by the output's extension unless --format says otherwise, json to stdout by default
+    let format = match (flag_value(args, "format"), &out_path) {
+        (Some(format), _) => format,
+        (None, Some(path)) if path.ends_with(".csv") => "csv".to_string(),
+        (None, _) => "json".to_string(),
+    };
+    let extracted = extract::extract_vault(&vault_path, &folders, &key_prefix)?;
+    let text = match format.as_str() {
+        "json" => extract::render_json(&extracted),
+        "csv" => extract::render_csv(&extracted),
+        _ => return Err(Error::Usage(format!("unknown extract format: {}, expected json or csv", format))),
+    };
+    match out_path {
+        Some(path) => {
+            write_file(&path, text)?;
+            term::info(&format!("extracted {} companies to {}", extracted.len(), path));
+        },
+        None => print!("{}", text),
+    }
+    Ok(())
+}
+
+fn summary_command(args: &ArgMatches) -> Result<(), Error> {
+    let vault_path = PathBuf::from(flag_value(args, "vault-path").unwrap_or_default());
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let key_prefix = flag_or_env(args, "key-prefix", "FAIR_RENDERER_KEY_PREFIX").unwrap_or_default();
+    let labels = match flag_or_env(args, "lang", "FAIR_RENDERER_LANG") {
+        Some(lang) => {
+            let template_path = flag_or_env(args, "template", "FAIR_RENDERER_TEMPLATE")
+                .unwrap_or_else(|| DEFAULT_TEMPLATE.to_string());
+            labels::Labels::load(&PathBuf::from(template_path), &lang)?
+        },
+        None => labels::Labels::default(),
+    };
+
+    // the fair's name and date for --note-name come from the input when it's given
+    let input_data = match flag_or_env(args, "input", "FAIR_RENDERER_INPUT") {
+        Some(input_path) => fetch::merge_pages(subcommand_input(args, &input_path)?),
+        None => Vec::new(),
+    };
+    let naming = naming::Naming::new(note_name_arg(args)?.as_deref(), &input_data, &parse_input(&input_data).unwrap_or_default())?;
+
+    let extracted = extract::extract_vault(&vault_path, &folders, &key_prefix)?;
+    let text = summary::render_summary(&extracted, &vault_path.join(&folders.follow_ups), &labels);
+    let file_path = vault_path.join(naming.index_file("Summary"));
+    write_file(&file_path, text)?;
+    println!("{}", file_path.to_string_lossy());
+    Ok(())
+}
+
+// the input a subcommand reads, given after its name or with --input
+fn input_path_arg(args: &ArgMatches, command: &str) -> Result<String, Error> {
+    match flag_value(args, "input-path").or_else(|| flag_or_env(args, "input", "FAIR_RENDERER_INPUT")) {
+        Some(path) => Ok(path),
+        None => Err(Error::Usage(format!("expected an input path: {} [input_path]", command))),
+    }
+}
+
+fn validate_command(args: &ArgMatches) -> Result<ExitCode, Error> {
+    let input_path = input_path_arg(args, "validate")?;
+    let problems = validate::validate(&fetch::merge_pages(subcommand_input(args, &input_path)?))?;
+    for problem in &problems {
+        println!("{}", problem);
+    }
+    match problems.len() {
+        0 => term::info(&format!("{} looks good", input_path)),
+        count => term::info(&format!("found {} problems in {}", count, input_path)),
+    }
+    Ok(if problems.is_empty() { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn stats_command(args: &ArgMatches) -> Result<(), Error> {
+    let input_path = input_path_arg(args, "stats")?;
+    let companies = parse_input(&fetch::merge_pages(subcommand_input(args, &input_path)?))?;
+    print!("{}", stats::render_stats(&companies));
+    Ok(())
+}
+
+// the merged pages as they'd be rendered, to keep a copy of an export or look at what the api returns
+fn fetch_command(args: &ArgMatches) -> Result<(), Error> {
+    let Some(url) = flag_value(args, "url").or_else(|| env_value("FAIR_RENDERER_FETCH")) else {
+        return Err(Error::Usage("expected a url: fetch [url] [-o path]".to_string()));
+    };
+    let token = flag_or_env(args, "token", "FAIR_RENDERER_TOKEN").or_else(auth::stored_token);
+    let input_data = fetch::merge_pages(fetch::fetch_input(&url, token.as_deref())?);
+    match flag_value(args, "output") {
+        Some(path) => {
+            write_file(&path, input_data)?;
+            term::info(&format!("saved {}", path));
+        },
+        None => io::stdout().write_all(&input_data)?,
+    }
+    Ok(())
+}
+
+fn lint_command(args: &ArgMatches) -> Result<ExitCode, Error> {
+    let vault_path = PathBuf::from(flag_value(args, "vault-path").unwrap_or_default());
+    let class_name = class_name_arg(args)?;
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let remaining = lint::lint_vault(&vault_path, switch(args, "fix"), &class_name, &folders)?;
+    Ok(if remaining == 0 { ExitCode::SUCCESS } else { ExitCode::FAILURE })
+}
+
+fn parse_cli(args: &ArgMatches, update: bool) -> Result<CliArgs, Error> {
+    let tasks_format = switch(args, "tasks-format");
+
+    // --link-fields is shorthand for the graph friendly setup, explicit --field-format still wins
+    let mut field_formats = fields::FieldFormats::default();
+    if switch(args, "link-fields") {
+        for field in LINK_FIELDS {
+            field_formats.set(field, fields::FieldFormat::Link);
+        }
+    }
+    let explicit_formats = fields::FieldFormats::parse(&flag_values(args, "field-format"))?;
+    for (field, format) in &explicit_formats.0 {
+        field_formats.set(field, *format);
+    }
+    let select_fields = fields::parse_select_fields(&flag_values(args, "select-fields"), &mut field_formats, &explicit_formats)?;
+
+    let key_prefix = check_key_prefix(flag_or_env(args, "key-prefix", "FAIR_RENDERER_KEY_PREFIX").unwrap_or_default())?;
+
+    let class_name = class_name_arg(args)?;
+    let class_per_fair = switch(args, "class-per-fair");
+    let note_name = note_name_arg(args)?;
+    let folders = folders::Folders::parse(&flag_values(args, "folder"))?;
+    let output = plain::Output {
+        extension: match flag_or_env(args, "extension", "FAIR_RENDERER_EXTENSION") {
+            Some(extension) => plain::check_extension(&extension)?,
+            None => "md".to_string(),
+        },
+        frontmatter: !switch(args, "no-frontmatter"),
+        flat: switch(args, "flat"),
+    };
+    // a plain folder of notes is written fresh every time, there's no vault to compare or pick up
+    if !output.is_vault() && ["check", "resume", "open", "register"].iter().any(|name| switch(args, name)) {
+        return Err(Error::Usage("--check, --resume, --open and --register only work on a vault, not with --extension, --no-frontmatter or --flat".to_string()));
+    }
+
+    let input_paths = input_paths_arg(args)?;
+    let fetch_url = flag_or_env(args, "fetch", "FAIR_RENDERER_FETCH");
+    let columns = spreadsheet::parse_columns(&flag_values(args, "column"))?;
+    let mapping = mapping_arg(args)?;
+    let (format, layout) = format_arg(args)?;
+    let encoding = encoding_arg(args)?;
+    let jobs_path = flag_or_env(args, "jobs", "FAIR_RENDERER_JOBS");
+    // the keyring is only asked when it's actually needed since it can pop up an unlock prompt
+    let token = match flag_or_env(args, "token", "FAIR_RENDERER_TOKEN") {
+        Some(token) => Some(token),
+        None if fetch_url.is_some() => auth::stored_token(),
+        None => None,
+    };
+
+    let interval = match flag_or_env(args, "interval", "FAIR_RENDERER_INTERVAL") {
+        Some(value) => match fetch::parse_interval(&value) {
+            Some(interval) => Some(interval),
+            None => return Err(Error::Usage(format!("invalid interval: {}, expected something like 30m or 6h", value))),
+        },
+        None => None,
+    };
+    if interval.is_some() && input_paths.iter().any(|p| p == "-") {
+        return Err(Error::Usage("--interval needs input it can read again, stdin can only be read once".to_string()));
+    }
+    // rerunning on a timer only makes sense if each run refreshes the same vault, and picking up an
+    // unfinished render means refreshing what it already wrote
+    let resume = switch(args, "resume");
+    let is_update = update || interval.is_some() || resume;
+    let template_data_path = flag_or_env(args, "template", "FAIR_RENDERER_TEMPLATE");
+    let output_data_path = flag_or_env(args, "output", "FAIR_RENDERER_OUTPUT");
+    let followup_template_path = flag_or_env(args, "followup-template", "FAIR_RENDERER_FOLLOWUP_TEMPLATE");
+    let note_stub_path = flag_or_env(args, "note-stub", "FAIR_RENDERER_NOTE_STUB");
+    // parsed up front so a typo fails before we spend time rendering
+    let exports = flag_values(args, "export").iter().map(|spec| export::parse_spec(spec)).collect::<Result<Vec<_>, Error>>()?;
+    let upload_target = match flag_or_env(args, "upload", "FAIR_RENDERER_UPLOAD") {
+        Some(spec) => Some(upload::UploadTarget::parse(&spec)?),
+        None => None,
+    };
+    let webhook_url = flag_or_env(args, "webhook", "FAIR_RENDERER_WEBHOOK");
+    let lang = flag_or_env(args, "lang", "FAIR_RENDERER_LANG");
+    let translate_cmd = flag_or_env(args, "translate-cmd", "FAIR_RENDERER_TRANSLATE_CMD");
+    let translate_to = flag_or_env(args, "translate-to", "FAIR_RENDERER_TRANSLATE_TO").unwrap_or_else(|| "en".to_string());
+    let summarize_cmd = flag_or_env(args, "summarize-cmd", "FAIR_RENDERER_SUMMARIZE_CMD");
+    let summarize = switch(args, "summarize") || summarize_cmd.is_some();
+    let summary_sentences = match flag_or_env(args, "summary-sentences", "FAIR_RENDERER_SUMMARY_SENTENCES") {
+        Some(value) => match value.parse::<usize>() {
+            Ok(sentences) if sentences > 0 => sentences,
+            _ => return Err(Error::Usage(format!("invalid --summary-sentences: {}, expected a number above 0", value))),
+        },
+        None => 2,
+    };
+    // a mini vault is meant to be small enough to sync to a phone, so logos stay links
+    let mini = switch(args, "mini");
+    let download_logos = switch(args, "download-logos") && !mini;
+    let mut limits = limits::Limits::default();
+    if let Some(value) = flag_or_env(args, "max-downloads", "FAIR_RENDERER_MAX_DOWNLOADS") {
+        match value.parse::<usize>() {
+            Ok(max) => limits.max_downloads = Some(max),
+            Err(_) => return Err(Error::Usage(format!("invalid --max-downloads: {}, expected a number", value))),
+        }
+    }
+    if let Some(value) = flag_or_env(args, "max-file-size", "FAIR_RENDERER_MAX_FILE_SIZE") {
+        limits.max_file_size = limits::parse_size(&value)?;
+    }
+    let max_field_size = match flag_or_env(args, "max-field-size", "FAIR_RENDERER_MAX_FIELD_SIZE") {
+        Some(value) => limits::parse_size(&value)? as usize,
+        None => oversize::DEFAULT_MAX_FIELD_SIZE,
+    };
+    // a mini vault is all the notes there are, so nothing gets moved out of them
+    let oversized = match flag_or_env(args, "oversized", "FAIR_RENDERER_OVERSIZED") {
+        _ if mini => oversize::Oversized::Truncate,
+        Some(mode) => oversize::Oversized::parse(&mode)?,
+        None => oversize::Oversized::Move,
+    };
+    if let Some(value) = flag_or_env(args, "timeout", "FAIR_RENDERER_TIMEOUT") {
+        match fetch::parse_interval(&value) {
+            Some(timeout) => limits.timeout = timeout,
+            None => return Err(Error::Usage(format!("invalid timeout: {}, expected something like 10s or 2m", value))),
+        }
+    }
+    let prep = switch(args, "prep");
+    let blocklist = match flag_or_env(args, "blocklist", "FAIR_RENDERER_BLOCKLIST") {
+        Some(path) => {
+            let mode = match flag_or_env(args, "blocklist-mode", "FAIR_RENDERER_BLOCKLIST_MODE") {
+                Some(mode) => blocklist::BlockMode::parse(&mode)?,
+                None => blocklist::BlockMode::Flag,
+            };
+            Some(blocklist::Blocklist::load(&path, mode)?)
+        },
+        None => None,
+    };
+    let starred = match flag_or_env(args, "starred", "FAIR_RENDERER_STARRED") {
+        Some(path) => Some(starred::Starred::load(&path)?),
+        None => None,
+    };
+    let industries = match flag_or_env(args, "industry-map", "FAIR_RENDERER_INDUSTRY_MAP") {
+        Some(path) => industry::IndustryModel::load(&path)?,
+        None => industry::IndustryModel::default(),
+    };
+    // read here so a mistake in the template's views fails before anything is rendered
+    let views = views::load(Path::new(template_data_path.as_deref().unwrap_or(DEFAULT_TEMPLATE)))?;
+    let my_majors = my_majors_arg(args);
+    let team = team_arg(args);
+    let prune_mode = match flag_or_env(args, "prune-logos", "FAIR_RENDERER_PRUNE_LOGOS") {
+        Some(mode) => attachments::PruneMode::parse(&mode)?,
+        None => attachments::PruneMode::Archive,
+    };
+    let policy = match (switch(args, "strict"), switch(args, "lenient")) {
+        (true, true) => return Err(Error::Usage("--strict and --lenient can't be used together".to_string())),
+        (true, false) => policy::Policy::Strict,
+        (false, true) => policy::Policy::Lenient,
+        (false, false) => policy::Policy::Warn,
+    };
+
+    Ok(CliArgs {
+        input_paths,
+        columns,
+        mapping,
+        format,
+        layout,
+        encoding,
+        jobs_path,
+        fetch_url,
+        token,
+        interval,
+        update: is_update,
+        resume,
+        check: switch(args, "check"),
+        output_path: output_data_path,
+        template_path: template_data_path,
+        followup_template_path,
+        note_stub_path,
+        exports,
+        upload_target,
+        webhook_url,
+        lang,
+        field_formats,
+        select_fields,
+        key_prefix,
+        class_name,
+        class_per_fair,
+        note_name,
+        folders,
+        output,
+        translate_cmd,
+        translate_to,
+        summarize,
+        summary_sentences,
+        summarize_cmd,
+        tasks_format,
+        download_logos,
+        limits,
+        max_field_size,
+        oversized,
+        prep,
+        my_majors,
+        team,
+        claims: switch(args, "claims"),
+        blocklist,
+        industries,
+        starred,
+        session: flag_or_env(args, "session", "FAIR_RENDERER_SESSION"),
+        mini,
+        views,
+        build_info: !switch(args, "no-build-info"),
+        skip_invalid: switch(args, "skip-invalid"),
+        report_path: flag_or_env(args, "report", "FAIR_RENDERER_REPORT"),
+        picked: None,
+        hooks: Vec::new(),
+        prune_mode,
+        policy,
+        open: switch(args, "open"),
+        register: switch(args, "register"),
+    })
+}
+
+// --input can be repeated or followed by several paths, which is also what a glob the shell
+// expanded looks like, globs it didn't expand are matched here
+fn input_paths_arg(args: &ArgMatches) -> Result<Vec<String>, Error> {
+    let mut paths = flag_values(args, "input");
+    if paths.is_empty() {
+        paths.extend(env_value("FAIR_RENDERER_INPUT"));
+    }
+    inputs::expand_inputs(&paths)
+}
+
+fn note_name_arg(args: &ArgMatches) -> Result<Option<String>, Error> {
+    let note_name = flag_or_env(args, "note-name", "FAIR_RENDERER_NOTE_NAME");
+    if let Some(scheme) = &note_name {
+        naming::check_scheme(scheme)?;
+    }
+    Ok(note_name)
+}
+
+fn mapping_arg(args: &ArgMatches) -> Result<Option<mapping::Mapping>, Error> {
+    match flag_or_env(args, "mapping", "FAIR_RENDERER_MAPPING") {
+        Some(path) => Ok(Some(mapping::Mapping::load(Path::new(&path))?)),
+        None => Ok(None),
+    }
+}
+
+// --format names the platform, how the input is laid out, or both as csv,handshake
+fn format_arg(args: &ArgMatches) -> Result<(Option<String>, Option<inputs::Layout>), Error> {
+    let Some(value) = flag_or_env(args, "format", "FAIR_RENDERER_FORMAT") else { return Ok((None, None)) };
+    let (mut format, mut layout) = (None, None);
+    for name in value.split(',').map(str::trim) {
+        match inputs::Layout::parse(name) {
+            Some(parsed) => layout = Some(parsed),
+            None => match sources::find_source(name) {
+                Ok(source) => format = Some(source.name().to_string()),
+                Err(e) => return Err(Error::Usage(format!("{}, or a layout: {}", e, inputs::LAYOUTS.join(", ")))),
+            },
+        }
+    }
+    Ok((format, layout))
+}
+
+fn encoding_arg(args: &ArgMatches) -> Result<Option<encoding::Encoding>, Error> {
+    match flag_or_env(args, "encoding", "FAIR_RENDERER_ENCODING") {
+        Some(name) => match encoding::Encoding::parse(&name) {
+            Some(encoding) => Ok(Some(encoding)),
+            None => Err(Error::Usage(format!("unknown encoding: {}, expected one of {}", name, encoding::ENCODINGS.join(", ")))),
+        },
+        None => Ok(None),
+    }
+}
+
+// subcommands take their input the same ways a render does, a file, a spreadsheet or stdin
+fn subcommand_input(args: &ArgMatches, input_path: &str) -> Result<Vec<u8>, Error> {
+    let input_data = match input_path {
+        "-" => {
+            let mut input_data = Vec::new();
+            io::stdin().read_to_end(&mut input_data).map_err(|source| Error::Input { path: "stdin".to_string(), source })?;
+            encoding::to_utf8(inputs::decompress(input_data)?, encoding_arg(args)?)?
+        },
+        path if spreadsheet::is_spreadsheet(path) => {
+            return spreadsheet::read_spreadsheet(Path::new(path), &spreadsheet::parse_columns(&flag_values(args, "column"))?);
+        },
+        path => {
+            let input_data = fs::read(path).map_err(|source| Error::Input { path: path.to_string(), source })?;
+            encoding::to_utf8(inputs::decompress(input_data)?, encoding_arg(args)?)?
+        },
+    };
+    let input_data = inputs::normalize(fetch::merge_pages(input_data), format_arg(args)?.1, &spreadsheet::parse_columns(&flag_values(args, "column"))?)?;
+    match mapping_arg(args)? {
+        Some(mapping) => mapping.apply(fetch::merge_pages(input_data)),
+        None => Ok(input_data),
+    }
+}
+
+// --class-name, the template's company class when it isn't given
+fn class_name_arg(args: &ArgMatches) -> Result<String, Error> {
+    check_class_name(flag_or_env(args, "class-name", "FAIR_RENDERER_CLASS_NAME").unwrap_or_else(|| "company".to_string()))
+}
+
+// --my-majors can be repeated, a plain list is the user's own and name=list is a teammate's,
+// the user's own come back without a name
+fn majors_sets(args: &ArgMatches) -> Vec<team::Member> {
+    let mut values = flag_values(args, "my-majors");
+    if values.is_empty() && let Ok(value) = std::env::var("FAIR_RENDERER_MY_MAJORS") {
+        values.push(value);
+    }
+    values.iter().map(|value| {
+        let (name, majors) = value.split_once('=').unwrap_or(("", value));
+        team::Member {
+            name: name.trim().to_string(),
+            majors: majors.split(',').map(|m| m.trim().to_string()).filter(|m| !m.is_empty()).collect(),
+        }
+    }).collect()
+}
+
+fn my_majors_arg(args: &ArgMatches) -> Vec<String> {
+    majors_sets(args).into_iter().filter(|m| m.name.is_empty()).flat_map(|m| m.majors).collect()
+}
+
+fn team_arg(args: &ArgMatches) -> Vec<team::Member> {
+    majors_sets(args).into_iter().filter(|m| !m.name.is_empty()).collect()
+}
+
+// the value of a flag, none when it wasn't passed or the command doesn't take it
+fn flag_value(args: &ArgMatches, name: &str) -> Option<String> {
+    args.try_get_one::<String>(name).ok().flatten().cloned()
+}
+
+// same as flag_value but for flags that can be passed more than once
+fn flag_values(args: &ArgMatches, name: &str) -> Vec<String> {
+    match args.try_get_many::<String>(name) {
+        Ok(Some(values)) => values.cloned().collect(),
+        _ => Vec::new(),
+    }
+}
+
+fn switch(args: &ArgMatches, name: &str) -> bool {
+    args.try_get_one::<bool>(name).ok().flatten().copied().unwrap_or(false)
+}
+
+// flags win, otherwise falls back to the environment variable so things like the api token
+// can stay out of shell history
+fn flag_or_env(args: &ArgMatches, name: &str, env_name: &str) -> Option<String> {
+    flag_value(args, name).or_else(|| env_value(env_name))
+}
+
+fn env_value(name: &str) -> Option<String> {
+    std::env::var(name).ok().filter(|value| !value.is_empty())
+}
+
+// the binary's whole main, the exit code is handed back rather than exiting from in here
+pub fn run() -> ExitCode {
+    match real_main(std::env::args().skip(1).collect()) {
+        Ok(code) => code,
+        Err(e) => {
+            term::error(&e.to_string());
+            ExitCode::from(exit::code(&e))
+        },
+    }
+}
+
+// -v can be stacked as -vv or -v -v, --quiet wins over any of them
+fn verbosity(args: &ArgMatches) -> u8 {
+    match switch(args, "quiet") {
+        true => term::QUIET,
+        false => (term::NORMAL + args.get_count("verbose")).min(term::TRACE),
+    }
+}
+
+// wrapper so that main prints Error Display rather than Debug, validate and lint fail with 1 when
+// they find problems without there being an error
+pub fn real_main(args: Vec<String>) -> Result<ExitCode, Error> {
+    let matches = match cli::parse(args)? {
+        Ok(matches) => matches,
+        Err(e) => {
+            let _ = e.print();
+            return Ok(ExitCode::from(e.exit_code() as u8));
+        },
+    };
+    let Some((name, args)) = matches.subcommand() else { return Ok(ExitCode::SUCCESS) };
+    term::init(switch(args, "no-color"), verbosity(args));
+
+    let ran = match name {
+        "validate" => return validate_command(args),
+        "lint" => return lint_command(args),
+        "render" | "update" => render_command(args, name == "update"),
+        "fetch" => fetch_command(args),
+        "stats" => stats_command(args),
+        "browse" => browse_command(args),
+        "completions" => {
+            print!("{}", completions::generate(&flag_value(args, "shell").unwrap_or_default())?);
+            Ok(())
+        },
+        "auth" => match args.subcommand_name() {
+            Some("login") => auth::login(),
+            _ => auth::logout(),
+        },
+        "anonymize" => anonymize_command(args),
+        "compare" => compare_command(args),
+        "export-subset" => export_subset_command(args),
+        "extract" => extract_command(args),
+        "summary" => summary_command(args),
+        _ => Err(Error::Other(format!("unknown subcommand {}", name))),
+    };
+    ran.map(|()| ExitCode::SUCCESS)
+}
+
+fn render_command(args: &ArgMatches, update: bool) -> Result<(), Error> {
+    let cli_args = parse_cli(args, update)?;
+    needs_input(&cli_args)?;
+
+    let interval = match cli_args.interval {
+        Some(interval) => interval,
+        None => {
+            let started = Instant::now();
+            let input_data = read_input(&cli_args, &mut std::io::stdin().lock());
+            return render_once(&cli_args, input_data, started);
+        },
+    };
+
+    // keeps going on errors since the next fetch might work, ctrl-c to stop
+    let mut opened = false;
+    loop {
+        let started = Instant::now();
+        let mut report = report::RenderReport::default();
+        let rendered = read_input(&cli_args, &mut std::io::empty()).and_then(|input_data| render_run(&cli_args, input_data, &mut report));
+        term::done();
+        if let Err(e) = save_report(&cli_args, &report, started, &rendered) {
+            term::error(&e.to_string());
+        }
+        match rendered {
+            Ok(()) if !opened => {
+                after_render(&cli_args);
+                opened = true;
+            },
+            Ok(()) => {},
+            Err(e) => term::error(&e.to_string()),
+        }
+        term::debug(&format!("run took {}, next run in {} seconds", term::elapsed(started), interval.as_secs()));
+        std::thread::sleep(interval);
+    }
+}
+
+// the library hands its input over already read, the command line has to say where it is
+fn needs_input(cli_args: &CliArgs) -> Result<(), Error> {
+    match cli_args.input_paths.is_empty() && cli_args.fetch_url.is_none() {
+        true => Err(Error::Usage("must supply input data: --input [path_to_input] or --fetch [url]".to_string())),
+        false => Ok(()),
+    }
+}
+
+// a single render rather than one of --interval's, the input is passed in already read, or not
+fn render_once(cli_args: &CliArgs, input_data: Result<Vec<u8>, Error>, started: Instant) -> Result<(), Error> {
+    let mut report = report::RenderReport::default();
+    let rendered = input_data.and_then(|input_data| render_run(cli_args, input_data, &mut report));
+    term::done();
+    save_report(cli_args, &report, started, &rendered)?;
+    rendered?;
+    term::debug(&format!("done in {}", term::elapsed(started)));
+    after_render(cli_args);
+    Ok(())
+}
+
+// the companies in the input to pick from before rendering only those, without -o the picked names are printed
+#[cfg(feature = "tui")]
+fn browse_command(args: &ArgMatches) -> Result<(), Error> {
+    let mut cli_args = parse_cli(args, false)?;
+    needs_input(&cli_args)?;
+    let input_data = read_input(&cli_args, &mut std::io::stdin().lock())?;
+    // only for the list, the render parses it again the way --strict or --lenient say
+    let companies = sources::pick_source(cli_args.format.as_deref(), &input_data)?.parse(&input_data, policy::Policy::Lenient)?;
+    term::done();
+
+    let Some(picked) = browse::browse(&companies)? else { return Ok(()) };
+    let picked: BTreeSet<String> = picked.iter().map(|i| companies[*i].name.clone()).collect();
+    if picked.is_empty() {
+        term::info("no companies were picked, nothing to render");
+        return Ok(());
+    }
+    if cli_args.output_path.is_none() {
+        for name in &picked {
+            println!("{}", name);
+        }
+        return Ok(());
+    }
+    cli_args.picked = Some(picked);
+    render_once(&cli_args, Ok(input_data), Instant::now())
+}
+
+#[cfg(not(feature = "tui"))]
+fn browse_command(_args: &ArgMatches) -> Result<(), Error> {
+    Err(missing_feature("browse", "tui"))
+}
+
+// a failed run still gets its report, with whatever it got through before the error
+fn save_report(cli_args: &CliArgs, report: &report::RenderReport, started: Instant, rendered: &Result<(), Error>) -> Result<(), Error> {
+    let Some(path) = &cli_args.report_path else { return Ok(()) };
+    let error = rendered.as_ref().err().map(|e| e.to_string());
+    let json = report.to_json(started.elapsed(), error.as_deref());
+    state::write_atomic(Path::new(path), serde_json::to_string_pretty(&json).unwrap_or_default() + "\n")
+}
+
+// the vault is already written at this point, not being able to hand it to obsidian isn't worth failing over
+fn after_render(cli_args: &CliArgs) {
+    let Some(output_path) = &cli_args.output_path else { return };
+    // registered first so --open finds it in obsidian's vault list
+    if cli_args.register {
+        match open::register_vault(&PathBuf::from(output_path)) {
+            Ok(true) => term::info("added the vault to obsidian's vault switcher"),
+            Ok(false) => term::debug("obsidian already knows the vault"),
+            Err(e) => term::error(&e.to_string()),
+        }
+    }
+    if cli_args.open {
+        match open::open_vault(&PathBuf::from(output_path)) {
+            Ok(()) => term::debug("opened the vault in obsidian"),
+            Err(e) => term::error(&e.to_string()),
+        }
+    }
+}
+
+// stdin is passed in rather than read here so a render can be handed its input from anywhere
+fn read_input(cli_args: &CliArgs, stdin: &mut dyn Read) -> Result<Vec<u8>, Error> {
+    if let Some(url) = &cli_args.fetch_url {
+        term::phase("fetching", None);
+        let input_data = fetch::merge_pages(fetch::fetch_input(url, cli_args.token.as_deref())?);
+        return match &cli_args.mapping {
+            Some(mapping) => mapping.apply(input_data),
+            None => Ok(input_data),
+        };
+    }
+    if cli_args.input_paths.is_empty() {
+        return Err(Error::Usage("must supply input data: --input [path_to_input] or --fetch [url]".to_string()));
+    }
+
+    let mut inputs = Vec::new();
+    for path in &cli_args.input_paths {
+        let input_data = match path.as_str() {
+            // use ./- for an actual file with that name
+            "-" => {
+                let mut input_data = Vec::new();
+                stdin.read_to_end(&mut input_data).map_err(|source| Error::Input { path: "stdin".to_string(), source })?;
+                input_data
+            },
+            // use ./clipboard for an actual file with that name
+            "clipboard" => clipboard::read_clipboard()?,
+            path if spreadsheet::is_spreadsheet(path) => {
+                inputs.push((path.to_string(), spreadsheet::read_spreadsheet(Path::new(path), &cli_args.columns)?));
+                continue;
+            },
+            path => fs::read(path).map_err(|source| Error::Input { path: path.to_string(), source })?,
+        };
+        let input_data = encoding::to_utf8(inputs::decompress(input_data)?, cli_args.encoding)?;
+        let input_data = inputs::normalize(fetch::merge_pages(input_data), cli_args.layout, &cli_args.columns)?;
+        let input_data = match &cli_args.mapping {
+            Some(mapping) => mapping.apply(input_data)?,
+            None => input_data,
+        };
+        inputs.push((path.clone(), input_data));
+    }
+    inputs::merge_inputs(inputs)
+}
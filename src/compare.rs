@@ -0,0 +1,78 @@
+use crate::{CompanyEntry, Error, links, prep};
+
+// table cells can't hold line breaks, <br> renders fine in obsidian's tables
+const CELL_BREAK: &str = "<br>";
+
+// exact name first, then a unique partial match so "globex" finds "Globex Corporation"
+pub fn find_company<'a>(companies: &'a [CompanyEntry], query: &str) -> Result<&'a CompanyEntry, Error> {
+    let query_lower = query.to_lowercase();
+    if let Some(company) = companies.iter().find(|c| c.name.to_lowercase() == query_lower) {
+        return Ok(company);
+    }
+
+    let matches: Vec<&CompanyEntry> = companies.iter().filter(|c| c.name.to_lowercase().contains(&query_lower)).collect();
+    match matches.as_slice() {
+        [company] => Ok(company),
+        [] => Err(Error::Other(format!("no company matches {}", query))),
+        _ => {
+            let names: Vec<&str> = matches.iter().take(5).map(|c| c.name.as_str()).collect();
+            Err(Error::Other(format!("{} matches more than one company: {}", query, names.join(", "))))
+        },
+    }
+}
+
+fn cell(text: &str) -> String {
+    match text.trim().is_empty() {
+        true => "-".to_string(),
+        false => text.replace('|', "\\|").replace('\n', " "),
+    }
+}
+
+// one column per company, priorities come from the existing notes when there is a vault
+pub fn render_comparison(companies: &[&CompanyEntry], priorities: &[Option<String>], my_majors: &[String]) -> String {
+    let names: Vec<&str> = companies.iter().map(|c| c.name.as_str()).collect();
+    let mut text = format!("# {}\n\n", names.join(" vs "));
+
+    let header: Vec<String> = companies.iter().map(|c| links::company_link(c).replace('|', "\\|")).collect();
+    text.push_str(&format!("| | {} |\n", header.join(" | ")));
+    text.push_str(&format!("|---|{}\n", "---|".repeat(companies.len())));
+
+    let mut rows: Vec<(&str, Vec<String>)> = Vec::new();
+
+    if priorities.iter().any(Option::is_some) {
+        rows.push(("Priority", priorities.iter().map(|p| p.clone().unwrap_or_default()).collect()));
+    }
+    if !my_majors.is_empty() {
+        rows.push(("Match", companies.iter().map(|c| match prep::match_score(c, my_majors) {
+            Some(score) => format!("{}%", score),
+            None => String::new(),
+        }).collect()));
+    }
+    rows.push(("Majors", companies.iter().map(|c| {
+        // with my majors known only the overlap matters, otherwise a count keeps the table readable
+        let mine: Vec<&str> = c.majors.iter()
+            .filter(|m| my_majors.iter().any(|mine| mine.eq_ignore_ascii_case(m)))
+            .map(String::as_str)
+            .collect();
+        match (mine.is_empty(), c.majors.len()) {
+            (_, 0) => String::new(),
+            (false, total) => format!("{} ({} total)", mine.join(", "), total),
+            (true, total) => format!("{} majors", total),
+        }
+    }).collect()));
+    rows.push(("Job types", companies.iter().map(|c| c.job_types.join(", ")).collect()));
+    rows.push(("Positions", companies.iter().map(|c| c.job_titles.clone()).collect()));
+    rows.push(("Sponsorship", companies.iter().map(|c| c.work_authorization.clone()).collect()));
+    rows.push(("Sessions", companies.iter().map(|c| {
+        c.attending_sessions.iter().map(|s| s.display_name.as_str()).collect::<Vec<&str>>().join(CELL_BREAK)
+    }).collect()));
+    rows.push(("Booth", companies.iter().map(|c| c.booth.clone().unwrap_or_default()).collect()));
+    rows.push(("Deadline", companies.iter().map(|c| c.deadline.clone().unwrap_or_default()).collect()));
+
+    for (label, values) in rows {
+        let cells: Vec<String> = values.iter().map(|v| cell(v)).collect();
+        text.push_str(&format!("| {} | {} |\n", label, cells.join(" | ")));
+    }
+
+    text
+}
@@ -0,0 +1,18 @@
+use clap_complete::Shell;
+
+use crate::{Error, cli};
+
+// generated from the same definition the parsing uses, so a new flag or subcommand completes right away
+pub fn generate(shell: &str) -> Result<String, Error> {
+    let shell = match shell {
+        "bash" => Shell::Bash,
+        "zsh" => Shell::Zsh,
+        "fish" => Shell::Fish,
+        "powershell" => Shell::PowerShell,
+        "elvish" => Shell::Elvish,
+        _ => return Err(Error::Other(format!("unknown shell: {}, expected bash, zsh, fish, powershell or elvish", shell))),
+    };
+    let mut script = Vec::new();
+    clap_complete::generate(shell, &mut cli::command(), "fair_renderer", &mut script);
+    Ok(String::from_utf8_lossy(&script).to_string())
+}
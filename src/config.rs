@@ -0,0 +1,147 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use clap::parser::ValueSource;
+use clap::{Arg, ArgAction, ArgMatches, Command, ValueHint};
+use toml::{Table, Value};
+
+use crate::Error;
+
+// looked for in the working directory when --config isn't given
+const DEFAULT_CONFIG: &str = "fair_renderer.toml";
+
+// what a value turns into on the command line, dates and floats are no flag's value
+fn text(value: &Value) -> Option<String> {
+    match value {
+        Value::String(text) => Some(text.clone()),
+        Value::Integer(number) => Some(number.to_string()),
+        _ => None,
+    }
+}
+
+// ./fair_renderer.toml is only read when it's there, one named with --config has to be
+fn config_path(matches: &ArgMatches) -> Result<Option<PathBuf>, Error> {
+    let named = match matches.get_one::<String>("config") {
+        Some(path) => Some(path.clone()),
+        None => std::env::var("FAIR_RENDERER_CONFIG").ok().filter(|p| !p.is_empty()),
+    };
+    match named {
+        Some(path) if !Path::new(&path).is_file() => Err(Error::Usage(format!("config file {} doesn't exist", path))),
+        Some(path) => Ok(Some(PathBuf::from(path))),
+        None => Ok(Some(PathBuf::from(DEFAULT_CONFIG)).filter(|p| p.is_file())),
+    }
+}
+
+// the flag a key stands for in this command, a key only another command takes is skipped, as is
+// one this command takes with another meaning, like extract's --format or fetch's --output
+fn find_flag<'a>(command: &'a Command, subcommand: &'a Command, name: &str) -> Result<Option<&'a Arg>, ()> {
+    let find = |command: &'a Command| command.get_arguments().find(|a| a.get_long() == Some(name));
+    let render = command.find_subcommand("render").and_then(find);
+    match find(subcommand).or_else(|| find(command)) {
+        Some(arg) if render.is_some_and(|r| r.get_help().map(ToString::to_string) != arg.get_help().map(ToString::to_string)) => Ok(None),
+        Some(arg) => Ok(Some(arg)),
+        None if command.get_subcommands().any(|c| find(c).is_some()) => Ok(None),
+        None => Err(()),
+    }
+}
+
+// the config as the flags it stands for, added after the command line ones so the parsing
+// doesn't change, a key the command line or its environment variable already sets is skipped
+// so those always win, paths are relative to the config file
+pub fn with_config(args: Vec<String>, command: &Command) -> Result<Vec<String>, Error> {
+    let Some(subcommand) = args.first().and_then(|a| command.find_subcommand(a)) else { return Ok(args) };
+    // the command line alone, to see what it sets, a mistake in it is left for the real parse to report
+    let matches = match command.clone().try_get_matches_from(std::iter::once("fair_renderer").chain(args.iter().map(String::as_str))) {
+        Ok(matches) => matches,
+        Err(_) => return Ok(args),
+    };
+    let Some((_, matches)) = matches.subcommand() else { return Ok(args) };
+    let Some(path) = config_path(matches)? else { return Ok(args) };
+    let shown = path.to_string_lossy().to_string();
+    let toml = match fs::read_to_string(&path) {
+        Ok(toml) => toml,
+        Err(e) => return Err(Error::Usage(format!("could not read config {}: {}", shown, e))),
+    };
+    let entries: Table = match toml::from_str(&toml) {
+        Ok(entries) => entries,
+        Err(e) => return Err(Error::Usage(format!("{}: {}", shown, e.to_string().trim()))),
+    };
+    let base = path.parent().map(Path::to_path_buf).unwrap_or_default();
+
+    let mut added = Vec::new();
+    for (key, value) in entries {
+        let name = key.replace('_', "-");
+        if ["config", "help"].contains(&name.as_str()) {
+            return Err(Error::Usage(format!("{}: {} can't be set in the config", shown, name)));
+        }
+        let flag = match find_flag(command, subcommand, &name) {
+            Ok(Some(flag)) => flag,
+            Ok(None) => continue,
+            Err(()) => return Err(Error::Usage(format!("{}: unknown option {}", shown, name))),
+        };
+        let takes_value = flag.get_action().takes_values();
+        let repeatable = matches!(flag.get_action(), ArgAction::Append | ArgAction::Count);
+        let is_path = matches!(flag.get_value_hint(), ValueHint::AnyPath | ValueHint::FilePath | ValueHint::DirPath);
+
+        let long = format!("--{}", name);
+        let on_command_line = matches.value_source(flag.get_id().as_str()) == Some(ValueSource::CommandLine);
+        let env_name = format!("FAIR_RENDERER_{}", name.replace('-', "_").to_uppercase());
+        if on_command_line || std::env::var(&env_name).is_ok_and(|v| !v.is_empty()) {
+            continue;
+        }
+
+        // switches are only the flag, verbose = 2 for -vv
+        if !takes_value {
+            let times = match &value {
+                Value::Boolean(on) => *on as i64,
+                Value::Integer(times) if repeatable => *times,
+                Value::Table(_) => return Err(Error::Usage(format!("{}: {} is a switch, set it with {} = true", shown, name, name))),
+                _ => return Err(Error::Usage(format!("{}: {} should be true or false", shown, name))),
+            };
+            for _ in 0..times {
+                added.push(long.clone());
+            }
+            continue;
+        }
+
+        let values: Vec<String> = match &value {
+            // [column] name = "Company" for --column name=Company
+            Value::Table(fields) => {
+                let mut values = Vec::new();
+                for (field, value) in fields {
+                    let joined = match value {
+                        Value::Array(values) => values.iter().map(text).collect::<Option<Vec<_>>>().map(|v| v.join(",")),
+                        value => text(value),
+                    };
+                    match joined {
+                        Some(joined) => values.push(format!("{}={}", field, joined)),
+                        None => return Err(Error::Usage(format!("{}: {}.{} should be text", shown, name, field))),
+                    }
+                }
+                values
+            },
+            Value::Array(values) if repeatable => match values.iter().map(text).collect::<Option<Vec<_>>>() {
+                Some(values) => values,
+                None => return Err(Error::Usage(format!("{}: {} should be a list of text", shown, name))),
+            },
+            Value::Array(_) => return Err(Error::Usage(format!("{}: {} takes one value, not a list", shown, name))),
+            value => match text(value) {
+                Some(value) => vec![value],
+                None => return Err(Error::Usage(format!("{}: {} should be text", shown, name))),
+            },
+        };
+
+        for value in values {
+            added.push(long.clone());
+            let value = match is_path && value != "-" && value != "clipboard" && Path::new(&value).is_relative() {
+                true => base.join(&value).to_string_lossy().to_string(),
+                false => value,
+            };
+            added.push(value);
+        }
+    }
+
+    let mut args = args;
+    args.extend(added);
+    Ok(args)
+}
@@ -0,0 +1,82 @@
+// where an export keeps the people coming to the booth, on the entry or its employer
+const CONTACT_KEYS: &[&str] = &["recruiters", "representatives", "employer_representatives", "employer_reps", "contacts"];
+
+// a recruiter or rep the export lists for the company, for the follow-up emails after the fair
+#[derive(Debug, Clone)]
+pub struct Contact {
+    pub name: String,
+    pub title: String,
+    pub email: String,
+}
+
+fn text(value: &serde_json::Value) -> String {
+    value.as_str().map(str::trim).unwrap_or_default().to_string()
+}
+
+// a contact as its own object or wrapped in a "user" key, the name whole or split in two
+fn parse_contact(value: &serde_json::Value) -> Option<Contact> {
+    let person = match value["user"].is_object() {
+        true => &value["user"],
+        false => value,
+    };
+    let name = match text(&person["name"]) {
+        name if !name.is_empty() => name,
+        _ => text(&person["full_name"]),
+    };
+    let name = match name.is_empty() {
+        true => format!("{} {}", text(&person["first_name"]), text(&person["last_name"])).trim().to_string(),
+        false => name,
+    };
+    let title = [&value["title"], &value["job_title"], &person["title"], &person["job_title"]].into_iter()
+        .map(text).find(|t| !t.is_empty()).unwrap_or_default();
+    let email = [&person["email"], &person["email_address"], &value["email"]].into_iter()
+        .map(text).find(|e| !e.is_empty()).unwrap_or_default();
+
+    match name.is_empty() && email.is_empty() {
+        true => None,
+        false => Some(Contact { name, title, email }),
+    }
+}
+
+pub fn find_contacts(entry: &serde_json::Value) -> Vec<Contact> {
+    let mut contacts: Vec<Contact> = Vec::new();
+    for parent in [entry, &entry["employer"]] {
+        for key in CONTACT_KEYS {
+            let Some(values) = parent[key].as_array() else { continue };
+            for contact in values.iter().filter_map(parse_contact) {
+                // the same rep can be under the entry and the employer
+                if !contacts.iter().any(|c| c.name == contact.name && c.email == contact.email) {
+                    contacts.push(contact);
+                }
+            }
+        }
+    }
+    contacts
+}
+
+// what goes in the frontmatter, the email when there's no name to go by
+pub fn names(contacts: &[Contact]) -> Vec<String> {
+    contacts.iter()
+        .map(|c| match c.name.is_empty() {
+            true => c.email.clone(),
+            false => c.name.clone(),
+        })
+        .collect()
+}
+
+// a contact as a list item, the email as a mailto link so it opens straight into a new message
+pub fn contact_line(contact: &Contact, mailto: bool) -> String {
+    let mut parts = Vec::new();
+    if !contact.name.is_empty() {
+        parts.push(contact.name.clone());
+    }
+    if !contact.title.is_empty() {
+        parts.push(contact.title.clone());
+    }
+    match (contact.email.is_empty(), mailto) {
+        (true, _) => {},
+        (false, true) => parts.push(format!("[{}](mailto:{})", contact.email, contact.email)),
+        (false, false) => parts.push(contact.email.clone()),
+    }
+    format!("- {}\n", parts.join(", "))
+}
@@ -0,0 +1,54 @@
+use crate::{CompanyEntry, links};
+use crate::labels::Labels;
+
+// handshake hasn't been consistent about what it calls these, so take whichever shows up first
+const DEADLINE_KEYS: [&str; 4] = ["apply_by", "application_deadline", "apply_end", "expiration_date"];
+
+pub fn find_deadline(json_entry: &serde_json::Value) -> Option<String> {
+    find_deadline_key(json_entry).map(|(_, deadline)| deadline)
+}
+
+// the key the deadline was found under along with it
+pub fn find_deadline_key(json_entry: &serde_json::Value) -> Option<(&'static str, String)> {
+    DEADLINE_KEYS.iter()
+        .find_map(|key| Some((*key, parse_date(json_entry[key].as_str()?)?)))
+}
+
+// only keeps the YYYY-MM-DD part of a timestamp so notes sort and display nicely
+fn parse_date(value: &str) -> Option<String> {
+    let date = value.get(..10)?;
+    let bytes = date.as_bytes();
+
+    let is_date = bytes.iter().enumerate().all(|(i, b)| match i {
+        4 | 7 => *b == b'-',
+        _ => b.is_ascii_digit(),
+    });
+
+    is_date.then(|| date.to_string())
+}
+
+// returns None when no company has a deadline so we don't write an empty note
+// tasks_format writes the due date the way the Tasks plugin expects so they show up in its queries
+pub fn render_deadlines(companies: &[CompanyEntry], tasks_format: bool, labels: &Labels) -> Option<String> {
+    let mut with_deadline: Vec<(&String, &CompanyEntry)> = companies.iter()
+        .filter_map(|c| c.deadline.as_ref().map(|d| (d, c)))
+        .collect();
+
+    if with_deadline.is_empty() {
+        return None;
+    }
+
+    with_deadline.sort_by(|a, b| a.0.cmp(b.0).then(a.1.name.cmp(&b.1.name)));
+
+    let mut text = format!("# {}\n\n", labels.deadlines);
+    for (deadline, company) in with_deadline {
+        let link = links::company_link(company);
+        if tasks_format {
+            text.push_str(&format!("- [ ] {} 📅 {}\n", labels.apply_task.replace("{company}", &link), deadline));
+        } else {
+            text.push_str(&format!("- [ ] {}\n", labels.apply_by.replace("{company}", &link).replace("{date}", deadline)));
+        }
+    }
+
+    Some(text)
+}
@@ -0,0 +1,102 @@
+use crate::{Error, term};
+
+// what the career office's export was saved as, anything but utf-8 is turned into it before the
+// input is read, --encoding names one when the guess is wrong
+#[derive(Clone, Copy, PartialEq)]
+pub enum Encoding {
+    Utf8,
+    Utf16Le,
+    Utf16Be,
+    // excel's csv on windows, latin-1 with smart quotes, dashes and € where latin-1 has control codes
+    Windows1252,
+    Latin1,
+}
+
+pub const ENCODINGS: &[&str] = &["utf-8", "utf-16le", "utf-16be", "windows-1252", "latin1"];
+
+// 0x80 to 0x9f in windows-1252, the five it leaves undefined stay the control codes latin-1 has there
+const WINDOWS_1252: [char; 32] = [
+    '€', '\u{81}', '‚', 'ƒ', '„', '…', '†', '‡', 'ˆ', '‰', 'Š', '‹', 'Œ', '\u{8d}', 'Ž', '\u{8f}',
+    '\u{90}', '‘', '’', '“', '”', '•', '–', '—', '˜', '™', 'š', '›', 'œ', '\u{9d}', 'ž', 'Ÿ',
+];
+
+impl Encoding {
+    pub fn parse(name: &str) -> Option<Encoding> {
+        match name.to_ascii_lowercase().replace('_', "-").as_str() {
+            "utf-8" | "utf8" => Some(Encoding::Utf8),
+            "utf-16le" | "utf16le" | "utf-16" | "utf16" => Some(Encoding::Utf16Le),
+            "utf-16be" | "utf16be" => Some(Encoding::Utf16Be),
+            "windows-1252" | "cp1252" | "win1252" | "ansi" => Some(Encoding::Windows1252),
+            "latin1" | "latin-1" | "iso-8859-1" | "iso8859-1" => Some(Encoding::Latin1),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Encoding::Utf8 => "utf-8",
+            Encoding::Utf16Le => "utf-16le",
+            Encoding::Utf16Be => "utf-16be",
+            Encoding::Windows1252 => "windows-1252",
+            Encoding::Latin1 => "latin1",
+        }
+    }
+}
+
+// a byte order mark settles it, otherwise text that is valid utf-8 is taken as that, zeros in every
+// other byte are utf-16 saved without a mark, and whatever is left is windows-1252 since that's what
+// excel writes and it reads latin-1 the same for everything but the control codes
+pub fn detect(input_data: &[u8]) -> Encoding {
+    if input_data.starts_with(b"\xff\xfe") {
+        return Encoding::Utf16Le;
+    }
+    if input_data.starts_with(b"\xfe\xff") {
+        return Encoding::Utf16Be;
+    }
+    if std::str::from_utf8(input_data).is_ok() {
+        return Encoding::Utf8;
+    }
+    let sample = &input_data[..input_data.len().min(1024)];
+    let zeros_at = |parity: usize| sample.iter().skip(parity).step_by(2).filter(|b| **b == 0).count();
+    let half = sample.len() / 2;
+    match (zeros_at(0), zeros_at(1)) {
+        (even, _) if half > 0 && even * 2 > half => Encoding::Utf16Be,
+        (_, odd) if half > 0 && odd * 2 > half => Encoding::Utf16Le,
+        _ => Encoding::Windows1252,
+    }
+}
+
+fn utf16(input_data: &[u8], from_bytes: fn([u8; 2]) -> u16) -> String {
+    let units = input_data.chunks_exact(2).map(|pair| from_bytes([pair[0], pair[1]]));
+    char::decode_utf16(units).map(|c| c.unwrap_or(char::REPLACEMENT_CHARACTER)).collect::<String>()
+        .trim_start_matches('\u{feff}').to_string()
+}
+
+// the input as utf-8, which it is already most of the time and then it's passed through untouched
+// so its hash stays the same, only a byte order mark is dropped since no json or yaml reader takes one
+pub fn to_utf8(input_data: Vec<u8>, encoding: Option<Encoding>) -> Result<Vec<u8>, Error> {
+    let input_data = match input_data.strip_prefix(b"\xef\xbb\xbf") {
+        Some(rest) => rest.to_vec(),
+        None => input_data,
+    };
+    let encoding = encoding.unwrap_or_else(|| detect(&input_data));
+    if encoding != Encoding::Utf8 {
+        term::debug(&format!("reading the input as {}", encoding.name()));
+    }
+    let text = match encoding {
+        Encoding::Utf8 => match std::str::from_utf8(&input_data) {
+            Ok(_) => return Ok(input_data),
+            Err(e) => return Err(Error::InvalidInput(format!("input is not valid utf-8: {}, try --encoding windows-1252", e))),
+        },
+        Encoding::Utf16Le => utf16(&input_data, u16::from_le_bytes),
+        Encoding::Utf16Be => utf16(&input_data, u16::from_be_bytes),
+        Encoding::Windows1252 => input_data.iter()
+            .map(|&b| match b {
+                0x80..=0x9f => WINDOWS_1252[(b - 0x80) as usize],
+                _ => b as char,
+            })
+            .collect(),
+        Encoding::Latin1 => input_data.iter().map(|&b| b as char).collect(),
+    };
+    Ok(text.into_bytes())
+}
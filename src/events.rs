@@ -0,0 +1,136 @@
+use std::fs;
+use std::path::Path;
+
+use crate::policy::Policy;
+use crate::{CompanyEntry, Error, fields, links, update};
+
+// per employer lists of events show up under a few names depending on the endpoint
+const EVENT_KEYS: [&str; 3] = ["events", "info_sessions", "employer_events"];
+
+pub struct Event {
+    id: Option<u64>,
+    name: String,
+    kind: Option<String>,
+    start: Option<String>,
+    end: Option<String>,
+    location: Option<String>,
+    description: Option<String>,
+    companies: Vec<String>,
+}
+
+// events can come nested in a fair entry or as a top level list that points at employers by id,
+// the same event listed by several employers becomes one note linking all of them
+pub fn parse_events(input_data: &[u8], companies: &[CompanyEntry]) -> Vec<Event> {
+    let Ok(json_data) = serde_json::from_slice::<serde_json::Value>(input_data) else { return Vec::new() };
+    let mut events: Vec<Event> = Vec::new();
+
+    if let serde_json::Value::Array(entries) = &json_data["results"] {
+        for entry in entries {
+            let company = entry["employer"]["name"].as_str();
+            for key in EVENT_KEYS {
+                for event_entry in entry[key].as_array().into_iter().flatten() {
+                    add_event(&mut events, event_entry, company);
+                }
+            }
+        }
+    }
+
+    for event_entry in json_data["events"].as_array().into_iter().flatten() {
+        let employer_id = event_entry["employer"]["id"].as_u64().or(event_entry["employer_id"].as_u64());
+        let company = match employer_id {
+            Some(id) => companies.iter().find(|c| c.employer_id == Some(id)).map(|c| c.name.as_str()),
+            None => event_entry["employer"]["name"].as_str(),
+        };
+        add_event(&mut events, event_entry, company);
+    }
+
+    events
+}
+
+fn add_event(events: &mut Vec<Event>, entry: &serde_json::Value, company: Option<&str>) {
+    let text = |keys: &[&str]| keys.iter().find_map(|key| entry[key].as_str()).map(str::to_string);
+
+    let Some(name) = text(&["name", "title", "display_name"]) else { return };
+    let id = entry["id"].as_u64();
+
+    let event = match events.iter_mut().find(|e| (e.id.is_some() && e.id == id) || (e.id.is_none() && e.name == name)) {
+        Some(event) => event,
+        None => {
+            events.push(Event {
+                id,
+                name,
+                kind: text(&["event_type", "type_name", "category"]),
+                start: text(&["start_date_time", "start_date", "starts_at"]),
+                end: text(&["end_date_time", "end_date", "ends_at"]),
+                location: text(&["location_name", "location"]),
+                description: text(&["description", "event_description"]),
+                companies: Vec::new(),
+            });
+            events.last_mut().unwrap()
+        },
+    };
+
+    if let Some(company) = company
+        && !event.companies.iter().any(|c| c == company)
+    {
+        event.companies.push(company.to_string());
+    }
+}
+
+// the date leads the name so the folder sorts chronologically
+fn note_name(event: &Event) -> String {
+    let name = links::short_name(&links::note_name(&event.name));
+    match event.start.as_deref().and_then(|s| s.get(..10)) {
+        Some(date) => format!("{} {}", date, name.trim()),
+        None => name.trim().to_string(),
+    }
+}
+
+fn frontmatter(event: &Event) -> Vec<(String, String)> {
+    use fields::RawValue::{Many, One};
+
+    let mut raw = Vec::new();
+    if let Some(kind) = &event.kind {
+        raw.push(("event_type", One(kind.clone())));
+    }
+    if let Some(start) = &event.start {
+        raw.push(("start", One(start.clone())));
+    }
+    if let Some(end) = &event.end {
+        raw.push(("end", One(end.clone())));
+    }
+    if let Some(location) = &event.location {
+        raw.push(("location", One(location.clone())));
+    }
+    raw.push(("companies", Many(event.companies.clone())));
+
+    // companies are always links since connecting events to company notes is the point
+    let mut formats = fields::FieldFormats::default();
+    formats.set("companies", fields::FieldFormat::Link);
+    fields::format_frontmatter(raw, &formats)
+}
+
+fn sections(event: &Event) -> Vec<(&'static str, String)> {
+    let mut text = format!("# {}\n", event.name);
+    if let Some(description) = &event.description {
+        text.push_str(&format!("\n{}\n", description));
+    }
+    vec![("event", text)]
+}
+
+// notes that already exist keep whatever the user added and only get their generated parts refreshed
+pub fn write_events(events_dir: &Path, events: &[Event], policy: Policy) -> Result<usize, Error> {
+    fs::create_dir_all(events_dir)?;
+
+    let mut written = 0;
+    for event in events {
+        let file_path = events_dir.join(format!("{}.md", note_name(event)));
+        match update::write_or_update(&file_path, &frontmatter(event), &sections(event), None) {
+            Ok(true) => written += 1,
+            Ok(false) => {},
+            Err(e) => policy.fail(&format!("{}: {}", file_path.to_string_lossy(), e))?,
+        }
+    }
+
+    Ok(written)
+}
@@ -0,0 +1,19 @@
+use crate::Error;
+
+// what a run exits with when it fails, going by what the error is about, so a script can tell a typo
+// in its flags from a broken export or a full disk, clap exits with 2 for usage errors too
+pub const FAILED: u8 = 1;
+pub const USAGE: u8 = 2;
+pub const INPUT: u8 = 3;
+pub const TEMPLATE: u8 = 4;
+pub const OUTPUT: u8 = 5;
+
+pub fn code(error: &Error) -> u8 {
+    match error {
+        Error::Usage(_) => USAGE,
+        Error::Json { .. } | Error::MissingField { .. } | Error::Input { .. } | Error::InvalidInput(_) => INPUT,
+        Error::Template(_) => TEMPLATE,
+        Error::Output { .. } => OUTPUT,
+        Error::Io(_) | Error::Other(_) => FAILED,
+    }
+}
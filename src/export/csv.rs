@@ -0,0 +1,59 @@
+use crate::CompanyEntry;
+use super::Exporter;
+
+pub struct Csv;
+
+impl Exporter for Csv {
+    fn name(&self) -> &'static str {
+        "csv"
+    }
+
+    fn render(&self, companies: &[CompanyEntry]) -> String {
+        render_csv(companies)
+    }
+}
+
+fn render_csv(companies: &[CompanyEntry]) -> String {
+    let rows: Vec<(&CompanyEntry, Vec<&str>)> = companies.iter().map(|company| (company, Vec::new())).collect();
+    render_csv_with_fields(&rows, &[])
+}
+
+// extra columns after the usual ones, for fields that don't come from the input like the ones
+// extract reads back out of a vault
+pub fn render_csv_with_fields(rows: &[(&CompanyEntry, Vec<&str>)], field_names: &[&str]) -> String {
+    let mut text = "name,location,website,majors,job_titles,job_types,school_years,international,sessions,deadline".to_string();
+    for name in field_names {
+        text.push(',');
+        text.push_str(&csv_escape(name));
+    }
+    text.push('\n');
+
+    for (company, fields) in rows {
+        let sessions: Vec<&str> = company.attending_sessions.iter().map(|s| s.display_name.as_str()).collect();
+        let row = [
+            company.name.as_str(),
+            &company.location,
+            &company.website,
+            &company.majors.join("; "),
+            &company.job_titles,
+            &company.job_types.join("; "),
+            &company.school_years.join("; "),
+            &company.work_authorization,
+            &sessions.join("; "),
+            company.deadline.as_deref().unwrap_or(""),
+        ];
+        let row: Vec<String> = row.iter().chain(fields).map(|field| csv_escape(field)).collect();
+        text.push_str(&row.join(","));
+        text.push('\n');
+    }
+
+    text
+}
+
+pub fn csv_escape(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
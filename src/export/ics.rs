@@ -0,0 +1,88 @@
+use crate::{CompanyEntry, Session};
+use super::Exporter;
+
+pub struct Ics;
+
+impl Exporter for Ics {
+    fn name(&self) -> &'static str {
+        "ics"
+    }
+
+    fn render(&self, companies: &[CompanyEntry]) -> String {
+        render_ics(companies)
+    }
+}
+
+// one event per fair session with the attending companies in the description
+fn render_ics(companies: &[CompanyEntry]) -> String {
+    // keeps first-seen order so the calendar is stable between runs
+    let mut sessions: Vec<(&Session, Vec<&str>)> = Vec::new();
+
+    for company in companies {
+        for session in &company.attending_sessions {
+            match sessions.iter_mut().find(|(s, _)| s.id == session.id && s.display_name == session.display_name) {
+                Some((_, names)) => names.push(&company.name),
+                None => sessions.push((session, vec![&company.name])),
+            }
+        }
+    }
+
+    let mut text = "BEGIN:VCALENDAR\r\nVERSION:2.0\r\nPRODID:-//fair_renderer//EN\r\n".to_string();
+
+    for (i, (session, names)) in sessions.iter().enumerate() {
+        let (Some(start), Some(end)) = (session.start.as_deref().and_then(ics_time), session.end.as_deref().and_then(ics_time)) else {
+            continue;
+        };
+        let uid = match session.id {
+            Some(id) => format!("session-{}@fair_renderer", id),
+            None => format!("session-index-{}@fair_renderer", i),
+        };
+
+        text.push_str("BEGIN:VEVENT\r\n");
+        text.push_str(&format!("UID:{}\r\n", uid));
+        // stamped with the start so the output is the same every run
+        text.push_str(&format!("DTSTAMP:{}\r\n", start));
+        text.push_str(&format!("DTSTART:{}\r\n", start));
+        text.push_str(&format!("DTEND:{}\r\n", end));
+        text.push_str(&ics_fold(&format!("SUMMARY:{}", ics_escape(&format!("Career Fair: {}", session.display_name)))));
+        text.push_str(&ics_fold(&format!("DESCRIPTION:{}", ics_escape(&format!("{} companies attending:\n{}", names.len(), names.join("\n"))))));
+        text.push_str("END:VEVENT\r\n");
+    }
+
+    text.push_str("END:VCALENDAR\r\n");
+    text
+}
+
+// 2025-09-17T17:00:00.000Z -> 20250917T170000Z
+fn ics_time(timestamp: &str) -> Option<String> {
+    let digits: String = timestamp.get(..19)?.chars().filter(|c| c.is_ascii_digit()).collect();
+    if digits.len() != 14 {
+        return None;
+    }
+    Some(format!("{}T{}Z", &digits[..8], &digits[8..]))
+}
+
+fn ics_escape(text: &str) -> String {
+    text.replace('\\', "\\\\")
+        .replace(';', "\\;")
+        .replace(',', "\\,")
+        .replace('\n', "\\n")
+}
+
+// calendar lines are supposed to be wrapped at 75 bytes with continuation lines starting with a space
+fn ics_fold(line: &str) -> String {
+    let mut folded = String::new();
+    let mut line_len = 0;
+
+    for c in line.chars() {
+        if line_len + c.len_utf8() > 75 {
+            folded.push_str("\r\n ");
+            line_len = 1;
+        }
+        folded.push(c);
+        line_len += c.len_utf8();
+    }
+
+    folded.push_str("\r\n");
+    folded
+}
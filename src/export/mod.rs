@@ -0,0 +1,62 @@
+use std::fs;
+
+use crate::{CompanyEntry, Error, cards};
+
+mod csv;
+mod ics;
+
+pub use csv::csv_escape;
+#[cfg(feature = "cli")]
+pub use csv::render_csv_with_fields;
+
+// an output format picked with --export kind:path, a new one is a module with an Exporter and an
+// entry in EXPORTERS, most only have to render text and get written for free
+pub trait Exporter: Sync {
+    fn name(&self) -> &'static str;
+
+    fn render(&self, companies: &[CompanyEntry]) -> String;
+
+    // formats that aren't a single text file, like the cards pdf, write the file themselves
+    fn write(&self, path: &str, companies: &[CompanyEntry]) -> Result<(), Error> {
+        fs::write(path, self.render(companies)).map_err(|source| Error::Output { path: path.to_string(), source })
+    }
+}
+
+// html, or pdf when the path ends in .pdf
+struct Cards;
+
+impl Exporter for Cards {
+    fn name(&self) -> &'static str {
+        "cards"
+    }
+
+    fn render(&self, companies: &[CompanyEntry]) -> String {
+        cards::render_cards(companies)
+    }
+
+    fn write(&self, path: &str, companies: &[CompanyEntry]) -> Result<(), Error> {
+        cards::write_cards(path, companies)
+    }
+}
+
+pub const EXPORTERS: &[&dyn Exporter] = &[&csv::Csv, &ics::Ics, &Cards];
+
+pub fn find_exporter(kind: &str) -> Option<&'static dyn Exporter> {
+    EXPORTERS.iter().find(|e| e.name() == kind).copied()
+}
+
+// spec comes straight from the cli as kind:path, ex. csv:fair.csv, checked before anything is rendered
+pub fn parse_spec(spec: &str) -> Result<(&'static dyn Exporter, String), Error> {
+    let (kind, path) = match spec.split_once(':') {
+        Some((kind, path)) if !path.is_empty() => (kind, path),
+        _ => return Err(Error::Usage(format!("export should look like kind:path, got: {}", spec))),
+    };
+
+    match find_exporter(kind) {
+        Some(exporter) => Ok((exporter, path.to_string())),
+        None => {
+            let kinds: Vec<&str> = EXPORTERS.iter().map(|e| e.name()).collect();
+            Err(Error::Usage(format!("unknown export kind: {}, expected one of {}", kind, kinds.join(", "))))
+        },
+    }
+}
@@ -0,0 +1,186 @@
+use std::fs;
+use std::path::Path;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::contacts::Contact;
+use crate::folders::Folders;
+use crate::{CompanyEntry, Error, Session, export, term, update};
+
+// the keys the renderer writes, everything else in a note's frontmatter is the user's
+const GENERATED_KEYS: &[&str] = &[
+    "location", "majors", "job_titles", "job_types", "school_years", "international", "sessions",
+    "website", "employer_id", "deadline", "language", "links", "industry", "contacts", "summary",
+];
+
+// a company read back out of a vault, with whatever the user filled in next to it
+pub struct Extracted {
+    pub company: CompanyEntry,
+    pub fields: Vec<(String, String)>,
+}
+
+// [[note|Original Name]] goes back to the name it was written from
+fn unlink(value: &str) -> String {
+    match value.strip_prefix("[[").and_then(|v| v.strip_suffix("]]")) {
+        Some(inner) => inner.rsplit('|').next().unwrap_or(inner).to_string(),
+        None => value.to_string(),
+    }
+}
+
+fn yaml_text(value: &Yaml) -> String {
+    match value {
+        Yaml::String(s) | Yaml::Real(s) => unlink(s),
+        Yaml::Integer(i) => i.to_string(),
+        Yaml::Boolean(b) => b.to_string(),
+        Yaml::Array(values) => values.iter().map(yaml_text).collect::<Vec<String>>().join("; "),
+        _ => String::new(),
+    }
+}
+
+// list fields written as a plain string were joined with ", ", sessions are left whole since
+// their names have commas of their own
+fn yaml_list(value: &Yaml, split: bool) -> Vec<String> {
+    match value {
+        Yaml::Array(values) => values.iter().map(yaml_text).filter(|v| !v.is_empty()).collect(),
+        Yaml::String(s) if split => s.split(", ").map(unlink).collect(),
+        Yaml::String(s) if !s.is_empty() => vec![unlink(s)],
+        _ => Vec::new(),
+    }
+}
+
+// the description section without its heading, and without the original when it was translated
+fn description(text: &str) -> String {
+    let section = update::section_contents(text, &["description"]);
+    let body = section.split_once("\n\n").map(|(_, body)| body).unwrap_or(&section);
+    let body = body.split("\n\n> [!").next().unwrap_or(body);
+    body.trim().to_string()
+}
+
+fn extract_note(name: String, text: &str, key_prefix: &str) -> Option<Extracted> {
+    let frontmatter = text.strip_prefix("---\n")?.split("\n---").next()?;
+    let docs = YamlLoader::load_from_str(frontmatter).ok()?;
+    let hash = docs.first()?.as_hash()?;
+
+    let mut company = CompanyEntry {
+        note: name.clone(),
+        name,
+        description: description(text),
+        location: String::new(),
+        website: String::new(),
+        logo_url: String::new(),
+        work_authorization: String::new(),
+        job_titles: String::new(),
+        job_types: Vec::new(),
+        majors: Vec::new(),
+        school_years: Vec::new(),
+        attending_sessions: Vec::new(),
+        employer_id: None,
+        booth: None,
+        deadline: None,
+        description_language: None,
+        translated_description: None,
+        links: Vec::new(),
+        positions: Vec::new(),
+        industry: None,
+        contacts: Vec::new(),
+        summary: None,
+        overflow_note: None,
+        sources: Vec::new(),
+    };
+    let mut fields = Vec::new();
+
+    for (key, value) in hash {
+        let Some(key) = key.as_str() else { continue };
+        let generated = key.strip_prefix(key_prefix).filter(|k| GENERATED_KEYS.contains(k));
+        match generated {
+            Some("location") => company.location = yaml_text(value),
+            Some("majors") => company.majors = yaml_list(value, true),
+            Some("job_titles") => company.job_titles = yaml_text(value),
+            Some("job_types") => company.job_types = yaml_list(value, true),
+            Some("school_years") => company.school_years = yaml_list(value, true),
+            Some("international") => company.work_authorization = yaml_text(value),
+            Some("sessions") => company.attending_sessions = yaml_list(value, false).into_iter()
+                .map(|display_name| Session { id: None, note: display_name.clone(), display_name, start: None, end: None, registered: None, capacity: None, waitlisted: None })
+                .collect(),
+            Some("website") => company.website = yaml_text(value),
+            Some("employer_id") => company.employer_id = yaml_text(value).parse().ok(),
+            Some("deadline") => company.deadline = Some(yaml_text(value)).filter(|d| !d.is_empty()),
+            Some("language") => company.description_language = Some(yaml_text(value)).filter(|l| !l.is_empty()),
+            Some("industry") => company.industry = Some(yaml_text(value)).filter(|i| !i.is_empty()),
+            Some("contacts") => company.contacts = yaml_list(value, true).into_iter()
+                .map(|name| Contact { name, title: String::new(), email: String::new() })
+                .collect(),
+            Some("summary") => company.summary = Some(yaml_text(value)).filter(|s| !s.is_empty()),
+            Some("links") => company.links = yaml_list(value, true).into_iter().map(|url| ("Link".to_string(), url)).collect(),
+            _ if key == "fileClass" || key == "tags" => {},
+            _ => fields.push((key.to_string(), yaml_text(value))),
+        }
+    }
+
+    Some(Extracted { company, fields })
+}
+
+// every company note in the vault, notes whose frontmatter doesn't parse are skipped with a
+// warning since lint is the place to fix them
+pub fn extract_vault(vault_path: &Path, folders: &Folders, key_prefix: &str) -> Result<Vec<Extracted>, Error> {
+    let companies_dir = vault_path.join(&folders.companies);
+    let entries = match fs::read_dir(&companies_dir) {
+        Ok(entries) => entries,
+        Err(e) => return Err(Error::Other(format!("could not read {}: {}", companies_dir.to_string_lossy(), e))),
+    };
+
+    let mut paths: Vec<_> = entries.filter_map(|e| e.ok()).map(|e| e.path()).filter(|p| p.extension().is_some_and(|e| e == "md")).collect();
+    paths.sort();
+
+    let mut extracted = Vec::new();
+    for path in paths {
+        let text = fs::read_to_string(&path)?;
+        let name = path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_default();
+        match extract_note(name, &text, key_prefix) {
+            Some(entry) => extracted.push(entry),
+            None => term::warn(&format!("skipped {}, its frontmatter doesn't parse", path.to_string_lossy())),
+        }
+    }
+    Ok(extracted)
+}
+
+pub fn render_json(extracted: &[Extracted]) -> String {
+    let entries: Vec<serde_json::Value> = extracted.iter()
+        .map(|Extracted { company, fields }| serde_json::json!({
+            "name": company.name,
+            "description": company.description,
+            "location": company.location,
+            "website": company.website,
+            "employer_id": company.employer_id,
+            "majors": company.majors,
+            "job_titles": company.job_titles,
+            "job_types": company.job_types,
+            "school_years": company.school_years,
+            "international": company.work_authorization,
+            "sessions": company.attending_sessions.iter().map(|s| s.display_name.clone()).collect::<Vec<String>>(),
+            "deadline": company.deadline,
+            "links": company.links.iter().map(|(_, url)| url.clone()).collect::<Vec<String>>(),
+            "fields": fields.iter().map(|(k, v)| (k.clone(), v.clone().into())).collect::<serde_json::Map<String, serde_json::Value>>(),
+        }))
+        .collect();
+    serde_json::to_string_pretty(&entries).unwrap_or_default() + "\n"
+}
+
+// the same columns as --export csv, followed by one for each of the user's own fields
+pub fn render_csv(extracted: &[Extracted]) -> String {
+    let mut field_names: Vec<&str> = Vec::new();
+    for (name, _) in extracted.iter().flat_map(|e| &e.fields) {
+        if !field_names.contains(&name.as_str()) {
+            field_names.push(name);
+        }
+    }
+    let rows: Vec<(&CompanyEntry, Vec<&str>)> = extracted.iter()
+        .map(|e| {
+            let values = field_names.iter()
+                .map(|name| e.fields.iter().find(|(n, _)| n == name).map(|(_, v)| v.as_str()).unwrap_or(""))
+                .collect();
+            (&e.company, values)
+        })
+        .collect();
+    export::render_csv_with_fields(&rows, &field_names)
+}
@@ -0,0 +1,133 @@
+use std::time::Duration;
+
+use crate::{Error, term};
+
+// handshake keeps the login in its rails session cookie, copy the value of _trajectory_session
+// out of the browser devtools and pass it as the token
+#[cfg(feature = "network")]
+const SESSION_COOKIE: &str = "_trajectory_session";
+
+// most handshake endpoints only send a page at a time, each page's results get merged into the first,
+// gives up after this many in case an endpoint keeps claiming there's more
+const MAX_PAGES: u64 = 500;
+
+#[cfg(feature = "network")]
+fn fetch_page(url: &str, token: Option<&str>) -> Result<Vec<u8>, Error> {
+    term::trace(&format!("fetching {}", url));
+
+    let mut request = ureq::get(url).header("Accept", "application/json");
+    if let Some(token) = token {
+        request = request.header("Cookie", &format!("{}={}", SESSION_COOKIE, token));
+    }
+
+    let mut response = match request.call() {
+        Ok(response) => response,
+        Err(e) => return Err(Error::InvalidInput(format!("failed fetching {}: {}", url, e))),
+    };
+
+    match response.body_mut().with_config().limit(256 * 1024 * 1024).read_to_vec() {
+        Ok(bytes) => Ok(bytes),
+        Err(e) => Err(Error::InvalidInput(format!("failed reading response from {}: {}", url, e))),
+    }
+}
+
+#[cfg(not(feature = "network"))]
+fn fetch_page(_url: &str, _token: Option<&str>) -> Result<Vec<u8>, Error> {
+    Err(crate::missing_feature("--fetch", "network"))
+}
+
+// the page count is either given outright or worked out from the total, at the top level or under meta
+fn total_pages(page: &serde_json::Value) -> Option<u64> {
+    let field = |name: &str| page[name].as_u64().or_else(|| page["meta"][name].as_u64());
+    match (field("total_pages"), field("total"), field("per_page")) {
+        (Some(pages), _, _) => Some(pages),
+        (None, Some(total), Some(per_page)) if per_page > 0 => Some(total.div_ceil(per_page)),
+        _ => None,
+    }
+}
+
+// swaps the page parameter in the url's query, everything else is left as it was given
+fn with_page(url: &str, page: u64) -> String {
+    let (base, query) = url.split_once('?').unwrap_or((url, ""));
+    let mut params: Vec<&str> = query.split('&').filter(|p| !p.is_empty() && !p.starts_with("page=")).collect();
+    let page = format!("page={}", page);
+    params.push(&page);
+    format!("{}?{}", base, params.join("&"))
+}
+
+pub fn fetch_input(url: &str, token: Option<&str>) -> Result<Vec<u8>, Error> {
+    let first = fetch_page(url, token)?;
+    let Ok(first_json) = serde_json::from_slice::<serde_json::Value>(&first) else { return Ok(first) };
+    let pages = match total_pages(&first_json) {
+        Some(pages) if pages > 1 => pages.min(MAX_PAGES),
+        _ => return Ok(first),
+    };
+
+    let mut all = vec![first_json];
+    for page in 2..=pages {
+        let bytes = fetch_page(&with_page(url, page), token)?;
+        match serde_json::from_slice(&bytes) {
+            Ok(json) => all.push(json),
+            Err(source) => return Err(Error::Json { what: format!("page {} of {}", page, url), source }),
+        }
+    }
+    term::debug(&format!("fetched {} pages", pages));
+
+    Ok(merge_results(all))
+}
+
+fn merge_results(mut pages: Vec<serde_json::Value>) -> Vec<u8> {
+    let results: Vec<serde_json::Value> = pages.iter_mut()
+        .flat_map(|page| match page["results"].take() {
+            serde_json::Value::Array(results) => results,
+            _ => Vec::new(),
+        })
+        .collect();
+    let mut merged = pages.swap_remove(0);
+    merged["results"] = serde_json::Value::Array(results);
+    serde_json::to_vec(&merged).unwrap_or_default()
+}
+
+// saved pages can come in pasted back to back or as an array of pages, either is merged into one
+// input, a single page is passed through untouched so its hash stays the same
+pub fn merge_pages(input_data: Vec<u8>) -> Vec<u8> {
+    let mut values = Vec::new();
+    for value in serde_json::Deserializer::from_slice(&input_data).into_iter::<serde_json::Value>() {
+        match value {
+            Ok(value) => values.push(value),
+            Err(_) => return input_data,
+        }
+    }
+
+    let pages = match values.as_slice() {
+        [serde_json::Value::Array(pages)] => pages.clone(),
+        [_] | [] => return input_data,
+        _ => values,
+    };
+    if pages.is_empty() || !pages.iter().all(|page| page["results"].is_array()) {
+        return input_data;
+    }
+
+    term::debug(&format!("merging {} pages of results", pages.len()));
+    merge_results(pages)
+}
+
+// accepts plain seconds or a number with an s/m/h/d suffix, ex. 90, 30m, 6h
+pub fn parse_interval(value: &str) -> Option<Duration> {
+    let (number, unit) = match value.char_indices().last()? {
+        (idx, c) if c.is_ascii_alphabetic() => (&value[..idx], c),
+        _ => (value, 's'),
+    };
+
+    let number: u64 = number.parse().ok()?;
+    // something like 999999999999999999d is just as invalid as 5x
+    let seconds = match unit {
+        's' => number,
+        'm' => number.checked_mul(60)?,
+        'h' => number.checked_mul(60 * 60)?,
+        'd' => number.checked_mul(60 * 60 * 24)?,
+        _ => return None,
+    };
+
+    (seconds > 0).then(|| Duration::from_secs(seconds))
+}
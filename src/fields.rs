@@ -0,0 +1,205 @@
+use crate::links;
+use crate::{CompanyEntry, Error};
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum FieldFormat {
+    // a single line, lists joined with commas
+    String,
+    // yaml list
+    List,
+    // wikilink, or a list of them
+    Link,
+    // values go into the shared tags property as field/value
+    Tag,
+}
+
+impl FieldFormat {
+    pub fn name(self) -> &'static str {
+        match self {
+            FieldFormat::String => "string",
+            FieldFormat::List => "list",
+            FieldFormat::Link => "link",
+            FieldFormat::Tag => "tag",
+        }
+    }
+}
+
+pub enum RawValue {
+    One(String),
+    Many(Vec<String>),
+}
+
+// lists that only make sense as one, everything else is a single line unless --field-format says otherwise
+fn default_format(field: &str) -> FieldFormat {
+    match field {
+        "links" => FieldFormat::List,
+        _ => FieldFormat::String,
+    }
+}
+
+#[derive(Default)]
+pub struct FieldFormats(pub Vec<(String, FieldFormat)>);
+
+impl FieldFormats {
+    // specs look like majors=list,location=link, later ones win
+    pub fn parse(specs: &[String]) -> Result<FieldFormats, Error> {
+        let mut formats = FieldFormats::default();
+        for spec in specs.iter().flat_map(|s| s.split(',')) {
+            let (field, format) = match spec.split_once('=') {
+                Some((field, format)) => (field.trim(), format.trim()),
+                None => return Err(Error::Usage(format!("field format should look like field=type, got: {}", spec))),
+            };
+            let format = match format {
+                "string" => FieldFormat::String,
+                "list" => FieldFormat::List,
+                "link" => FieldFormat::Link,
+                "tag" => FieldFormat::Tag,
+                _ => return Err(Error::Usage(format!("unknown field format {}, expected string, list, link or tag", format))),
+            };
+            formats.set(field, format);
+        }
+        Ok(formats)
+    }
+
+    pub fn set(&mut self, field: &str, format: FieldFormat) {
+        self.0.retain(|(f, _)| f != field);
+        self.0.push((field.to_string(), format));
+    }
+
+    pub fn get(&self, field: &str) -> FieldFormat {
+        self.0.iter()
+            .find(|(f, _)| f == field)
+            .map(|(_, format)| *format)
+            .unwrap_or(default_format(field))
+    }
+}
+
+pub fn format_frontmatter(raw: Vec<(&str, RawValue)>, formats: &FieldFormats) -> Vec<(String, String)> {
+    let mut fields = Vec::new();
+    let mut tags: Vec<String> = Vec::new();
+
+    for (key, value) in raw {
+        let values = match &value {
+            RawValue::One(v) => vec![v.clone()],
+            RawValue::Many(vs) => vs.clone(),
+        };
+
+        let formatted = match (formats.get(key), &value) {
+            (FieldFormat::String, RawValue::One(v)) => yaml_string(v),
+            (FieldFormat::String, RawValue::Many(vs)) => yaml_string(&vs.join(", ")),
+            (FieldFormat::List, _) => quoted_list(&values),
+            (FieldFormat::Link, RawValue::One(v)) => links::quoted_link(v),
+            (FieldFormat::Link, RawValue::Many(vs)) => links::quoted_link_list(vs),
+            (FieldFormat::Tag, _) => {
+                tags.extend(values.iter().map(|v| format!("{}/{}", tag_slug(key), tag_slug(v))));
+                continue;
+            },
+        };
+        fields.push((key.to_string(), formatted));
+    }
+
+    if !tags.is_empty() {
+        fields.push(("tags".to_string(), quoted_list(&tags)));
+    }
+
+    fields
+}
+
+fn quoted_list(values: &[String]) -> String {
+    let quoted: Vec<String> = values.iter().map(|v| links::quoted(v)).collect();
+    format!("[{}]", quoted.join(", "))
+}
+
+// plain values are left as is unless yaml would read them as something other than a string
+pub fn yaml_string(value: &str) -> String {
+    let special_start = value.starts_with([
+        '-', '?', ':', ',', '[', ']', '{', '}', '#', '&', '*', '!', '|', '>', '\'', '"', '%', '@', '`',
+    ]);
+    let needs_quotes = special_start
+        || value.contains(": ")
+        || value.contains(" #")
+        || value.ends_with(':')
+        || value != value.trim();
+
+    match needs_quotes && !value.is_empty() {
+        true => links::quoted(value),
+        false => value.to_string(),
+    }
+}
+
+// obsidian tags can't have spaces and most punctuation, and can't be only digits
+fn tag_slug(value: &str) -> String {
+    let mut slug = String::new();
+    for c in value.trim().chars() {
+        match c {
+            c if c.is_alphanumeric() || c == '_' || c == '-' => slug.extend(c.to_lowercase()),
+            _ if !slug.ends_with('-') => slug.push('-'),
+            _ => {},
+        }
+    }
+    let slug = slug.trim_matches('-').to_string();
+
+    match slug.chars().all(|c| c.is_ascii_digit()) {
+        true => format!("_{}", slug),
+        false => slug,
+    }
+}
+
+// fields with few enough values to pick from a dropdown in metadata menu, the lists as Multi and the
+// single values as Select
+pub const SELECT_FIELDS: &[(&str, &str)] = &[
+    ("majors", "Multi"), ("job_types", "Multi"), ("school_years", "Multi"), ("sessions", "Multi"),
+    ("industry", "Select"), ("international", "Select"),
+];
+
+// a field's values across the companies, the options of a Select or Multi field in the fileClass,
+// field_type is set for the ones --select-fields adds as that type instead of Input
+pub struct Choice {
+    pub field: &'static str,
+    pub field_type: Option<&'static str>,
+    pub values: Vec<String>,
+}
+
+// a Multi field holds a yaml list, so the lists are written as one unless --field-format said otherwise
+pub fn parse_select_fields(specs: &[String], formats: &mut FieldFormats, explicit: &FieldFormats) -> Result<Vec<String>, Error> {
+    let mut selected = Vec::new();
+    for field in specs.iter().flat_map(|s| s.split(',')).map(str::trim).filter(|f| !f.is_empty()) {
+        let Some((field, field_type)) = SELECT_FIELDS.iter().find(|(f, _)| *f == field) else {
+            let names: Vec<&str> = SELECT_FIELDS.iter().map(|(f, _)| *f).collect();
+            return Err(Error::Usage(format!("unknown select field {}, expected one of {}", field, names.join(", "))));
+        };
+        if *field_type == "Multi" {
+            match explicit.0.iter().find(|(f, _)| f == field).map(|(_, format)| *format) {
+                None | Some(FieldFormat::List) => formats.set(field, FieldFormat::List),
+                Some(format) => return Err(Error::Usage(format!("--select-fields {} needs it written as a list, not {}", field, format.name()))),
+            }
+        }
+        selected.push(field.to_string());
+    }
+    Ok(selected)
+}
+
+pub fn choices(companies: &[CompanyEntry], selected: &[String]) -> Vec<Choice> {
+    SELECT_FIELDS.iter()
+        .map(|(field, field_type)| {
+            let mut values: Vec<String> = Vec::new();
+            for company in companies {
+                let company_values: Vec<&String> = match *field {
+                    "majors" => company.majors.iter().collect(),
+                    "job_types" => company.job_types.iter().collect(),
+                    "school_years" => company.school_years.iter().collect(),
+                    "sessions" => company.attending_sessions.iter().map(|s| &s.display_name).collect(),
+                    "industry" => company.industry.iter().collect(),
+                    _ => vec![&company.work_authorization],
+                };
+                for value in company_values.into_iter().map(|v| v.trim()).filter(|v| !v.is_empty()) {
+                    if !values.iter().any(|v| v.eq_ignore_ascii_case(value)) {
+                        values.push(value.to_string());
+                    }
+                }
+            }
+            values.sort_by_key(|v| v.to_lowercase());
+            Choice { field, field_type: selected.iter().any(|s| s == field).then_some(*field_type), values }
+        })
+        .collect()
+}
@@ -0,0 +1,66 @@
+use crate::Error;
+
+// where everything goes inside the vault, all relative to its root, so the output can follow
+// whatever layout a vault already has
+pub struct Folders {
+    pub companies: String,
+    pub classes: String,
+    pub attachments: String,
+    pub events: String,
+    pub jobs: String,
+    pub prep: String,
+    pub follow_ups: String,
+    pub comparisons: String,
+}
+
+impl Default for Folders {
+    fn default() -> Self {
+        Folders {
+            companies: "companies".to_string(),
+            classes: "classes".to_string(),
+            attachments: "attachments".to_string(),
+            events: "events".to_string(),
+            jobs: "jobs".to_string(),
+            prep: "prep".to_string(),
+            follow_ups: "follow_ups".to_string(),
+            comparisons: "comparisons".to_string(),
+        }
+    }
+}
+
+impl Folders {
+    // specs look like companies=Employers,attachments=Assets/fair, later ones win
+    pub fn parse(specs: &[String]) -> Result<Folders, Error> {
+        let mut folders = Folders::default();
+        for spec in specs.iter().flat_map(|s| s.split(',')) {
+            let (name, path) = match spec.split_once('=') {
+                Some((name, path)) => (name.trim(), path.trim().trim_matches('/')),
+                None => return Err(Error::Usage(format!("folder should look like name=path, got: {}", spec))),
+            };
+            // staying inside the vault is the whole point, obsidian wouldn't see anything outside it
+            if path.is_empty() || path.split('/').any(|part| part.is_empty() || part == "." || part == "..") || path.contains('\\') {
+                return Err(Error::Usage(format!("folder {} should be a relative path inside the vault, got: {}", name, path)));
+            }
+            let folder = match name {
+                "companies" => &mut folders.companies,
+                "classes" => &mut folders.classes,
+                "attachments" => &mut folders.attachments,
+                "events" => &mut folders.events,
+                "jobs" => &mut folders.jobs,
+                "prep" => &mut folders.prep,
+                "follow_ups" => &mut folders.follow_ups,
+                "comparisons" => &mut folders.comparisons,
+                _ => return Err(Error::Usage(format!(
+                    "unknown folder {}, expected companies, classes, attachments, events, jobs, prep, follow_ups or comparisons",
+                    name,
+                ))),
+            };
+            *folder = path.to_string();
+        }
+        Ok(folders)
+    }
+
+    pub fn logos(&self) -> String {
+        format!("{}/logos", self.attachments)
+    }
+}
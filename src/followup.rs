@@ -0,0 +1,62 @@
+use crate::labels::Labels;
+use crate::{CompanyEntry, Error, links};
+
+pub const PLACEHOLDERS: &[&str] = &["company", "website", "recruiter_name", "recruiter_email", "position"];
+
+// a typo'd placeholder would otherwise go out as literal braces in the email, so the template is
+// checked before anything renders, unknown and unclosed placeholders fail with their line numbers
+// and the placeholders it doesn't use are returned so they can be mentioned
+pub fn check_template(template: &str) -> Result<Vec<&'static str>, Error> {
+    let mut problems = Vec::new();
+    let mut used = Vec::new();
+
+    for (i, line) in template.lines().enumerate() {
+        let mut rest = line;
+        while let Some(start) = rest.find("{{") {
+            let after = &rest[start + 2..];
+            let Some(end) = after.find("}}") else {
+                problems.push(format!("unclosed {{{{ on line {}", i + 1));
+                break;
+            };
+            let name = &after[..end];
+            match PLACEHOLDERS.iter().find(|p| **p == name) {
+                Some(placeholder) => used.push(*placeholder),
+                None => problems.push(format!("unknown {{{{{}}}}} on line {}", name, i + 1)),
+            }
+            rest = &after[end + 2..];
+        }
+    }
+
+    if !problems.is_empty() {
+        return Err(Error::Template(format!(
+            "follow-up template has {}, expected {{{{company}}}}, {{{{website}}}}, {{{{recruiter_name}}}}, {{{{recruiter_email}}}} or {{{{position}}}}",
+            problems.join(", "),
+        )));
+    }
+
+    Ok(PLACEHOLDERS.iter().filter(|p| !used.contains(p)).copied().collect())
+}
+
+// placeholders the user can put in their email template, the ones we can't know
+// are swapped for a visible marker so they stand out when filling in the email,
+// the recruiter is the first contact the export lists when it lists any
+pub fn render_followup(template: &str, company: &CompanyEntry, labels: &Labels) -> String {
+    let mut text = format!("{}: {}\n\n", labels.company, links::company_link(company));
+
+    let recruiter = company.contacts.first();
+    let recruiter_name = recruiter.map(|c| c.name.as_str()).filter(|n| !n.is_empty()).unwrap_or(&labels.recruiter_marker);
+    let recruiter_email = recruiter.map(|c| c.email.as_str()).filter(|e| !e.is_empty()).unwrap_or(&labels.recruiter_marker);
+
+    text.push_str(&template
+        .replace("{{company}}", &company.name)
+        .replace("{{website}}", &company.website)
+        .replace("{{recruiter_name}}", recruiter_name)
+        .replace("{{recruiter_email}}", recruiter_email)
+        .replace("{{position}}", &labels.position_marker));
+
+    if !text.ends_with('\n') {
+        text.push('\n');
+    }
+
+    text
+}
@@ -0,0 +1,51 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use crate::export::csv_escape;
+use crate::labels::Labels;
+use crate::notify::RosterChanges;
+use crate::{Error, upload};
+
+// 20251016T140200Z as 2025-10-16 14:02 UTC
+fn now() -> String {
+    let (_, stamp) = upload::utc_timestamp();
+    format!("{}-{}-{} {}:{} UTC", &stamp[0..4], &stamp[4..6], &stamp[6..8], &stamp[9..11], &stamp[11..13])
+}
+
+fn append(path: &Path, header: &str, text: &str) -> Result<(), Error> {
+    let is_new = !path.exists();
+    let mut file = OpenOptions::new().create(true).append(true).open(path)?;
+    if is_new {
+        file.write_all(header.as_bytes())?;
+    }
+    file.write_all(text.as_bytes())?;
+    Ok(())
+}
+
+// every refresh that changed the roster adds a dated entry to the end of the note and a row per change
+// to the csv next to it, neither is ever rewritten so the timeline of the fair builds up over the runs
+pub fn append_history(note_path: &Path, changes: &RosterChanges, labels: &Labels) -> Result<(), Error> {
+    let date = now();
+
+    let mut text = format!("\n## {}\n\n", date);
+    for line in changes.summary().lines() {
+        text.push_str(&format!("- {}\n", line));
+    }
+    // linked from the note so obsidian shows it and lint doesn't take it for an unused attachment
+    let csv_path = note_path.with_extension("csv");
+    let csv_name = csv_path.file_name().unwrap_or_default().to_string_lossy().to_string();
+    append(note_path, &format!("# {}\n\n[[{}]]\n", labels.roster_history, csv_name), &text)?;
+
+    let mut rows = Vec::new();
+    rows.extend(changes.added.iter().map(|name| ["added", name, "", ""]));
+    rows.extend(changes.removed.iter().map(|name| ["removed", name, "", ""]));
+    rows.extend(changes.session_changes.iter().map(|(name, old, new)| ["sessions", name.as_str(), old.as_str(), new.as_str()]));
+    let mut csv = String::new();
+    for row in rows {
+        let fields: Vec<String> = std::iter::once(date.as_str()).chain(row).map(csv_escape).collect();
+        csv.push_str(&fields.join(","));
+        csv.push('\n');
+    }
+    append(&csv_path, "date,change,company,old_sessions,new_sessions\n", &csv)
+}
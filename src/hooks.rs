@@ -0,0 +1,31 @@
+use std::sync::Arc;
+
+use crate::CompanyEntry;
+
+// a way into the render for whatever the flags don't cover, ex. a section of your own in every note,
+// without forking the crate, both steps do nothing unless they're implemented
+pub trait Hook: Send + Sync {
+    // each company after it's been parsed, filtered, translated and summarized, just before its note,
+    // the exports and the index notes are made from it, the note's file name is already picked by then
+    fn company(&self, _company: &mut CompanyEntry) {}
+
+    // a note's text right before it's written, path is inside the vault ex. companies/Acme.md, on update
+    // it's the existing note with its sections refreshed so a section added here is already in it
+    fn note(&self, _path: &str, _text: &mut String) {}
+}
+
+// run in the order they were added to the renderer
+pub type Hooks = Vec<Arc<dyn Hook>>;
+
+pub fn apply_companies(hooks: &Hooks, companies: &mut [CompanyEntry]) {
+    for hook in hooks {
+        companies.iter_mut().for_each(|company| hook.company(company));
+    }
+}
+
+pub fn apply_note(hooks: &Hooks, path: &str, mut text: String) -> String {
+    for hook in hooks {
+        hook.note(path, &mut text);
+    }
+    text
+}
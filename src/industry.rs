@@ -0,0 +1,154 @@
+use std::fs;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{CompanyEntry, Error};
+
+// broad categories and the words that give them away, good enough to group a sparse export, a
+// company that matches none is left without one rather than guessed at
+const CATEGORIES: &[(&str, &[&str])] = &[
+    ("Technology", &["software", "saas", "cloud", "cybersecurity", "cyber", "semiconductor", "semiconductors",
+        "internet", "computing", "computer", "data", "analytics", "ai", "artificial intelligence", "machine learning",
+        "it services", "tech", "technology", "technologies", "digital"]),
+    ("Healthcare", &["health", "healthcare", "hospital", "hospitals", "medical", "medicine", "clinic", "clinical",
+        "pharmaceutical", "pharmaceuticals", "pharma", "biotech", "biotechnology", "therapeutics", "patients",
+        "patient", "nursing", "dental", "diagnostics"]),
+    ("Finance", &["bank", "banking", "financial", "finance", "insurance", "investment", "investments", "capital",
+        "credit", "accounting", "asset management", "wealth", "fintech", "trading", "tax", "audit"]),
+    ("Consulting", &["consulting", "consultancy", "advisory", "consultants", "professional services"]),
+    ("Engineering & Construction", &["construction", "engineering", "engineers", "infrastructure", "architecture",
+        "civil", "contractor", "contractors", "structural", "surveying"]),
+    ("Manufacturing", &["manufacturing", "manufacturer", "manufacturers", "industrial", "materials", "chemical",
+        "chemicals", "automotive", "machinery", "plant", "plants", "production", "equipment", "packaging"]),
+    ("Energy & Utilities", &["energy", "utility", "utilities", "oil", "gas", "power", "electric", "electricity",
+        "renewable", "renewables", "solar", "wind", "nuclear", "water"]),
+    ("Aerospace & Defense", &["aerospace", "defense", "defence", "aircraft", "aviation", "space", "satellite",
+        "satellites", "missile", "missiles"]),
+    ("Government", &["government", "federal", "agency", "public sector", "county", "city of", "state of",
+        "department of", "army", "navy", "air force", "military", "marines"]),
+    ("Education", &["university", "college", "school", "schools", "education", "educational", "teaching",
+        "teachers", "academy"]),
+    ("Retail & Consumer", &["retail", "retailer", "consumer", "consumers", "food", "foods", "beverage", "beverages",
+        "restaurant", "restaurants", "grocery", "apparel", "fashion", "stores", "brands", "hospitality", "hotel",
+        "hotels"]),
+    ("Transportation & Logistics", &["logistics", "transportation", "shipping", "freight", "rail", "railroad",
+        "trucking", "airline", "airlines", "supply chain", "delivery", "distribution"]),
+    ("Media & Entertainment", &["media", "entertainment", "publishing", "news", "broadcasting", "film", "music",
+        "gaming", "games", "advertising", "marketing"]),
+    ("Nonprofit", &["nonprofit", "non profit", "charity", "charitable", "foundation", "volunteer", "volunteers",
+        "mission driven"]),
+    ("Agriculture", &["agriculture", "agricultural", "farm", "farms", "farming", "crop", "crops", "agribusiness",
+        "livestock"]),
+];
+
+// a hit in the name says more than one somewhere in a long description
+const NAME_WEIGHT: usize = 3;
+
+pub struct IndustryModel {
+    pub path: Option<String>,
+    categories: Vec<(String, Vec<String>)>,
+}
+
+impl Default for IndustryModel {
+    fn default() -> Self {
+        let categories = CATEGORIES.iter()
+            .map(|(category, keywords)| (category.to_string(), keywords.iter().map(|k| k.to_string()).collect()))
+            .collect();
+        IndustryModel { path: None, categories }
+    }
+}
+
+// lowercase words split on anything that isn't a letter or digit, padded so a keyword can be
+// matched as whole words with a plain contains
+fn words(text: &str) -> String {
+    let words: Vec<String> = text.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(str::to_string)
+        .collect();
+    format!(" {} ", words.join(" "))
+}
+
+fn hits(text: &str, keywords: &[String]) -> usize {
+    keywords.iter().map(|k| text.matches(&format!(" {} ", k)).count()).sum()
+}
+
+impl IndustryModel {
+    // --industry-map, a yaml map of category to keywords, a built in category listed there has its
+    // keywords replaced, an empty list drops it, and new ones are checked first so they win ties
+    pub fn load(path: &str) -> Result<IndustryModel, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => return Err(Error::Usage(format!("could not read industry map {}: {}", path, e))),
+        };
+        let docs = match YamlLoader::load_from_str(&text) {
+            Ok(docs) => docs,
+            Err(e) => return Err(Error::Usage(format!("industry map {} is invalid yaml: {}", path, e))),
+        };
+        let Some(hash) = docs.first().and_then(Yaml::as_hash) else {
+            return Err(Error::Usage(format!("industry map {} should be a map of industry to keywords", path)));
+        };
+
+        let mut model = IndustryModel::default();
+        let mut added = Vec::new();
+        for (category, keywords) in hash {
+            let Some(category) = category.as_str() else {
+                return Err(Error::Usage(format!("industry map {} has a non-text industry name", path)));
+            };
+            let keywords: Vec<String> = match keywords {
+                Yaml::Array(keywords) => keywords.iter().filter_map(Yaml::as_str).map(|k| words(k).trim().to_string()).collect(),
+                Yaml::String(keyword) => vec![words(keyword).trim().to_string()],
+                Yaml::Null => Vec::new(),
+                _ => return Err(Error::Usage(format!("industry map {}: keywords for {} should be a list", path, category))),
+            };
+            model.categories.retain(|(c, _)| !c.eq_ignore_ascii_case(category));
+            if !keywords.is_empty() {
+                added.push((category.to_string(), keywords));
+            }
+        }
+        added.append(&mut model.categories);
+        model.categories = added;
+        model.path = Some(path.to_string());
+        Ok(model)
+    }
+
+    pub fn classify(&self, company: &CompanyEntry) -> Option<&str> {
+        let name = words(&company.name);
+        let description = words(&company.description);
+
+        let mut best: Option<(&str, usize)> = None;
+        for (category, keywords) in &self.categories {
+            let score = hits(&name, keywords) * NAME_WEIGHT + hits(&description, keywords);
+            if score > 0 && best.is_none_or(|(_, top)| score > top) {
+                best = Some((category, score));
+            }
+        }
+        best.map(|(category, _)| category)
+    }
+}
+
+// the industry an export gives the employer, handshake has used a few shapes for it
+pub fn payload_industry(employer: &serde_json::Value) -> Option<String> {
+    employer["industry"]["name"].as_str()
+        .or(employer["industry"].as_str())
+        .or(employer["industry_name"].as_str())
+        .or(employer["industries"][0]["name"].as_str())
+        .map(str::trim)
+        .filter(|industry| !industry.is_empty())
+        .map(str::to_string)
+}
+
+// companies the export gave no industry get one from the model, returns how many it couldn't place
+pub fn classify_missing(companies: &mut [CompanyEntry], model: &IndustryModel) -> usize {
+    let mut unplaced = 0;
+    for company in companies.iter_mut().filter(|c| c.industry.is_none()) {
+        match model.classify(company) {
+            Some(industry) => {
+                company.industry = Some(industry.to_string());
+                company.sources.push(("industry", "guessed from keywords in the name and company_description".to_string()));
+            },
+            None => unplaced += 1,
+        }
+    }
+    unplaced
+}
@@ -0,0 +1,283 @@
+use std::fs;
+use std::io::Read;
+use std::path::Path;
+
+use flate2::read::MultiGzDecoder;
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::{Error, spreadsheet, term};
+
+// * and ? only, in the file name part, for when the shell didn't expand the glob itself like
+// a quoted one or on windows
+fn glob_matches(pattern: &[char], name: &[char]) -> bool {
+    match (pattern.first(), name.first()) {
+        (None, None) => true,
+        (Some('*'), _) => glob_matches(&pattern[1..], name) || (!name.is_empty() && glob_matches(pattern, &name[1..])),
+        (Some('?'), Some(_)) => glob_matches(&pattern[1..], &name[1..]),
+        (Some(p), Some(n)) if p == n => glob_matches(&pattern[1..], &name[1..]),
+        _ => false,
+    }
+}
+
+// paths that exist, and stdin and clipboard, are taken as they are
+pub fn expand_inputs(paths: &[String]) -> Result<Vec<String>, Error> {
+    let mut expanded = Vec::new();
+    for path in paths {
+        if !path.contains(['*', '?']) || Path::new(path).exists() {
+            expanded.push(path.clone());
+            continue;
+        }
+
+        let path_buf = Path::new(path);
+        let dir = match path_buf.parent() {
+            Some(dir) if !dir.as_os_str().is_empty() => dir,
+            _ => Path::new("."),
+        };
+        let pattern: Vec<char> = path_buf.file_name().map(|n| n.to_string_lossy().chars().collect()).unwrap_or_default();
+
+        let mut matched: Vec<String> = match fs::read_dir(dir) {
+            Ok(entries) => entries
+                .filter_map(|entry| entry.ok())
+                .filter(|entry| entry.path().is_file())
+                .filter(|entry| glob_matches(&pattern, &entry.file_name().to_string_lossy().chars().collect::<Vec<char>>()))
+                .map(|entry| dir.join(entry.file_name()).to_string_lossy().to_string())
+                .collect(),
+            Err(source) => return Err(Error::Input { path: dir.to_string_lossy().to_string(), source }),
+        };
+        if matched.is_empty() {
+            return Err(Error::InvalidInput(format!("no input files match {}", path)));
+        }
+        matched.sort();
+        expanded.extend(matched);
+    }
+    Ok(expanded)
+}
+
+// gzipped input is recognised by its magic bytes rather than .gz so piped archives work too,
+// anything else is passed through as it is
+pub fn decompress(input_data: Vec<u8>) -> Result<Vec<u8>, Error> {
+    if !input_data.starts_with(&[0x1f, 0x8b]) {
+        return Ok(input_data);
+    }
+    let mut decompressed = Vec::new();
+    match MultiGzDecoder::new(input_data.as_slice()).read_to_end(&mut decompressed) {
+        Ok(_) => Ok(decompressed),
+        Err(e) => Err(Error::InvalidInput(format!("could not decompress gzipped input: {}", e))),
+    }
+}
+
+// how an input is laid out, told apart by its content so a copied results array or a csv from the
+// career office works as well as the saved api response, --format names one when it guesses wrong
+#[derive(Clone, Copy, PartialEq)]
+pub enum Layout {
+    // the api response, an object with a results array
+    Response,
+    // just the results array
+    Results,
+    // one entry per line
+    Ndjson,
+    Csv,
+    // a hand kept list of entries, or a response with a results list, written as yaml
+    Yaml,
+}
+
+pub const LAYOUTS: &[&str] = &["json", "results", "ndjson", "csv", "yaml"];
+
+impl Layout {
+    pub fn parse(name: &str) -> Option<Layout> {
+        match name {
+            "json" => Some(Layout::Response),
+            "results" => Some(Layout::Results),
+            "ndjson" => Some(Layout::Ndjson),
+            "csv" => Some(Layout::Csv),
+            "yaml" | "yml" => Some(Layout::Yaml),
+            _ => None,
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Layout::Response => "json",
+            Layout::Results => "results",
+            Layout::Ndjson => "ndjson",
+            Layout::Csv => "csv",
+            Layout::Yaml => "yaml",
+        }
+    }
+}
+
+// anything that isn't clearly one of the others is taken as the response, whose errors say best
+// what's wrong with it
+pub fn detect_layout(input_data: &[u8]) -> Layout {
+    let Some(first) = input_data.iter().find(|b| !b.is_ascii_whitespace()) else { return Layout::Response };
+    match first {
+        b'[' => match serde_json::from_slice::<serde_json::Value>(input_data) {
+            Ok(serde_json::Value::Array(entries)) if !entries.iter().all(|e| e["results"].is_array()) => Layout::Results,
+            _ => Layout::Response,
+        },
+        b'{' => {
+            let values: Vec<serde_json::Value> = serde_json::Deserializer::from_slice(input_data).into_iter().map_while(Result::ok).collect();
+            match values.len() > 1 && !values.iter().any(|v| v["results"].is_array()) {
+                true => Layout::Ndjson,
+                false => Layout::Response,
+            }
+        },
+        _ => {
+            let text = String::from_utf8_lossy(input_data);
+            if looks_like_yaml(&text) {
+                return Layout::Yaml;
+            }
+            match text.lines().find(|l| !l.trim().is_empty()).is_some_and(|l| l.contains([',', ';', '\t'])) {
+                true => Layout::Csv,
+                false => Layout::Response,
+            }
+        },
+    }
+}
+
+// a list or a key to start with and it has to load, a csv header like "name: company,location" doesn't
+fn looks_like_yaml(text: &str) -> bool {
+    let Some(first) = text.lines().map(str::trim_end).find(|l| !l.trim().is_empty() && !l.starts_with('#')) else { return false };
+    let starts_yaml = first == "---" || first.starts_with("- ")
+        || first.split_once(':').is_some_and(|(key, rest)| !key.is_empty() && !key.contains([' ', ',', ';', '\t']) && (rest.is_empty() || rest.starts_with(' ')));
+    starts_yaml && matches!(YamlLoader::load_from_str(text).as_deref(), Ok([Yaml::Array(_) | Yaml::Hash(_), ..]))
+}
+
+fn yaml_to_json(yaml: &Yaml) -> serde_json::Value {
+    match yaml {
+        Yaml::String(s) => serde_json::Value::String(s.clone()),
+        Yaml::Integer(i) => serde_json::json!(i),
+        // kept as written when it's too big or small for json, ex. .inf
+        Yaml::Real(s) => s.parse::<f64>().ok().and_then(serde_json::Number::from_f64).map(serde_json::Value::Number).unwrap_or_else(|| serde_json::Value::String(s.clone())),
+        Yaml::Boolean(b) => serde_json::Value::Bool(*b),
+        Yaml::Array(items) => serde_json::Value::Array(items.iter().map(yaml_to_json).collect()),
+        Yaml::Hash(hash) => serde_json::Value::Object(hash.iter()
+            .map(|(key, value)| {
+                let key = match key {
+                    Yaml::String(s) | Yaml::Real(s) => s.clone(),
+                    Yaml::Integer(i) => i.to_string(),
+                    Yaml::Boolean(b) => b.to_string(),
+                    _ => String::new(),
+                };
+                (key, yaml_to_json(value))
+            })
+            .collect()),
+        Yaml::Null | Yaml::Alias(_) | Yaml::BadValue => serde_json::Value::Null,
+    }
+}
+
+fn wrap(results: Vec<serde_json::Value>) -> Vec<u8> {
+    serde_json::to_vec(&serde_json::json!({ "results": results })).unwrap_or_default()
+}
+
+// the input as a handshake response, the response itself is passed through untouched so its hash
+// stays the same as before any of this
+pub fn normalize(input_data: Vec<u8>, layout: Option<Layout>, columns: &[(String, String)]) -> Result<Vec<u8>, Error> {
+    let layout = layout.unwrap_or_else(|| detect_layout(&input_data));
+    if layout != Layout::Response {
+        term::debug(&format!("reading the input as {}", layout.name()));
+    }
+    match layout {
+        Layout::Response => Ok(input_data),
+        Layout::Results => match serde_json::from_slice(&input_data) {
+            Ok(serde_json::Value::Array(results)) => Ok(wrap(results)),
+            Ok(_) => Err(Error::InvalidInput("input should be a json array of results".to_string())),
+            Err(source) => Err(Error::Json { what: "input data".to_string(), source }),
+        },
+        Layout::Ndjson => {
+            let mut results = Vec::new();
+            for entry in serde_json::Deserializer::from_slice(&input_data).into_iter() {
+                match entry {
+                    Ok(entry) => results.push(entry),
+                    // counted from 0 like the entries in every other error
+                    Err(source) => return Err(Error::Json { what: format!("entry {} of the input", results.len()), source }),
+                }
+            }
+            Ok(wrap(results))
+        },
+        Layout::Csv => spreadsheet::read_csv(&input_data, columns),
+        // a list is the results, anything else has to be laid out like the response
+        Layout::Yaml => match YamlLoader::load_from_str(&String::from_utf8_lossy(&input_data)) {
+            Ok(docs) => match docs.first().map(yaml_to_json) {
+                Some(serde_json::Value::Array(results)) => Ok(wrap(results)),
+                Some(response) if response["results"].is_array() => Ok(serde_json::to_vec(&response).unwrap_or_default()),
+                _ => Err(Error::InvalidInput("yaml input should be a list of entries or have a results list".to_string())),
+            },
+            Err(e) => Err(Error::InvalidInput(format!("input data is invalid yaml: {}", e))),
+        },
+    }
+}
+
+// job ids for job search exports, otherwise the employer id falling back on its name
+fn entry_key(entry: &serde_json::Value) -> Option<String> {
+    let job = match entry["job"].is_object() {
+        true => &entry["job"],
+        false => entry,
+    };
+    if job["title"].is_string() && entry["company_description"].is_null() {
+        return job["id"].as_u64().map(|id| format!("job:{}", id));
+    }
+    match (entry["employer"]["id"].as_u64(), entry["employer"]["name"].as_str()) {
+        (Some(id), _) => Some(format!("employer:{}", id)),
+        (None, Some(name)) => Some(format!("name:{}", name.trim().to_lowercase())),
+        (None, None) => None,
+    }
+}
+
+// the same company in the morning and afternoon exports keeps its first entry, with the sessions
+// and booths from the later one added so it shows as attending both
+fn merge_entry(kept: &mut serde_json::Value, later: &serde_json::Value) {
+    for key in ["attending_career_fair_sessions", "assigned_booth_identifiers"] {
+        let Some(extra) = later[key].as_array() else { continue };
+        if !kept[key].is_array() {
+            kept[key] = serde_json::Value::Array(Vec::new());
+        }
+        let Some(existing) = kept[key].as_array_mut() else { continue };
+        for value in extra {
+            if !existing.contains(value) {
+                existing.push(value.clone());
+            }
+        }
+    }
+}
+
+// every input's results in one payload, the rest of the first input is kept as it was, a single
+// input is passed through untouched so its hash stays the same
+pub fn merge_inputs(inputs: Vec<(String, Vec<u8>)>) -> Result<Vec<u8>, Error> {
+    if inputs.len() == 1 {
+        return Ok(inputs.into_iter().next().map(|(_, data)| data).unwrap_or_default());
+    }
+
+    let mut merged: Option<serde_json::Value> = None;
+    let mut results: Vec<serde_json::Value> = Vec::new();
+    let mut keys: Vec<Option<String>> = Vec::new();
+    let mut duplicates = 0;
+    for (path, data) in inputs {
+        let mut json: serde_json::Value = match serde_json::from_slice(&data) {
+            Ok(json) => json,
+            Err(source) => return Err(Error::Json { what: format!("input {}", path), source }),
+        };
+        let serde_json::Value::Array(entries) = json["results"].take() else {
+            return Err(Error::InvalidInput(format!("input {} is an invalid format", path)));
+        };
+        for entry in entries {
+            let key = entry_key(&entry);
+            match key.as_ref().and_then(|key| keys.iter().position(|k| k.as_ref() == Some(key))) {
+                Some(idx) => {
+                    merge_entry(&mut results[idx], &entry);
+                    duplicates += 1;
+                },
+                None => {
+                    results.push(entry);
+                    keys.push(key);
+                },
+            }
+        }
+        merged.get_or_insert(json);
+    }
+
+    term::debug(&format!("merged inputs into {} results, {} duplicates combined", results.len(), duplicates));
+    let mut merged = merged.unwrap_or_default();
+    merged["results"] = serde_json::Value::Array(results);
+    Ok(serde_json::to_vec(&merged).unwrap_or_default())
+}
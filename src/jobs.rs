@@ -0,0 +1,243 @@
+use std::fs;
+use std::path::Path;
+
+use crate::policy::Policy;
+use crate::term::{self, Status};
+use crate::{CompanyEntry, Error, deadlines, fields, industry, links, social, update};
+
+pub struct Job {
+    id: Option<u64>,
+    title: String,
+    employer: String,
+    employer_id: Option<u64>,
+    website: String,
+    logo_url: String,
+    employer_description: String,
+    location: String,
+    job_type: Option<String>,
+    deadline: Option<String>,
+    description: String,
+    url: Option<String>,
+    links: Vec<(String, String)>,
+    industry: Option<String>,
+}
+
+// a posting as it's listed under Open positions in its company's note
+#[derive(Debug, Clone)]
+pub struct Position {
+    pub title: String,
+    pub location: String,
+    pub url: Option<String>,
+}
+
+fn text(value: &serde_json::Value) -> Option<String> {
+    value.as_str().map(str::to_string)
+}
+
+// the posting itself or wrapped in a "job" key
+fn unwrap_job(entry: &serde_json::Value) -> &serde_json::Value {
+    match entry["job"].is_object() {
+        true => &entry["job"],
+        false => entry,
+    }
+}
+
+fn job_location(job: &serde_json::Value) -> String {
+    text(&job["location_name"]).or(text(&job["locations"][0]["name"])).unwrap_or_default()
+}
+
+fn job_url(job: &serde_json::Value) -> Option<String> {
+    text(&job["url"]).or(text(&job["apply_url"]))
+}
+
+// a job search export has postings in its results instead of fair registrations, each one either
+// the posting itself or wrapped in a "job" key, returns None for anything that looks like a fair
+pub fn parse_jobs(input_data: &[u8]) -> Option<Vec<Job>> {
+    let json_data: serde_json::Value = serde_json::from_slice(input_data).ok()?;
+    let entries = json_data["results"].as_array()?;
+
+    let is_job_search = entries.first().is_some_and(|e| e["job"].is_object() || (e["title"].is_string() && e["company_description"].is_null()));
+    if !is_job_search {
+        return None;
+    }
+
+    let mut jobs = Vec::new();
+    for entry in entries {
+        let job = unwrap_job(entry);
+
+        let Some(title) = text(&job["title"]) else { continue };
+        let Some(employer) = text(&job["employer"]["name"]).or(text(&job["employer_name"])) else {
+            term::status(Status::Skipped, &format!("job {}, it has no employer", title));
+            continue;
+        };
+
+        jobs.push(Job {
+            id: job["id"].as_u64(),
+            title,
+            employer,
+            employer_id: job["employer"]["id"].as_u64().or(job["employer_id"].as_u64()),
+            website: text(&job["employer"]["website"]).unwrap_or_default(),
+            logo_url: text(&job["employer"]["logo_url"]).unwrap_or_default(),
+            employer_description: text(&job["employer"]["description"]).unwrap_or_default(),
+            location: job_location(job),
+            job_type: text(&job["job_type"]["name"]).or(text(&job["job_type"])),
+            deadline: deadlines::find_deadline(job),
+            description: text(&job["description"]).unwrap_or_default(),
+            url: job_url(job),
+            links: social::find_links(&job["employer"], job["employer"]["website"].as_str().unwrap_or_default()),
+            industry: industry::payload_industry(&job["employer"]),
+        });
+    }
+
+    Some(jobs)
+}
+
+// the postings a fair entry carries itself, under "jobs" on the entry or its employer
+pub fn nested_positions(entry: &serde_json::Value) -> Vec<Position> {
+    let jobs = entry["jobs"].as_array().or(entry["employer"]["jobs"].as_array());
+    jobs.into_iter().flatten()
+        .map(unwrap_job)
+        .filter_map(|job| Some(Position { title: text(&job["title"])?, location: job_location(job), url: job_url(job) }))
+        .collect()
+}
+
+// postings from a separate job search export (--jobs) go under the fair company with the same employer
+// id, or the same name when either has no id, returns how many matched no company
+pub fn attach_positions(companies: &mut [CompanyEntry], jobs: &[Job]) -> usize {
+    let mut unmatched = 0;
+    for job in jobs {
+        let company = companies.iter_mut().find(|c| match (c.employer_id, job.employer_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => c.name.eq_ignore_ascii_case(&job.employer),
+        });
+        let Some(company) = company else {
+            unmatched += 1;
+            continue;
+        };
+        if !company.positions.iter().any(|p| p.title == job.title && p.url == job.url) {
+            company.positions.push(Position { title: job.title.clone(), location: job.location.clone(), url: job.url.clone() });
+        }
+    }
+    unmatched
+}
+
+// one company per employer so the rest of the render treats a job search like a fair roster,
+// the earliest posting deadline becomes the company's deadline
+pub fn group_by_employer(jobs: &[Job]) -> Vec<CompanyEntry> {
+    let mut companies: Vec<CompanyEntry> = Vec::new();
+
+    for job in jobs {
+        let company = match companies.iter_mut().find(|c| c.name == job.employer) {
+            Some(company) => company,
+            None => {
+                companies.push(CompanyEntry {
+                    name: job.employer.clone(),
+                    note: job.employer.clone(),
+                    description: job.employer_description.clone(),
+                    location: job.location.clone(),
+                    website: job.website.clone(),
+                    logo_url: job.logo_url.clone(),
+                    work_authorization: String::new(),
+                    job_titles: String::new(),
+                    job_types: Vec::new(),
+                    majors: Vec::new(),
+                    school_years: Vec::new(),
+                    attending_sessions: Vec::new(),
+                    employer_id: job.employer_id,
+                    booth: None,
+                    deadline: None,
+                    description_language: None,
+                    translated_description: None,
+                    links: job.links.clone(),
+                    positions: Vec::new(),
+                    industry: job.industry.clone(),
+                    contacts: Vec::new(),
+                    summary: None,
+                    overflow_note: None,
+                    sources: vec![
+                        ("location", "location_name or locations[0].name of its first posting".to_string()),
+                        ("job_titles", "title of each posting".to_string()),
+                        ("job_types", "job_type of each posting".to_string()),
+                        ("website", "employer.website of its first posting".to_string()),
+                        ("links", "employer profile urls of its first posting".to_string()),
+                        ("employer_id", "employer.id or employer_id of its first posting".to_string()),
+                        ("deadline", "the earliest apply_by, application_deadline, apply_end or expiration_date of its postings".to_string()),
+                        ("industry", "employer.industry of its first posting".to_string()),
+                    ],
+                });
+                companies.last_mut().unwrap()
+            },
+        };
+
+        company.job_titles = match company.job_titles.is_empty() {
+            true => job.title.clone(),
+            false if company.job_titles.split(", ").any(|t| t == job.title) => company.job_titles.clone(),
+            false => format!("{}, {}", company.job_titles, job.title),
+        };
+        if let Some(job_type) = &job.job_type
+            && !company.job_types.contains(job_type)
+        {
+            company.job_types.push(job_type.clone());
+        }
+        if let Some(deadline) = &job.deadline
+            && company.deadline.as_ref().is_none_or(|d| deadline < d)
+        {
+            company.deadline = Some(deadline.clone());
+        }
+    }
+
+    companies
+}
+
+fn frontmatter(job: &Job) -> Vec<(String, String)> {
+    use fields::RawValue::One;
+
+    let mut raw = vec![("company", One(job.employer.clone()))];
+    if let Some(job_type) = &job.job_type {
+        raw.push(("job_type", One(job_type.clone())));
+    }
+    raw.push(("location", One(job.location.clone())));
+    if let Some(deadline) = &job.deadline {
+        raw.push(("deadline", One(deadline.clone())));
+    }
+    if let Some(url) = &job.url {
+        raw.push(("url", One(url.clone())));
+    }
+    if let Some(id) = job.id {
+        raw.push(("job_id", One(id.to_string())));
+    }
+
+    let mut formats = fields::FieldFormats::default();
+    formats.set("company", fields::FieldFormat::Link);
+    fields::format_frontmatter(raw, &formats)
+}
+
+// jobs/<employer>/<title>.md so postings sit together under their company, a repeated title
+// gets the posting id added to tell them apart
+pub fn write_jobs(jobs_dir: &Path, jobs: &[Job], policy: Policy) -> Result<usize, Error> {
+    let mut used_names: Vec<String> = Vec::new();
+    let mut written = 0;
+
+    for job in jobs {
+        let employer_dir = jobs_dir.join(links::short_name(links::note_name(&job.employer).trim()));
+        fs::create_dir_all(&employer_dir)?;
+
+        let mut name = links::short_name(links::note_name(&job.title).trim());
+        let key = format!("{}/{}", job.employer, name);
+        if used_names.contains(&key) && let Some(id) = job.id {
+            name = format!("{} ({})", name, id);
+        }
+        used_names.push(key);
+
+        let file_path = employer_dir.join(format!("{}.md", name));
+        let sections = vec![("job", format!("# {}\n\n{}\n", job.title, job.description.trim()))];
+
+        match update::write_or_update(&file_path, &frontmatter(job), &sections, None) {
+            Ok(true) => written += 1,
+            Ok(false) => {},
+            Err(e) => policy.fail(&format!("{}: {}", file_path.to_string_lossy(), e))?,
+        }
+    }
+
+    Ok(written)
+}
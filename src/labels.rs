@@ -0,0 +1,155 @@
+use std::fs;
+use std::path::Path;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::Error;
+
+// every piece of text the renderer writes into the vault itself, so a template can ship
+// labels.<lang>.yaml with translations, any key left out keeps the english default
+// {company} and {date} are filled in where they make sense
+pub struct Labels {
+    pub description: String,
+    pub original_description: String,
+    pub deadlines: String,
+    pub apply_by: String,
+    pub apply_task: String,
+    pub company: String,
+    pub recruiter_marker: String,
+    pub position_marker: String,
+    pub schedule: String,
+    pub session_full: String,
+    pub registered: String,
+    pub capacity: String,
+    pub waitlisted: String,
+    pub checklist: String,
+    pub booth: String,
+    pub links: String,
+    pub team: String,
+    pub recruits_all: String,
+    pub recruits_any: String,
+    pub coverage: String,
+    pub board: String,
+    pub unclaimed: String,
+    pub claimed_twice: String,
+    pub summary: String,
+    pub pending_followups: String,
+    pub roster_history: String,
+    pub blocklisted: String,
+    pub open_positions: String,
+    pub industries: String,
+    pub contacts: String,
+    pub starred: String,
+    pub starred_missing: String,
+    pub ungrouped: String,
+    pub full_description: String,
+    pub cut_short: String,
+}
+
+impl Default for Labels {
+    fn default() -> Self {
+        Labels {
+            description: "Description".to_string(),
+            original_description: "Original description".to_string(),
+            deadlines: "Deadlines".to_string(),
+            apply_by: "Apply to {company} by {date}".to_string(),
+            apply_task: "Apply to {company}".to_string(),
+            company: "Company".to_string(),
+            recruiter_marker: "[recruiter name]".to_string(),
+            position_marker: "[position discussed]".to_string(),
+            schedule: "Schedule".to_string(),
+            session_full: "Full, register early".to_string(),
+            registered: "Registered".to_string(),
+            capacity: "Capacity".to_string(),
+            waitlisted: "Waitlisted".to_string(),
+            checklist: "Checklist".to_string(),
+            booth: "booth".to_string(),
+            links: "Links".to_string(),
+            team: "Team".to_string(),
+            recruits_all: "Recruiting all of us".to_string(),
+            recruits_any: "Recruiting any of us".to_string(),
+            coverage: "Coverage".to_string(),
+            board: "Board".to_string(),
+            unclaimed: "Unclaimed".to_string(),
+            claimed_twice: "Claimed more than once".to_string(),
+            summary: "Fair summary".to_string(),
+            pending_followups: "Pending follow-ups".to_string(),
+            roster_history: "Roster history".to_string(),
+            blocklisted: "On your blocklist".to_string(),
+            open_positions: "Open positions".to_string(),
+            industries: "Industries".to_string(),
+            contacts: "Contacts".to_string(),
+            starred: "Starred".to_string(),
+            starred_missing: "Starred but not at this fair".to_string(),
+            ungrouped: "Other".to_string(),
+            full_description: "Full description".to_string(),
+            cut_short: "Cut short, the full description is {size}".to_string(),
+        }
+    }
+}
+
+impl Labels {
+    pub fn load(template_path: &Path, lang: &str) -> Result<Labels, Error> {
+        let path = template_path.join(format!("labels.{}.yaml", lang));
+        let text = match fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => return Err(Error::Template(format!("could not read {}: {}", path.to_string_lossy(), e))),
+        };
+
+        let docs = match YamlLoader::load_from_str(&text) {
+            Ok(docs) => docs,
+            Err(e) => return Err(Error::Template(format!("invalid yaml in {}: {}", path.to_string_lossy(), e))),
+        };
+        let Some(doc) = docs.first() else {
+            return Ok(Labels::default());
+        };
+
+        let mut labels = Labels::default();
+        let fields = [
+            ("description", &mut labels.description),
+            ("original_description", &mut labels.original_description),
+            ("deadlines", &mut labels.deadlines),
+            ("apply_by", &mut labels.apply_by),
+            ("apply_task", &mut labels.apply_task),
+            ("company", &mut labels.company),
+            ("recruiter_marker", &mut labels.recruiter_marker),
+            ("position_marker", &mut labels.position_marker),
+            ("schedule", &mut labels.schedule),
+            ("session_full", &mut labels.session_full),
+            ("registered", &mut labels.registered),
+            ("capacity", &mut labels.capacity),
+            ("waitlisted", &mut labels.waitlisted),
+            ("checklist", &mut labels.checklist),
+            ("booth", &mut labels.booth),
+            ("links", &mut labels.links),
+            ("team", &mut labels.team),
+            ("recruits_all", &mut labels.recruits_all),
+            ("recruits_any", &mut labels.recruits_any),
+            ("coverage", &mut labels.coverage),
+            ("board", &mut labels.board),
+            ("unclaimed", &mut labels.unclaimed),
+            ("claimed_twice", &mut labels.claimed_twice),
+            ("summary", &mut labels.summary),
+            ("pending_followups", &mut labels.pending_followups),
+            ("roster_history", &mut labels.roster_history),
+            ("blocklisted", &mut labels.blocklisted),
+            ("open_positions", &mut labels.open_positions),
+            ("industries", &mut labels.industries),
+            ("contacts", &mut labels.contacts),
+            ("starred", &mut labels.starred),
+            ("starred_missing", &mut labels.starred_missing),
+            ("ungrouped", &mut labels.ungrouped),
+            ("full_description", &mut labels.full_description),
+            ("cut_short", &mut labels.cut_short),
+        ];
+        for (key, field) in fields {
+            match &doc[key] {
+                Yaml::String(value) => *field = value.clone(),
+                Yaml::BadValue => {},
+                _ => return Err(Error::Template(format!("label {} in {} should be a string", key, path.to_string_lossy()))),
+            }
+        }
+
+        Ok(labels)
+    }
+}
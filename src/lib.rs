@@ -0,0 +1,1590 @@
+// the library on its own only renders what it's handed, the flags that reach the rest, like --fetch,
+// --blocklist or --export, are the command line's
+#![cfg_attr(not(feature = "cli"), allow(dead_code))]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+
+use yaml_rust2::{Yaml, YamlEmitter};
+use yaml_rust2::yaml::Hash;
+
+use term::Status;
+
+#[cfg(feature = "cli")]
+mod anonymize;
+mod api;
+mod attachments;
+#[cfg(feature = "cli")]
+mod auth;
+mod blocklist;
+#[cfg(feature = "tui")]
+mod browse;
+mod build_info;
+mod cards;
+mod claims;
+#[cfg(feature = "cli")]
+mod cli;
+#[cfg(feature = "cli")]
+mod clipboard;
+#[cfg(feature = "cli")]
+mod commands;
+#[cfg(feature = "cli")]
+mod compare;
+#[cfg(feature = "cli")]
+mod completions;
+#[cfg(feature = "cli")]
+mod config;
+mod contacts;
+mod deadlines;
+mod encoding;
+mod events;
+#[cfg(feature = "cli")]
+mod exit;
+mod export;
+#[cfg(feature = "cli")]
+mod extract;
+mod fetch;
+mod fields;
+mod folders;
+mod history;
+mod hooks;
+mod followup;
+mod industry;
+mod inputs;
+mod jobs;
+mod labels;
+mod limits;
+mod links;
+#[cfg(feature = "cli")]
+mod lint;
+mod manifest;
+mod mapping;
+mod naming;
+mod notify;
+#[cfg(feature = "cli")]
+mod open;
+mod oversize;
+mod plain;
+mod policy;
+mod prep;
+mod progress;
+mod provenance;
+#[cfg(feature = "python")]
+mod python;
+mod rename;
+mod report;
+mod route;
+mod schedule;
+mod social;
+mod sources;
+mod spreadsheet;
+mod starred;
+mod state;
+#[cfg(feature = "cli")]
+mod stats;
+mod subset;
+mod summarize;
+#[cfg(feature = "cli")]
+mod summary;
+mod team;
+#[cfg(feature = "test-support")]
+pub mod test_support;
+mod term;
+mod translate;
+mod update;
+mod upload;
+mod validate;
+mod views;
+#[cfg(feature = "wasm")]
+mod wasm;
+
+// what other tools build on, the command line goes through the same render
+pub use api::{Fair, VaultRenderer};
+#[cfg(feature = "cli")]
+pub use commands::run;
+pub use contacts::Contact;
+pub use hooks::Hook;
+pub use jobs::Position;
+pub use report::{RenderReport, WriteFailure};
+
+// the kinds of failure a caller might handle differently, anything else is a message
+#[derive(Debug, thiserror::Error)]
+pub enum Error {
+    #[error("io error: {0}")]
+    Io(#[from] io::Error),
+    // what is ex. input data or a settings file's path
+    #[error("{what} is invalid json: {source}")]
+    Json { what: String, source: serde_json::Error },
+    // index is the entry's place in the input, company its name when it has one and field the path to
+    // the value in the entry's json
+    #[error("{}: {field}: {problem}", policy::entry_label(*index, company.as_deref()))]
+    MissingField { index: usize, company: Option<String>, field: String, problem: String },
+    // a flag, config key or environment variable that can't be used as given
+    #[error("{0}")]
+    Usage(String),
+    // input that was read but isn't something a render can go by, ex. empty or the wrong shape
+    #[error("{0}")]
+    InvalidInput(String),
+    #[error("{0}")]
+    Template(String),
+    // path is stdin when the input was piped in
+    #[error("failed reading {path}: {source}")]
+    Input { path: String, source: io::Error },
+    #[error("failed writing {path}: {source}")]
+    Output { path: String, source: io::Error },
+    #[error("{0}")]
+    Other(String),
+}
+
+// what a build without one of the optional cargo features says when it's asked to do that anyway
+#[cfg_attr(all(feature = "network", feature = "xlsx", feature = "pdf", feature = "keyring", feature = "tui"), allow(dead_code))]
+fn missing_feature(what: &str, feature: &str) -> Error {
+    Error::Other(format!("{} needs the {} feature, this build was made without it, rebuild with --features {}", what, feature, feature))
+}
+
+#[derive(Debug)]
+pub struct CompanyEntry {
+    pub name: String,
+    // the note's file name without .md, the name unless --note-name says otherwise
+    pub note: String,
+    pub description: String,
+    pub location: String,
+    pub website: String,
+    pub logo_url: String,
+    pub work_authorization: String,
+    pub job_titles: String,
+    pub job_types: Vec<String>,
+    pub majors: Vec<String>,
+    pub school_years: Vec<String>,
+    pub attending_sessions: Vec<Session>,
+    pub employer_id: Option<u64>,
+    pub booth: Option<String>,
+    pub deadline: Option<String>,
+    pub description_language: Option<String>,
+    pub translated_description: Option<String>,
+    // (label, url) of the employer's profiles, linkedin, careers page and so on
+    pub links: Vec<(String, String)>,
+    // the jobs it's hiring for, from the entry itself or --jobs
+    pub positions: Vec<jobs::Position>,
+    // from the export, or guessed from the name and description when it has none
+    pub industry: Option<String>,
+    // recruiters and reps the export lists for it
+    pub contacts: Vec<contacts::Contact>,
+    // a sentence or two of the description for tables and cards, with --summarize
+    pub summary: Option<String>,
+    // the whole description when it was too long for the note and moved into its own, see oversize
+    pub overflow_note: Option<String>,
+    // frontmatter key and where its value came from, printed with -vv
+    pub sources: Vec<(&'static str, String)>,
+}
+
+#[derive(Debug)]
+pub struct Session {
+    pub id: Option<u64>,
+    pub display_name: String,
+    // what a link to the session points at, see naming
+    pub note: String,
+    pub start: Option<String>,
+    pub end: Option<String>,
+    pub registered: Option<u64>,
+    pub capacity: Option<u64>,
+    pub waitlisted: Option<u64>,
+}
+
+// used when -t isn't given, relative so it works from a checkout of the repo
+const DEFAULT_TEMPLATE: &str = "./vault_templates/career_fair_2025_template";
+
+struct CliArgs {
+    input_paths: Vec<String>,
+    columns: Vec<(String, String)>,
+    mapping: Option<mapping::Mapping>,
+    format: Option<String>,
+    layout: Option<inputs::Layout>,
+    encoding: Option<encoding::Encoding>,
+    jobs_path: Option<String>,
+    fetch_url: Option<String>,
+    token: Option<String>,
+    interval: Option<std::time::Duration>,
+    update: bool,
+    resume: bool,
+    check: bool,
+    output_path: Option<String>,
+    template_path: Option<String>,
+    followup_template_path: Option<String>,
+    note_stub_path: Option<String>,
+    exports: Vec<(&'static dyn export::Exporter, String)>,
+    upload_target: Option<upload::UploadTarget>,
+    webhook_url: Option<String>,
+    lang: Option<String>,
+    field_formats: fields::FieldFormats,
+    select_fields: Vec<String>,
+    key_prefix: String,
+    class_name: String,
+    class_per_fair: bool,
+    note_name: Option<String>,
+    folders: folders::Folders,
+    output: plain::Output,
+    translate_cmd: Option<String>,
+    translate_to: String,
+    summarize: bool,
+    summary_sentences: usize,
+    summarize_cmd: Option<String>,
+    tasks_format: bool,
+    download_logos: bool,
+    limits: limits::Limits,
+    max_field_size: usize,
+    oversized: oversize::Oversized,
+    prep: bool,
+    my_majors: Vec<String>,
+    team: Vec<team::Member>,
+    claims: bool,
+    blocklist: Option<blocklist::Blocklist>,
+    industries: industry::IndustryModel,
+    starred: Option<starred::Starred>,
+    session: Option<String>,
+    mini: bool,
+    views: Vec<views::View>,
+    build_info: bool,
+    skip_invalid: bool,
+    report_path: Option<String>,
+    // the names picked in browse, everything else in the input is left out
+    picked: Option<BTreeSet<String>>,
+    // only set by the library, the command line has no way to load them
+    hooks: hooks::Hooks,
+    prune_mode: attachments::PruneMode,
+    policy: policy::Policy,
+    open: bool,
+    register: bool,
+}
+
+// what a render does with nothing given, the library starts from these and sets what it has options for
+impl Default for CliArgs {
+    fn default() -> Self {
+        CliArgs {
+            input_paths: Vec::new(),
+            columns: Vec::new(),
+            mapping: None,
+            format: None,
+            layout: None,
+            encoding: None,
+            jobs_path: None,
+            fetch_url: None,
+            token: None,
+            interval: None,
+            update: false,
+            resume: false,
+            check: false,
+            output_path: None,
+            template_path: None,
+            followup_template_path: None,
+            note_stub_path: None,
+            exports: Vec::new(),
+            upload_target: None,
+            webhook_url: None,
+            lang: None,
+            field_formats: fields::FieldFormats::default(),
+            select_fields: Vec::new(),
+            key_prefix: String::new(),
+            class_name: "company".to_string(),
+            class_per_fair: false,
+            note_name: None,
+            folders: folders::Folders::default(),
+            output: plain::Output::default(),
+            translate_cmd: None,
+            translate_to: "en".to_string(),
+            summarize: false,
+            summary_sentences: 2,
+            summarize_cmd: None,
+            tasks_format: false,
+            download_logos: false,
+            limits: limits::Limits::default(),
+            max_field_size: oversize::DEFAULT_MAX_FIELD_SIZE,
+            oversized: oversize::Oversized::Move,
+            prep: false,
+            my_majors: Vec::new(),
+            team: Vec::new(),
+            claims: false,
+            blocklist: None,
+            industries: industry::IndustryModel::default(),
+            starred: None,
+            session: None,
+            mini: false,
+            views: Vec::new(),
+            build_info: true,
+            skip_invalid: false,
+            report_path: None,
+            picked: None,
+            hooks: Vec::new(),
+            prune_mode: attachments::PruneMode::Archive,
+            policy: policy::Policy::Warn,
+            open: false,
+            register: false,
+        }
+    }
+}
+
+// what --link-fields turns into wikilinks
+const LINK_FIELDS: &[&str] = &["location", "majors", "sessions"];
+
+// the fileClass name is also its file name in classes/, so it's held to the same characters as key prefixes
+fn check_class_name(class_name: String) -> Result<String, Error> {
+    if class_name.is_empty() || !class_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(Error::Usage(format!("class name {} can only use letters, numbers, _ and -", class_name)));
+    }
+    Ok(class_name)
+}
+
+// keys end up as yaml keys and metadata menu field names, anything fancier than this breaks one of them
+fn check_key_prefix(key_prefix: String) -> Result<String, Error> {
+    if !key_prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        return Err(Error::Usage(format!("key prefix {} can only use letters, numbers, _ and -", key_prefix)));
+    }
+    Ok(key_prefix)
+}
+
+// takes any export a source recognizes, a career fair or a job search, never panics on bad input so it
+// is safe to point at whatever handshake sends back, strict about missing fields where a render goes by --strict/--lenient
+pub fn parse_input(input_data: &[u8]) -> Result<Vec<CompanyEntry>, Error> {
+    let input_data = encoding::to_utf8(input_data.to_vec(), None)?;
+    let input_data = inputs::normalize(fetch::merge_pages(input_data), None, &[])?;
+    sources::detect(&input_data).parse(&input_data, policy::Policy::Strict)
+}
+
+fn render_run(cli_args: &CliArgs, input_data: Vec<u8>, report: &mut report::RenderReport) -> Result<(), Error> {
+    let input_data = match cli_args.skip_invalid {
+        true => {
+            let (valid_data, skipped) = validate::skip_invalid(&input_data)?;
+            for (entry, reason) in &skipped {
+                term::debug(&format!("skipping {}, {}", entry, reason));
+            }
+            report.skipped_entries = skipped;
+            valid_data
+        },
+        false => input_data,
+    };
+
+    // --format or whichever source recognizes the input, a job search goes through the same render
+    // with one company per employer
+    term::phase("parsing", None);
+    let source = sources::pick_source(cli_args.format.as_deref(), &input_data)?;
+    term::debug(&format!("reading the input as {}", source.name()));
+    let jobs = source.jobs(&input_data);
+    let mut companies = source.parse(&input_data, cli_args.policy)?;
+    if let Some(name) = &cli_args.session {
+        let session = schedule::keep_session(&mut companies, name)?;
+        term::debug(&format!("only rendering the {} companies at {}", companies.len(), session));
+    }
+    if let Some(picked) = &cli_args.picked {
+        companies.retain(|c| picked.contains(&c.name));
+        term::debug(&format!("only rendering the {} companies picked", companies.len()));
+    }
+    if let Some(blocklist) = &cli_args.blocklist {
+        let removed = blocklist.remove_blocked(&mut companies);
+        for (name, reason) in &removed {
+            term::status(Status::Skipped, &format!("{}, it's on the blocklist: {}", name, reason));
+        }
+        if !removed.is_empty() {
+            term::info(&format!("left out {} companies on the blocklist", removed.len()));
+        }
+    }
+    if let Some(path) = &cli_args.jobs_path {
+        let jobs_data = match fs::read(path) {
+            Ok(bytes) => fetch::merge_pages(encoding::to_utf8(inputs::decompress(bytes)?, None)?),
+            Err(source) => return Err(Error::Input { path: path.clone(), source }),
+        };
+        let Some(postings) = jobs::parse_jobs(&jobs_data) else {
+            return Err(Error::InvalidInput(format!("{} isn't a job search export", path)));
+        };
+        let unmatched = jobs::attach_positions(&mut companies, &postings);
+        if unmatched > 0 {
+            term::warn(&format!("{} postings in {} are from employers that aren't at the fair", unmatched, path));
+        }
+    }
+    if let Some(starred) = &cli_args.starred {
+        let missing = starred.missing(&companies);
+        if !missing.is_empty() {
+            term::warn(&format!("{} starred companies aren't at the fair: {}", missing.len(), missing.join(", ")));
+        }
+    }
+    let unplaced = industry::classify_missing(&mut companies, &cli_args.industries);
+    if unplaced > 0 {
+        term::debug(&format!("{} companies matched no industry keywords and were left without one", unplaced));
+    }
+
+    let naming = naming::Naming::new(cli_args.note_name.as_deref(), &input_data, &companies)?;
+    naming.apply(&mut companies);
+
+    let translator: Box<dyn translate::Translator> = match &cli_args.translate_cmd {
+        Some(command) => Box::new(translate::CommandTranslator { command: command.clone(), timeout: cli_args.limits.timeout }),
+        None => Box::new(translate::NoopTranslator),
+    };
+    let cache_path = cli_args.output_path.as_ref().map(|p| translate::cache_path(&PathBuf::from(p)));
+    let mut translation_cache = translate::TranslationCache::open(cache_path);
+    translate::translate_companies(&mut companies, translator.as_ref(), &cli_args.translate_to, &mut translation_cache, cli_args.policy)?;
+
+    let mut summary_cache = translate::TranslationCache::open(cli_args.output_path.as_ref().map(|p| summarize::cache_path(&PathBuf::from(p))));
+    if cli_args.summarize {
+        let sentences = summarize::SentenceSummarizer { sentences: cli_args.summary_sentences };
+        match &cli_args.summarize_cmd {
+            Some(command) => {
+                let summarizer = summarize::CommandSummarizer { command: command.clone(), sentences: cli_args.summary_sentences, timeout: cli_args.limits.timeout };
+                summarize::summarize_companies(&mut companies, &summarizer, &sentences, Some(&mut summary_cache), cli_args.policy)?;
+            },
+            None => summarize::summarize_companies(&mut companies, &sentences, &sentences, None, cli_args.policy)?,
+        }
+    }
+
+    hooks::apply_companies(&cli_args.hooks, &mut companies);
+    term::debug(&format!("rendering data for {} companies", companies.len()));
+    report.companies = companies.len();
+    // still renders so an early export before any employers register gives a working vault
+    if companies.is_empty() {
+        term::warn("the input has no companies yet, the vault will be empty");
+    }
+
+    for (exporter, path) in &cli_args.exports {
+        exporter.write(path, &companies)?;
+        term::debug(&format!("exported {}:{}", exporter.name(), path));
+    }
+
+    let template_path = match &cli_args.template_path {
+        Some(path) => path,
+        None => DEFAULT_TEMPLATE,
+    };
+
+    let file_class_bytes = match fs::read(PathBuf::from(template_path).join("classes/company.md")) {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(Error::Template(format!("could not read template path: {}", e))),
+    };
+
+    let user_additions: &[&str] = match cli_args.claims {
+        true => &[claims::CLAIM_FIELD],
+        false => &[],
+    };
+    let choices = fields::choices(&companies, &cli_args.select_fields);
+    let (mut user_fields, new_fileclass, _) = parse_fileclass_with_prefix(&file_class_bytes, &cli_args.key_prefix, user_additions, &choices)?;
+
+    let labels = match &cli_args.lang {
+        Some(lang) => labels::Labels::load(&PathBuf::from(template_path), lang)?,
+        None => labels::Labels::default(),
+    };
+
+    // copied verbatim into every company note, so Templater/QuickAdd syntax like <% tp.file.title %>
+    // is left for those plugins to expand when the note is opened
+    let note_stub = match &cli_args.note_stub_path {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => Some(text),
+            Err(e) => return Err(Error::Template(format!("could not read note stub: {}", e))),
+        },
+        None => fs::read_to_string(PathBuf::from(template_path).join("stubs/company.md")).ok(),
+    };
+
+    let followup_template = match &cli_args.followup_template_path {
+        Some(path) => match fs::read_to_string(path) {
+            Ok(text) => Some(text),
+            Err(e) => return Err(Error::Template(format!("could not read follow-up template: {}", e))),
+        },
+        None => None,
+    };
+    if let Some(template) = &followup_template {
+        let unused = followup::check_template(template)?;
+        if !unused.is_empty() {
+            term::debug(&format!("follow-up template doesn't use {}", unused.join(", ")));
+        }
+    }
+
+    let output_path = match &cli_args.output_path {
+        Some(path) => path.clone(),
+        None => {
+            if cli_args.exports.is_empty() {
+                term::info("Exiting with no output");
+            }
+            report.print();
+            return Ok(())
+        },
+    };
+
+    if !cli_args.output.is_vault() {
+        let files = plain::render_plain(&companies, &cli_args.field_formats, &cli_args.folders.companies, &cli_args.team, &naming, &labels, &cli_args.output);
+        let mut written = 0;
+        term::phase("writing notes", Some(files.len()));
+        for (name, text) in &files {
+            term::advance(name);
+            let text = hooks::apply_note(&cli_args.hooks, name, text.clone());
+            let file_path = PathBuf::from(output_path.clone()).join(name);
+            if let Some(parent) = file_path.parent() {
+                fs::create_dir_all(parent).map_err(|source| Error::Output { path: parent.to_string_lossy().to_string(), source })?;
+            }
+            match fs::write(&file_path, &text) {
+                Ok(()) => {
+                    written += 1;
+                    report.wrote(name);
+                    term::status(Status::Written, &file_path.to_string_lossy());
+                },
+                Err(e) => cli_args.policy.fail(&format!("{}: {}", file_path.to_string_lossy(), e))?,
+            }
+        }
+        term::debug(&format!("wrote {} of {} notes", written, files.len()));
+        if let Some(target) = &cli_args.upload_target {
+            term::phase("uploading", None);
+            let uploaded = upload::upload_vault(&PathBuf::from(output_path.clone()), target, cli_args.limits.timeout)?;
+            term::debug(&format!("uploaded {} files", uploaded));
+        }
+        report.print();
+        return Ok(());
+    }
+
+    // only the notes are cut, the exports and plain files above keep the whole text
+    let guarded = oversize::guard_fields(&mut companies, cli_args.max_field_size, cli_args.oversized, &cli_args.folders, &labels);
+    if guarded > 0 {
+        term::debug(&format!("cut {} descriptions longer than {} bytes", guarded, cli_args.max_field_size));
+    }
+
+    let class_name = file_class_name(cli_args, &input_data)?;
+    let class_file = format!("{}/{}.md", cli_args.folders.classes, class_name);
+
+    // an existing vault keeps its own template files and fileClass, only the notes get refreshed, with a
+    // class per fair the vault can already be there from an earlier fair without this fair's class
+    let is_vault = || PathBuf::from(output_path.clone()).join(&class_file).exists()
+        || PathBuf::from(output_path.clone()).join(".obsidian").is_dir();
+    let input_hash = manifest::hash_input(&input_data);
+    let stamp = cli_args.build_info.then(|| build_info::stamp(&input_hash));
+
+    if cli_args.check && !is_vault() {
+        return Err(Error::Usage(format!("{} doesn't contain a vault to check", output_path)));
+    }
+    // another run on the same vault, like a cron job while an --interval one is going, finishes first,
+    // the lock is the first thing written so two renders into a new folder don't both copy the template,
+    // whether there's a vault is only known once the other run is done with it
+    let lock = state::VaultLock::acquire(&PathBuf::from(output_path.clone()))?;
+    let vault_exists = is_vault();
+
+    if cli_args.check {
+        let drift = check_vault(&PathBuf::from(output_path.clone()), &companies, cli_args, &class_name, &labels, &naming, stamp.as_deref());
+        for line in &drift {
+            term::info(line);
+        }
+        return match drift.len() {
+            0 => {
+                term::info("vault is up to date with the input");
+                Ok(())
+            },
+            n => {
+                Err(Error::Other(format!("vault has drifted from the input, {} files differ", n)))
+            },
+        };
+    }
+
+    let mut manifest = manifest::Manifest::load(&PathBuf::from(output_path.clone())).unwrap_or_default();
+    let config = render_config(cli_args, &class_name);
+
+    // rendering the same input the same way into the same vault again is fine, there is nothing to do,
+    // manifests from before the config was recorded only have the input to go by
+    if vault_exists && !cli_args.update {
+        if manifest.input_hash == input_hash && (manifest.config.is_null() || manifest.config == config) {
+            term::info("vault is already up to date with the input");
+            return Ok(());
+        }
+        if progress::unfinished(&PathBuf::from(output_path.clone())) {
+            return Err(Error::Usage(format!("{} contains an unfinished render, run fair_renderer update --resume to pick up where it left off", output_path)));
+        }
+        return Err(Error::Usage(format!("{} already contains a vault, run fair_renderer update to refresh it", output_path)));
+    }
+
+    if !vault_exists {
+        term::phase("copying template", None);
+        // a new vault goes into a folder that only holds its lock
+        let entries = fs::read_dir(&output_path).map_err(|source| Error::Output { path: output_path.clone(), source })?;
+        if entries.flatten().any(|entry| entry.file_name() != ".fair_renderer") {
+            return Err(Error::Usage(format!("failed copying template to output path: {} already has files in it", output_path)));
+        }
+        if let Err(source) = copy_template(Path::new(template_path), Path::new(&output_path)) {
+            return Err(Error::Output { path: output_path.clone(), source });
+        };
+        if cli_args.folders.classes != "classes" {
+            move_classes(&PathBuf::from(output_path.clone()), &cli_args.folders.classes)?;
+        }
+        // the template's class is renamed rather than left behind next to the configured one
+        if class_name != "company" {
+            let template_class = PathBuf::from(output_path.clone()).join(&cli_args.folders.classes).join("company.md");
+            fs::remove_file(&template_class).map_err(|source| Error::Output { path: template_class.to_string_lossy().to_string(), source })?;
+        }
+        write_file(PathBuf::from(output_path.clone()).join(&class_file), &new_fileclass)?;
+    }
+
+    // the vault's own fileClass wins over the template, it only gets whatever renderer fields it's missing
+    let vault_fileclass_path = PathBuf::from(output_path.clone()).join(&class_file);
+    if vault_exists && !vault_fileclass_path.exists() {
+        let classes_path = PathBuf::from(output_path.clone()).join(&cli_args.folders.classes);
+        fs::create_dir_all(&classes_path).map_err(|source| Error::Output { path: classes_path.to_string_lossy().to_string(), source })?;
+        write_file(&vault_fileclass_path, &new_fileclass)?;
+        term::status(Status::Written, &vault_fileclass_path.to_string_lossy());
+    } else if vault_exists {
+        let vault_fileclass = match fs::read(&vault_fileclass_path) {
+            Ok(bytes) => bytes,
+            Err(e) => return Err(Error::Template(format!("could not read {}: {}", vault_fileclass_path.to_string_lossy(), e))),
+        };
+        let (vault_fields, merged_fileclass, changed) = match parse_fileclass_with_prefix(&vault_fileclass, &cli_args.key_prefix, user_additions, &choices) {
+            Ok(parsed) => parsed,
+            Err(e) => return Err(Error::Template(format!("{} in {}", e, vault_fileclass_path.to_string_lossy()))),
+        };
+        if changed > 0 {
+            write_file(&vault_fileclass_path, merged_fileclass)?;
+            term::status(Status::Updated, &vault_fileclass_path.to_string_lossy());
+        }
+        user_fields = vault_fields;
+    }
+
+    let companies_dir = PathBuf::from(output_path.clone()).join(&cli_args.folders.companies);
+    fs::create_dir_all(&companies_dir).map_err(|source| Error::Output { path: companies_dir.to_string_lossy().to_string(), source })?;
+
+    if vault_exists {
+        let renames = rename::find_renames(&companies_dir, &companies, &cli_args.key_prefix);
+        rename::apply_renames(&PathBuf::from(output_path.clone()), &renames, &naming.index_notes(), &cli_args.folders, &mut manifest, cli_args.policy)?;
+
+        // a new --note-name would leave the index notes under their old names, they're moved so
+        // the checklist keeps its ticks and the rest get rewritten in place
+        let previous_scheme = manifest.config["note_name"].as_str();
+        if previous_scheme != cli_args.note_name.as_deref()
+            && let Ok(previous) = naming::Naming::new(previous_scheme, &input_data, &companies)
+        {
+            for (old_name, new_name) in previous.index_notes().iter().zip(naming.index_notes()) {
+                let old_path = PathBuf::from(output_path.clone()).join(old_name);
+                let new_path = PathBuf::from(output_path.clone()).join(&new_name);
+                if old_path != new_path && old_path.exists() && !new_path.exists() {
+                    let failed = |source| Error::Output { path: new_path.to_string_lossy().to_string(), source };
+                    fs::rename(&old_path, &new_path).map_err(failed)?;
+                    // the roster history keeps its csv next to it
+                    if old_path.with_extension("csv").exists() {
+                        fs::rename(old_path.with_extension("csv"), new_path.with_extension("csv")).map_err(failed)?;
+                    }
+                    term::status(Status::Updated, &format!("{} is now {}", old_path.to_string_lossy(), new_name));
+                }
+            }
+        }
+    }
+
+    // only meaningful when refreshing, a brand new vault would report every company as added
+    let roster_changes = match vault_exists && !cli_args.mini {
+        true => Some(notify::diff_roster(&companies_dir, &companies, &cli_args.field_formats, &cli_args.key_prefix, &class_name)),
+        false => None,
+    };
+
+    let (mut new_count, mut updated_count) = (0, 0);
+    let mut downloader = limits::Downloader::new(&cli_args.limits, cli_args.policy);
+
+    let mut progress = progress::Progress::start(&PathBuf::from(output_path.clone()), &input_hash, cli_args.resume)?;
+    if progress.resumed() > 0 {
+        term::info(&format!("resuming, {} companies were already rendered", progress.resumed()));
+    }
+
+    term::phase("rendering", Some(companies.len()));
+    for (i, company) in companies.iter().enumerate() {
+        term::advance(&company.name);
+        let file_path = companies_dir.join(company.note.clone() + ".md");
+        let step = format!("company:{}", company.name);
+
+        // the render that died never saved its manifest, so what it recorded for the notes it got through is
+        // taken from the progress, a note it skipped keeps its old entry rather than what's on disk now,
+        // which would make the user's edit look like ours
+        if progress.is_done(&step) {
+            if let Some((path, hash)) = progress.recorded(&step) {
+                manifest.files.insert(path.to_string(), hash.to_string());
+            }
+            term::trace(&format!("already rendered {}", file_path.to_string_lossy()));
+            continue;
+        }
+
+        let generated = note_frontmatter(company, cli_args);
+        provenance::trace_frontmatter(company, &generated, &cli_args.field_formats, &cli_args.key_prefix);
+        if !cli_args.download_logos && attachments::expires(&company.logo_url) {
+            term::info(&format!("the logo for {} is a signed url that expires, saving it into the vault instead of linking it", company.name));
+        }
+        let logo_file = match cli_args.download_logos || attachments::keeps_locally(company) {
+            true => attachments::download_logo(&PathBuf::from(output_path.clone()), company, &cli_args.folders, &mut downloader)?,
+            false => None,
+        };
+        let sections = note_sections(company, logo_file.as_deref(), &labels, cli_args, stamp.as_deref());
+        let section_names: Vec<&str> = sections.iter().map(|(name, _)| *name).collect();
+
+        if cli_args.update && let Some(file_path) = existing_note(&companies_dir, i, company) {
+            let existing = fs::read_to_string(&file_path)?;
+            let with_sections = add_new_sections(company, &existing, cli_args, &labels);
+            let relative = vault_relative(&output_path, &file_path);
+
+            // text typed into a generated section would be lost on the swap, so the whole note waits
+            // until the user moves it out or deletes the section
+            if manifest.edited(&relative, &update::section_contents(&existing, &section_names)) {
+                term::status(Status::Skipped, &format!("{}, generated sections were edited", file_path.to_string_lossy()));
+                report.skip(&relative, "generated sections were edited since the last render");
+                progress.mark_done(&step, None)?;
+                continue;
+            }
+
+            let updated = update::update_frontmatter(&with_sections, &with_file_class(&class_name, &generated))
+                .map(|text| update::update_sections(&text, &sections))
+                .map(|text| hooks::apply_note(&cli_args.hooks, &relative, text));
+            match updated {
+                Some(updated) if updated != existing => {
+                    write_file(&file_path, &updated)?;
+                    manifest.record(&relative, &update::section_contents(&updated, &section_names));
+                    report.wrote(&relative);
+                    updated_count += 1;
+                    term::status(Status::Updated, &file_path.to_string_lossy());
+                },
+                Some(_) => {
+                    manifest.record(&relative, &update::section_contents(&existing, &section_names));
+                    term::trace(&format!("unchanged {}", file_path.to_string_lossy()));
+                },
+                None => {
+                    term::status(Status::Skipped, &format!("{}, couldn't find its frontmatter", file_path.to_string_lossy()));
+                    report.skip(&relative, "couldn't find its frontmatter");
+                },
+            }
+            progress.mark_done(&step, manifest.files.get(&relative).map(|hash| (relative.as_str(), hash.as_str())))?;
+            continue;
+        }
+
+        let file_text = company_note(&class_name, &user_fields, &generated, &sections, note_stub.as_deref());
+        let mut file_text = hooks::apply_note(&cli_args.hooks, &vault_relative(&output_path, &file_path), file_text);
+
+        new_count += 1;
+
+        let written_sections = update::section_contents(&file_text, &section_names);
+        let written_path = match fs::write(&file_path, &file_text) {
+            Ok(()) => {
+                manifest.record(&vault_relative(&output_path, &file_path), &written_sections);
+                report.wrote(&vault_relative(&output_path, &file_path));
+                term::status(Status::Written, &file_path.to_string_lossy());
+                vault_relative(&output_path, &file_path)
+            },
+            Err(e) => {
+                let alt_path = companies_dir.join(fallback_note_name(i, company));
+                cli_args.policy.fail(&format!("{}: {}, instead writing: {}", file_path.to_string_lossy(), e, alt_path.to_string_lossy()))?;
+                report.fail(&vault_relative(&output_path, &file_path), &e.to_string(), Some(&vault_relative(&output_path, &alt_path)));
+                file_text.push_str("==This file failed to write, likely because of an issue with the name. If everything else looks fine then you can set the name yourself==\n\n");
+                file_text.push_str(&format!("**Company name:** {}\n", company.name));
+                file_text.push_str(&format!("**Write error:** {}\n", e));
+                if let Err(e) = fs::write(&alt_path, &file_text) {
+                    return Err(Error::Output { path: alt_path.to_string_lossy().to_string(), source: e });
+                }
+                manifest.record(&vault_relative(&output_path, &alt_path), &written_sections);
+                report.wrote(&vault_relative(&output_path, &alt_path));
+                vault_relative(&output_path, &alt_path)
+            },
+        };
+        progress.mark_done(&step, manifest.files.get(&written_path).map(|hash| (written_path.as_str(), hash.as_str())))?;
+    }
+
+    term::debug(&format!("wrote {} new company notes, updated {}", new_count, updated_count));
+    term::phase("writing notes", None);
+
+    let overflow_notes = oversize::overflow_notes(&companies, &cli_args.folders);
+    if !overflow_notes.is_empty() {
+        let descriptions_dir = PathBuf::from(output_path.clone()).join(&cli_args.folders.attachments).join("descriptions");
+        fs::create_dir_all(&descriptions_dir).map_err(|source| Error::Output { path: descriptions_dir.to_string_lossy().to_string(), source })?;
+    }
+    for (name, text) in &overflow_notes {
+        write_whole_note(&output_path, name, &build_info::stamp_text(text.clone(), stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    // logos from companies that left or changed their logo would otherwise pile up every render, the
+    // logos folder is only this run's to clean up when it's downloading them
+    if cli_args.download_logos {
+        let pruned = attachments::prune_logos(&PathBuf::from(output_path.clone()), cli_args.prune_mode, &cli_args.folders)?;
+        if pruned > 0 {
+            term::debug(&format!("pruned {} unused logos", pruned));
+        }
+    }
+
+    if let Some(changes) = roster_changes.filter(|c| !c.is_empty()) {
+        for line in changes.summary().lines() {
+            term::debug(line);
+        }
+        history::append_history(&PathBuf::from(output_path.clone()).join(naming.index_file("Roster History")), &changes, &labels)?;
+        if let Some(url) = &cli_args.webhook_url {
+            // a flaky webhook shouldn't stop the vault from being updated, unless --strict
+            if let Err(e) = notify::send_webhook(url, &changes, &cli_args.limits) {
+                cli_args.policy.fail(&e.to_string())?;
+            }
+        }
+    }
+
+    // a mini vault is just the companies, the schedule and the checklist to carry around the fair
+    let extras = !cli_args.mini;
+
+    if extras && let Some(deadlines_text) = deadlines::render_deadlines(&companies, cli_args.tasks_format, &labels) {
+        write_whole_note(&output_path, &naming.index_file("Deadlines"), &build_info::stamp_text(deadlines_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    if let Some(schedule_text) = schedule::render_schedule(&companies, &labels) {
+        write_whole_note(&output_path, &naming.index_file("Schedule"), &build_info::stamp_text(schedule_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    // the route only covers companies the user gave a priority so it fills in on later renders
+    let checklist_path = PathBuf::from(output_path.clone()).join(naming.index_file("Checklist"));
+    let existing_checklist = fs::read_to_string(&checklist_path).unwrap_or_default();
+    if let Some(checklist_text) = route::render_checklist(&companies_dir, &companies, &existing_checklist, &labels, cli_args.mini) {
+        write_file(checklist_path, checklist_text)?;
+    }
+
+    if extras && let Some(team_text) = team::render_team(&companies, &cli_args.team, &labels) {
+        write_whole_note(&output_path, &naming.index_file("Team"), &build_info::stamp_text(team_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    // read back from the notes so members' claims merged in from their own copies show up here
+    if extras && let Some(board_text) = claims::render_board(&companies_dir, &companies, cli_args.claims, &labels) {
+        write_whole_note(&output_path, &naming.index_file("Board"), &build_info::stamp_text(board_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    if extras && let Some(starred) = &cli_args.starred {
+        let starred_text = starred.render_starred(&companies, &labels);
+        write_whole_note(&output_path, &naming.index_file("Starred"), &build_info::stamp_text(starred_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    // after the company notes so views on fields like Priority see what's in them
+    for view in cli_args.views.iter().filter(|_| extras) {
+        let view_text = view.render(&companies, Some(&companies_dir), &labels);
+        write_whole_note(&output_path, &naming.index_file(&view.name), &build_info::stamp_text(view_text, stamp.as_deref()), &mut manifest, report, &cli_args.hooks)?;
+    }
+
+    // info sessions and networking events some payloads carry alongside the fair itself
+    let events = events::parse_events(&input_data, &companies);
+    if extras && !events.is_empty() {
+        let written = events::write_events(&PathBuf::from(output_path.clone()).join(&cli_args.folders.events), &events, cli_args.policy)?;
+        term::debug(&format!("wrote {} new event notes", written));
+    }
+
+    if extras && let Some(jobs) = &jobs {
+        let written = jobs::write_jobs(&PathBuf::from(output_path.clone()).join(&cli_args.folders.jobs), jobs, cli_args.policy)?;
+        term::debug(&format!("wrote {} new job notes", written));
+    }
+
+    if extras && cli_args.prep {
+        let targets = prep::priority_companies(&companies_dir, &companies);
+        if targets.is_empty() {
+            term::info("no companies have a Priority yet, set one on the companies to prepare for and rerun fair_renderer update --prep");
+        }
+        let written = prep::write_prep_notes(&PathBuf::from(output_path.clone()).join(&cli_args.folders.prep), &targets, &cli_args.my_majors, cli_args.policy)?;
+        term::debug(&format!("wrote {} new prep notes", written));
+    }
+
+    translation_cache.save()?;
+    if cli_args.summarize_cmd.is_some() {
+        summary_cache.save()?;
+    }
+
+    if extras && let Some(template) = &followup_template {
+        let followups_dir = PathBuf::from(output_path.clone()).join(&cli_args.folders.follow_ups);
+        fs::create_dir_all(&followups_dir).map_err(|source| Error::Output { path: followups_dir.to_string_lossy().to_string(), source })?;
+
+        for company in &companies {
+            let file_path = followups_dir.join(format!("{} - Follow-up.md", company.note));
+            // these get edited into real emails so never replace one that's already there
+            if file_path.exists() {
+                continue;
+            }
+            match fs::write(&file_path, followup::render_followup(template, company, &labels)) {
+                Ok(()) => report.wrote(&vault_relative(&output_path, &file_path)),
+                Err(e) => {
+                    cli_args.policy.fail(&format!("follow-up {}: {}", file_path.to_string_lossy(), e))?;
+                    report.fail(&vault_relative(&output_path, &file_path), &e.to_string(), None);
+                },
+            }
+        }
+    }
+
+    manifest.input_hash = input_hash;
+    manifest.config = config;
+    manifest.save(&PathBuf::from(output_path.clone()))?;
+
+    // the vault itself is done, an upload that fails is simply retried as a whole
+    progress.finish()?;
+    drop(lock);
+
+    if let Some(target) = &cli_args.upload_target {
+        term::phase("uploading", None);
+        let uploaded = upload::upload_vault(&PathBuf::from(output_path.clone()), target, cli_args.limits.timeout)?;
+        term::debug(&format!("uploaded {} files", uploaded));
+    }
+
+    report.print();
+    Ok(())
+}
+
+// the template keeps its classes in classes/, metadata menu has to be told where they went
+fn move_classes(vault_path: &Path, classes_folder: &str) -> Result<(), Error> {
+    let new_dir = vault_path.join(classes_folder);
+    let failed = |source| Error::Output { path: new_dir.to_string_lossy().to_string(), source };
+    if let Some(parent) = new_dir.parent() {
+        fs::create_dir_all(parent).map_err(failed)?;
+    }
+    fs::rename(vault_path.join("classes"), &new_dir).map_err(failed)?;
+
+    let settings_path = vault_path.join(".obsidian/plugins/metadata-menu/data.json");
+    let Ok(bytes) = fs::read(&settings_path) else { return Ok(()) };
+    let mut settings: serde_json::Value = match serde_json::from_slice(&bytes) {
+        Ok(settings) => settings,
+        Err(source) => return Err(Error::Json { what: settings_path.to_string_lossy().to_string(), source }),
+    };
+    settings["classFilesPath"] = serde_json::Value::String(format!("{}/", classes_folder));
+    write_file(&settings_path, serde_json::to_string_pretty(&settings).unwrap_or_default())?;
+    Ok(())
+}
+
+// the options that change what a render writes, kept in the manifest so a vault can tell how it was
+// made, tokens and urls that could carry credentials are left out
+fn render_config(cli_args: &CliArgs, class_name: &str) -> serde_json::Value {
+    let field_formats: serde_json::Map<String, serde_json::Value> = cli_args.field_formats.0.iter()
+        .map(|(field, format)| (field.clone(), format.name().into()))
+        .collect();
+    let folders = &cli_args.folders;
+    serde_json::json!({
+        "format": cli_args.format,
+        "layout": cli_args.layout.map(inputs::Layout::name),
+        "encoding": cli_args.encoding.map(encoding::Encoding::name),
+        "jobs": cli_args.jobs_path,
+        "template": cli_args.template_path,
+        "followup_template": cli_args.followup_template_path,
+        "note_stub": cli_args.note_stub_path,
+        "lang": cli_args.lang,
+        "translate_to": cli_args.translate_cmd.as_ref().map(|_| &cli_args.translate_to),
+        "summary_sentences": cli_args.summarize.then_some(cli_args.summary_sentences),
+        "max_field_size": cli_args.max_field_size,
+        "oversized": cli_args.oversized.name(),
+        "field_formats": field_formats,
+        "select_fields": cli_args.select_fields,
+        "key_prefix": cli_args.key_prefix,
+        "class_name": class_name,
+        "note_name": cli_args.note_name,
+        "folders": {
+            "companies": folders.companies,
+            "classes": folders.classes,
+            "attachments": folders.attachments,
+            "events": folders.events,
+            "jobs": folders.jobs,
+            "prep": folders.prep,
+            "follow_ups": folders.follow_ups,
+            "comparisons": folders.comparisons,
+        },
+        "tasks_format": cli_args.tasks_format,
+        "download_logos": cli_args.download_logos,
+        "prep": cli_args.prep,
+        "my_majors": cli_args.my_majors,
+        "team": cli_args.team.iter().map(|m| (m.name.clone(), m.majors.clone())).collect::<BTreeMap<String, Vec<String>>>(),
+        "claims": cli_args.claims,
+        "blocklist": cli_args.blocklist.as_ref().map(|b| &b.path),
+        "blocklist_mode": cli_args.blocklist.as_ref().map(|b| b.mode.name()),
+        "industry_map": cli_args.industries.path,
+        "starred": cli_args.starred.as_ref().map(|s| &s.path),
+        "session": cli_args.session,
+        "mini": cli_args.mini,
+        "build_info": cli_args.build_info,
+    })
+}
+
+// manifest and report paths are relative to the vault so they survive moving it
+fn vault_relative(vault_path: &str, file_path: &Path) -> String {
+    file_path.strip_prefix(vault_path).unwrap_or(file_path).to_string_lossy().to_string()
+}
+
+// fs::write with the path in the error
+fn write_file(path: impl AsRef<Path>, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    let path = path.as_ref();
+    fs::write(path, contents).map_err(|source| Error::Output { path: path.to_string_lossy().to_string(), source })
+}
+
+// notes generated as a whole have nothing to merge, one the user edited is left alone instead
+fn write_whole_note(vault_path: &str, name: &str, text: &str, manifest: &mut manifest::Manifest, report: &mut report::RenderReport, hooks: &hooks::Hooks) -> Result<(), Error> {
+    let file_path = PathBuf::from(vault_path).join(name);
+    let text = &hooks::apply_note(hooks, name, text.to_string());
+    if let Ok(existing) = fs::read_to_string(&file_path) && manifest.edited(name, &existing) {
+        term::status(Status::Skipped, &format!("{}, edited since the last render", file_path.to_string_lossy()));
+        report.skip(name, "edited since the last render");
+        return Ok(());
+    }
+    write_file(&file_path, text)?;
+    manifest.record(name, text);
+    report.wrote(name);
+    Ok(())
+}
+
+// renders the notes a fresh vault would get without touching the output directory or the network,
+// keyed by their path inside the vault, so the generated content can be checked by snapshot tests
+// uses the default labels and field formats and leaves logos as remote images
+pub fn render_to_memory(input_data: &[u8], template_path: &Path) -> Result<BTreeMap<String, String>, Error> {
+    render_to_memory_with(input_data, template_path, &Vec::new())
+}
+
+fn render_to_memory_with(input_data: &[u8], template_path: &Path, hooks: &hooks::Hooks) -> Result<BTreeMap<String, String>, Error> {
+    let file_class_bytes = match fs::read(template_path.join("classes/company.md")) {
+        Ok(bytes) => bytes,
+        Err(e) => return Err(Error::Template(format!("could not read template path: {}", e))),
+    };
+    let note_stub = fs::read_to_string(template_path.join("stubs/company.md")).ok();
+    render_notes(input_data, &file_class_bytes, note_stub.as_deref(), hooks)
+}
+
+// the part of render_to_memory that never touches the filesystem, the template's files are passed in
+// so it also runs in a browser
+fn render_notes(input_data: &[u8], file_class_bytes: &[u8], note_stub: Option<&str>, hooks: &hooks::Hooks) -> Result<BTreeMap<String, String>, Error> {
+    let mut companies = parse_input(input_data)?;
+    // detection only, there is no translator so nothing leaves the machine
+    let mut translation_cache = translate::TranslationCache::open(None);
+    translate::translate_companies(&mut companies, &translate::NoopTranslator, "en", &mut translation_cache, policy::Policy::Strict)?;
+
+    let (user_fields, new_fileclass) = parse_fileclass(file_class_bytes)?;
+    let labels = labels::Labels::default();
+    let formats = fields::FieldFormats::default();
+
+    let folders = folders::Folders::default();
+    hooks::apply_companies(hooks, &mut companies);
+    oversize::guard_fields(&mut companies, oversize::DEFAULT_MAX_FIELD_SIZE, oversize::Oversized::Move, &folders, &labels);
+
+    let mut files = BTreeMap::new();
+    files.insert("classes/company.md".to_string(), new_fileclass);
+    for (name, text) in oversize::overflow_notes(&companies, &folders) {
+        files.insert(name, text);
+    }
+
+    for company in &companies {
+        let generated = generated_frontmatter(company, &formats, "");
+        let sections = generated_sections(company, None, &labels);
+        files.insert(format!("companies/{}.md", company.note), company_note("company", &user_fields, &generated, &sections, note_stub));
+    }
+    if let Some(deadlines_text) = deadlines::render_deadlines(&companies, false, &labels) {
+        files.insert("Deadlines.md".to_string(), deadlines_text);
+    }
+    if let Some(schedule_text) = schedule::render_schedule(&companies, &labels) {
+        files.insert("Schedule.md".to_string(), schedule_text);
+    }
+
+    // the fileClass isn't a note
+    for (name, text) in files.iter_mut().filter(|(name, _)| !name.starts_with("classes/")) {
+        *text = hooks::apply_note(hooks, name, std::mem::take(text));
+    }
+    Ok(files)
+}
+
+// a brand new company note, the user's own fields from the fileClass come first and are left empty
+fn company_note(class_name: &str, user_fields: &[String], generated: &[(String, String)], sections: &[(&str, String)], note_stub: Option<&str>) -> String {
+    let mut file_text = format!("---\nfileClass: {}\n", class_name);
+
+    for field in user_fields {
+        file_text.push_str(field);
+        file_text.push_str(": \n");
+    }
+
+    for (key, value) in generated {
+        file_text.push_str(&format!("{}: {}\n", key, value));
+    }
+
+    // end frontmatter
+    file_text.push_str("---\n\n");
+
+    for (i, (name, content)) in sections.iter().enumerate() {
+        if i > 0 {
+            file_text.push('\n');
+        }
+        file_text.push_str(&update::marked_section(name, content));
+    }
+
+    if let Some(stub) = note_stub {
+        file_text.push('\n');
+        file_text.push_str(stub);
+        if !stub.ends_with('\n') {
+            file_text.push('\n');
+        }
+    }
+
+    file_text
+}
+
+// a company's note, or the error<i>.md it fell back to when its name couldn't be a file name
+// the slug keeps the fallback recognizable in the file list, the index keeps it unique
+fn fallback_note_name(index: usize, company: &CompanyEntry) -> String {
+    format!("error{}-{}.md", index, links::slug(&company.name))
+}
+
+fn existing_note(companies_dir: &Path, index: usize, company: &CompanyEntry) -> Option<PathBuf> {
+    let file_path = companies_dir.join(company.note.clone() + ".md");
+    if file_path.exists() {
+        return Some(file_path);
+    }
+    // vaults from before the slug was added to fallbacks still have the plain error{index}.md
+    [fallback_note_name(index, company), format!("error{index}.md")].into_iter()
+        .map(|name| companies_dir.join(name))
+        .find(|alt_path| {
+            fs::read_to_string(alt_path).is_ok_and(|text| text.contains(&format!("**Company name:** {}\n", company.name)))
+        })
+}
+
+// every note an --update would write or change, without touching the vault or the network
+fn check_vault(vault_path: &Path, companies: &[CompanyEntry], cli_args: &CliArgs, class_name: &str, labels: &labels::Labels, naming: &naming::Naming, stamp: Option<&str>) -> Vec<String> {
+    let companies_dir = vault_path.join(&cli_args.folders.companies);
+    let mut drift = Vec::new();
+
+    for (i, company) in companies.iter().enumerate() {
+        let Some(file_path) = existing_note(&companies_dir, i, company) else {
+            drift.push(format!("missing companies/{}.md", company.note));
+            continue;
+        };
+        let logo_file = match cli_args.download_logos || attachments::keeps_locally(company) {
+            true => attachments::existing_logo(vault_path, company, &cli_args.folders),
+            false => None,
+        };
+        let generated = note_frontmatter(company, cli_args);
+        let sections = note_sections(company, logo_file.as_deref(), labels, cli_args, stamp);
+
+        let existing = fs::read_to_string(&file_path).unwrap_or_default();
+        let with_sections = add_new_sections(company, &existing, cli_args, labels);
+        let relative = vault_relative(&vault_path.to_string_lossy(), &file_path);
+        let updated = update::update_frontmatter(&with_sections, &with_file_class(class_name, &generated))
+            .map(|text| update::update_sections(&text, &sections))
+            .map(|text| hooks::apply_note(&cli_args.hooks, &relative, text));
+        if updated.is_none_or(|u| u != existing) {
+            drift.push(format!("changed {}", file_path.strip_prefix(vault_path).unwrap_or(&file_path).to_string_lossy()));
+        }
+    }
+
+    let mut whole_notes = vec![
+        (naming.index_file("Deadlines"), deadlines::render_deadlines(companies, cli_args.tasks_format, labels)),
+        (naming.index_file("Schedule"), schedule::render_schedule(companies, labels)),
+        (naming.index_file("Checklist"), route::render_checklist(
+            &companies_dir,
+            companies,
+            &fs::read_to_string(vault_path.join(naming.index_file("Checklist"))).unwrap_or_default(),
+            labels,
+            cli_args.mini,
+        )),
+        (naming.index_file("Team"), team::render_team(companies, &cli_args.team, labels)),
+        (naming.index_file("Board"), claims::render_board(&companies_dir, companies, cli_args.claims, labels)),
+        (naming.index_file("Starred"), cli_args.starred.as_ref().map(|s| s.render_starred(companies, labels))),
+    ];
+    for (name, text) in oversize::overflow_notes(companies, &cli_args.folders) {
+        whole_notes.push((name, Some(text)));
+    }
+    for view in &cli_args.views {
+        whole_notes.push((naming.index_file(&view.name), Some(view.render(companies, Some(&companies_dir), labels))));
+    }
+    for (name, expected) in whole_notes {
+        let Some(expected) = expected else { continue };
+        if cli_args.mini && name != naming.index_file("Schedule") && name != naming.index_file("Checklist") {
+            continue;
+        }
+        // the checklist is merged with the ticks already in it, so it's the one note without a stamp
+        let expected = match name == naming.index_file("Checklist") {
+            true => expected,
+            false => hooks::apply_note(&cli_args.hooks, &name, build_info::stamp_text(expected, stamp)),
+        };
+        match fs::read_to_string(vault_path.join(&name)) {
+            Ok(existing) if existing == expected => {},
+            Ok(_) => drift.push(format!("changed {}", name)),
+            Err(_) => drift.push(format!("missing {}", name)),
+        }
+    }
+
+    drift
+}
+
+// the configured class name, with --class-per-fair the fair's id is added so every fair gets
+// its own class and views in one vault
+fn file_class_name(cli_args: &CliArgs, input_data: &[u8]) -> Result<String, Error> {
+    if !cli_args.class_per_fair {
+        return Ok(cli_args.class_name.clone());
+    }
+    let json_data: serde_json::Value = serde_json::from_slice(input_data).unwrap_or_default();
+    let fair_id = json_data["results"].as_array()
+        .and_then(|results| results.iter().find_map(|r| r["career_fair_id"].as_u64()));
+    match fair_id {
+        Some(id) => Ok(format!("{}_{}", cli_args.class_name, id)),
+        None => Err(Error::Usage("--class-per-fair needs a career_fair_id in the input, job search results don't have one".to_string())),
+    }
+}
+
+// existing notes get moved to the configured class when it changes
+fn with_file_class(class_name: &str, generated: &[(String, String)]) -> Vec<(String, String)> {
+    let mut keys = vec![("fileClass".to_string(), class_name.to_string())];
+    keys.extend(generated.iter().cloned());
+    keys
+}
+
+// the frontmatter keys the renderer owns, in the order they are written
+fn generated_frontmatter(company: &CompanyEntry, formats: &fields::FieldFormats, key_prefix: &str) -> Vec<(String, String)> {
+    use fields::RawValue::{Many, One};
+
+    // links point at the session's note, which only differs from its name with --note-name
+    let session_names: Vec<String> = company.attending_sessions.iter()
+        .map(|s| match formats.get("sessions") {
+            fields::FieldFormat::Link => s.note.clone(),
+            _ => s.display_name.clone(),
+        })
+        .collect();
+
+    let mut raw = vec![
+        ("location", One(company.location.clone())),
+        ("majors", Many(company.majors.clone())),
+        ("job_titles", One(company.job_titles.clone())),
+        ("job_types", Many(company.job_types.clone())),
+        ("school_years", Many(company.school_years.clone())),
+        ("international", One(company.work_authorization.clone())),
+        ("sessions", Many(session_names)),
+        ("website", One(company.website.clone())),
+    ];
+    // stays the same when a company changes its name so the note can follow it
+    if let Some(id) = company.employer_id {
+        raw.push(("employer_id", One(id.to_string())));
+    }
+    if let Some(deadline) = &company.deadline {
+        raw.push(("deadline", One(deadline.clone())));
+    }
+    if let Some(lang) = &company.description_language {
+        raw.push(("language", One(lang.clone())));
+    }
+    if let Some(industry) = &company.industry {
+        raw.push(("industry", One(industry.clone())));
+    }
+    if !company.contacts.is_empty() {
+        raw.push(("contacts", Many(contacts::names(&company.contacts))));
+    }
+    if let Some(summary) = &company.summary {
+        raw.push(("summary", One(summary.clone())));
+    }
+    if !company.links.is_empty() {
+        raw.push(("links", Many(company.links.iter().map(|(_, url)| url.clone()).collect())));
+    }
+
+    // formats are picked by the plain key, the prefix only changes what ends up in the note
+    fields::format_frontmatter(raw, formats).into_iter()
+        .map(|(key, value)| (format!("{}{}", key_prefix, key), value))
+        .collect()
+}
+
+// the frontmatter and sections a render writes into a company's note, with what the options add to
+// them, shared by the render, a resumed render and --check so they agree on what the renderer owns
+fn note_frontmatter(company: &CompanyEntry, cli_args: &CliArgs) -> Vec<(String, String)> {
+    let mut generated = generated_frontmatter(company, &cli_args.field_formats, &cli_args.key_prefix);
+    if let Some(blocklist) = &cli_args.blocklist {
+        blocklist.flag_frontmatter(company, &mut generated, &cli_args.key_prefix);
+    }
+    if let Some(starred) = &cli_args.starred {
+        starred.star_frontmatter(company, &mut generated, &cli_args.key_prefix);
+    }
+    generated
+}
+
+fn note_sections(company: &CompanyEntry, logo_file: Option<&str>, labels: &labels::Labels, cli_args: &CliArgs, stamp: Option<&str>) -> Vec<(&'static str, String)> {
+    let mut sections = generated_sections(company, logo_file, labels);
+    if let Some(blocklist) = &cli_args.blocklist {
+        blocklist.banner(company, &mut sections, labels);
+    }
+    build_info::with_stamp(sections, stamp)
+}
+
+// room in a note written before these sections existed, open positions and contacts only when the
+// user hasn't already got the heading there, which would mean they took the section over
+fn add_new_sections(company: &CompanyEntry, existing: &str, cli_args: &CliArgs, labels: &labels::Labels) -> String {
+    let mut text = match &cli_args.blocklist {
+        Some(blocklist) => blocklist.add_banner_section(company, existing),
+        None => existing.to_string(),
+    };
+    if !company.positions.is_empty() && !text.contains(&format!("### {}\n", labels.open_positions)) {
+        text = update::insert_section(&text, "positions", Some("logo"));
+    }
+    if !company.contacts.is_empty() && !text.contains(&format!("### {}\n", labels.contacts)) {
+        text = update::insert_section(&text, "contacts", Some("description"));
+    }
+    text
+}
+
+// the parts of the note body the renderer owns, each one ends up between marker comments
+fn generated_sections(company: &CompanyEntry, logo_file: Option<&str>, labels: &labels::Labels) -> Vec<(&'static str, String)> {
+    let logo = match logo_file {
+        Some(file) => format!("![[{}|80]]\n", file),
+        None => format!("<img src=\"{}\" style=\"width: 80px;\">\n", company.logo_url.replace('"', "%22")),
+    };
+
+    let description = match (&company.translated_description, &company.description_language) {
+        (Some(translated), Some(lang)) => {
+            let mut text = format!("### {}\n\n{}\n\n", labels.description, translated);
+            // the original stays in the note, folded away in a callout
+            text.push_str(&format!("> [!quote]- {} ({})\n", labels.original_description, lang));
+            for line in company.description.lines() {
+                text.push_str(&format!("> {}\n", line));
+            }
+            text
+        },
+        _ => format!("### {}\n\n{}\n", labels.description, company.description),
+    };
+
+    let mut sections = vec![("logo", logo)];
+    if !company.positions.is_empty() {
+        let mut positions = format!("### {}\n\n", labels.open_positions);
+        for position in &company.positions {
+            let title = match &position.url {
+                Some(url) => format!("[{}]({})", position.title.replace(['[', ']'], ""), url),
+                None => position.title.clone(),
+            };
+            match position.location.is_empty() {
+                true => positions.push_str(&format!("- {}\n", title)),
+                false => positions.push_str(&format!("- {}, {}\n", title, position.location)),
+            }
+        }
+        sections.push(("positions", positions));
+    }
+    sections.push(("description", description));
+    if !company.contacts.is_empty() {
+        let mut contacts = format!("### {}\n\n", labels.contacts);
+        for contact in &company.contacts {
+            contacts.push_str(&contacts::contact_line(contact, true));
+        }
+        sections.push(("contacts", contacts));
+    }
+    if !company.links.is_empty() {
+        let mut links = format!("### {}\n\n", labels.links);
+        for (label, url) in &company.links {
+            links.push_str(&format!("- [{}]({})\n", label, url));
+        }
+        sections.push(("links", links));
+    }
+    sections
+}
+
+// returns the names of the user's own fields and the fileClass with the generated fields added,
+// a template that isn't a metadata menu fileClass is an error rather than a panic
+pub fn parse_fileclass(file_class_bytes: &[u8]) -> Result<(Vec<String>, String), Error> {
+    parse_fileclass_with_prefix(file_class_bytes, "", &[], &[]).map(|(fields, text, _)| (fields, text))
+}
+
+// also returns how many of the renderer's fields had to be added or given new options, a fileClass
+// that already has all of them doesn't need writing again, user_additions are fields the user owns
+// but a mode like --claims needs there, they're added when missing and then left alone like any
+// other user field
+fn parse_fileclass_with_prefix(file_class_bytes: &[u8], key_prefix: &str, user_additions: &[&str], choices: &[fields::Choice]) -> Result<(Vec<String>, String, usize), Error> {
+    match read_fileclass_yaml(file_class_bytes, key_prefix, user_additions, choices) {
+        Some(parsed) => Ok(parsed),
+        None => Err(Error::Template("failed reading fileClass".to_string())),
+    }
+}
+
+// metadata menu's list of values for a Select or Multi field, keyed "1", "2" and on
+fn values_list(values: &[String]) -> Hash {
+    let mut list = Hash::new();
+    for (i, value) in values.iter().enumerate() {
+        list.insert(Yaml::String((i + 1).to_string()), Yaml::String(value.clone()));
+    }
+    let mut options = Hash::new();
+    options.insert(Yaml::String("sourceType".to_string()), Yaml::String("ValuesList".to_string()));
+    options.insert(Yaml::String("valuesList".to_string()), Yaml::Hash(list));
+    options
+}
+
+// values the data has that a Select or Multi field doesn't offer yet are added after the ones it has,
+// so options the user put in by hand stay, returns whether it added any
+fn merge_options(field: &mut Hash, values: &[String]) -> bool {
+    let field_type = field.get(&Yaml::from_str("type")).and_then(Yaml::as_str).unwrap_or_default();
+    if !["Select", "Multi", "Cycle"].contains(&field_type) || values.is_empty() {
+        return false;
+    }
+    // get_mut rather than entry, which would move the key to the end of the field
+    let options_key = Yaml::String("options".to_string());
+    if !field.contains_key(&options_key) {
+        field.insert(options_key.clone(), Yaml::Hash(Hash::new()));
+    }
+    let Some(options) = field.get_mut(&options_key).and_then(Yaml::as_mut_hash) else { return false };
+    // options from a note or a dataview query aren't ours to fill in
+    let source = options.get(&Yaml::from_str("sourceType")).and_then(Yaml::as_str).unwrap_or("ValuesList");
+    if source != "ValuesList" {
+        return false;
+    }
+    if options.is_empty() {
+        *options = values_list(values);
+        return true;
+    }
+    let list_key = Yaml::String("valuesList".to_string());
+    if !options.contains_key(&list_key) {
+        options.insert(list_key.clone(), Yaml::Hash(Hash::new()));
+    }
+    let Some(list) = options.get_mut(&list_key).and_then(Yaml::as_mut_hash) else { return false };
+    let mut next = list.keys().filter_map(|k| k.as_str().and_then(|k| k.parse::<usize>().ok())).max().unwrap_or(0) + 1;
+    let mut added = false;
+    for value in values {
+        if list.values().any(|v| v.as_str().is_some_and(|v| v.eq_ignore_ascii_case(value))) {
+            continue;
+        }
+        list.insert(Yaml::String(next.to_string()), Yaml::String(value.clone()));
+        next += 1;
+        added = true;
+    }
+    added
+}
+
+// fields that are already there, from the template or from a fileClass the vault had before, keep
+// their id, type and position, only the missing ones get appended, Select and Multi ones get the
+// values the companies have as options
+fn read_fileclass_yaml(file_class_bytes: &[u8], key_prefix: &str, user_additions: &[&str], choices: &[fields::Choice]) -> Option<(Vec<String>, String, usize)> {
+    let file_class_str = std::str::from_utf8(clean_yaml_md_file(file_class_bytes)).ok()?;
+    let mut file_class_yaml = yaml_rust2::YamlLoader::load_from_str(file_class_str).ok()?;
+    let file_class = file_class_yaml.first_mut()?.as_mut_hash()?;
+
+    let field_strings = [
+        ("location", "Input"), ("majors", "Input"), ("job_titles", "Input"), ("job_types", "Input"),
+        ("school_years", "Input"), ("international", "Input"), ("sessions", "Input"), ("website", "Input"),
+        ("employer_id", "Number"), ("deadline", "Date"), ("language", "Input"), ("links", "Input"),
+        ("industry", "Input"), ("contacts", "Input"), ("summary", "Input"),
+    ];
+    let generated_names: Vec<String> = field_strings.iter().map(|(name, _)| format!("{}{}", key_prefix, name)).collect();
+
+    let fields = file_class.get_mut(&Yaml::String("fields".to_string()))?.as_mut_vec()?;
+
+    let mut existing_names = Vec::with_capacity(fields.len());
+    let mut existing_ids = Vec::with_capacity(fields.len());
+    for field in fields.iter() {
+        let field = field.as_hash()?;
+        existing_names.push(field.get(&Yaml::from_str("name"))?.as_str()?.to_owned());
+        if let Some(id) = field.get(&Yaml::from_str("id")).and_then(Yaml::as_str) {
+            existing_ids.push(id.to_owned());
+        }
+    }
+    let mut field_names: Vec<String> = existing_names.iter().filter(|name| !generated_names.contains(name)).cloned().collect();
+
+    let choice = |name: &str| choices.iter().find(|c| format!("{}{}", key_prefix, c.field) == name);
+    let mut options_changed = 0;
+    for field in fields.iter_mut() {
+        let field = field.as_mut_hash()?;
+        let name = field.get(&Yaml::from_str("name"))?.as_str()?.to_owned();
+        let Some(choice) = choice(&name) else { continue };
+        // a vault rendered before --select-fields has these as the Input we used to add them as
+        if let Some(select_type) = choice.field_type
+            && let Some(field_type) = field.get_mut(&Yaml::from_str("type"))
+            && field_type.as_str() == Some("Input") {
+            *field_type = Yaml::String(select_type.to_string());
+            options_changed += 1;
+        }
+        if merge_options(field, &choice.values) {
+            options_changed += 1;
+        }
+    }
+
+    let mut id = [b'a', b'b', b'c', b'd', b'e', b'f'];
+    let mut added_ids = Vec::new();
+
+    let mut wanted: Vec<(&str, &String)> = field_strings.iter().map(|(_, field_type)| *field_type).zip(&generated_names).collect();
+    let user_additions: Vec<String> = user_additions.iter().map(|name| name.to_string()).collect();
+    for name in &user_additions {
+        if !existing_names.contains(name) {
+            field_names.push(name.clone());
+        }
+        wanted.push(("Input", name));
+    }
+
+    for (field_type, name) in wanted {
+        if existing_names.contains(name) {
+            continue;
+        }
+        // skips ids the fileClass already uses, metadata menu only needs them unique within the file
+        while existing_ids.iter().any(|existing| existing.as_bytes() == id) {
+            next_field_id(&mut id)?;
+        }
+        let id_string = std::str::from_utf8(&id).ok()?.to_string();
+
+        let (field_type, options) = match choice(name).and_then(|c| c.field_type.map(|t| (t, &c.values))) {
+            Some((select_type, values)) => (select_type, values_list(values)),
+            None => (field_type, Hash::new()),
+        };
+        let mut hash = Hash::new();
+        hash.insert(Yaml::String("name".to_string()), Yaml::String(name.clone()));
+        hash.insert(Yaml::String("type".to_string()), Yaml::String(field_type.to_string()));
+        hash.insert(Yaml::String("options".to_string()), Yaml::Hash(options));
+        hash.insert(Yaml::String("path".to_string()), Yaml::String("".to_string()));
+        hash.insert(Yaml::String("id".to_string()), Yaml::String(id_string.clone()));
+        fields.push(Yaml::Hash(hash));
+
+        existing_ids.push(id_string.clone());
+        added_ids.push(id_string);
+        next_field_id(&mut id)?;
+    }
+
+    // separate since fields holds a mutable borrow of the fileClass, older fileClasses have no order at all
+    if let Some(order) = file_class.get_mut(&Yaml::String("fieldsOrder".to_string())).and_then(Yaml::as_mut_vec) {
+        order.extend(added_ids.iter().map(|id| Yaml::String(id.clone())));
+    }
+
+    let mut processed_fileclass = String::new();
+    let mut emitter = YamlEmitter::new(&mut processed_fileclass);
+    emitter.dump(file_class_yaml.first()?).ok()?;
+    processed_fileclass.push_str("\n---"); // misses this for some reason
+
+    Some((field_names, processed_fileclass, added_ids.len() + options_changed))
+}
+
+// metadata menu's ids are six letters and digits, counted up from the first character like an odometer
+// so abcdef is followed by bbcdef, zbcdef by Abcdef and 9bcdef by accdef, None once every id is used
+const FIELD_ID_CHARS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+fn next_field_id(id: &mut [u8; 6]) -> Option<()> {
+    for c in id.iter_mut() {
+        let position = FIELD_ID_CHARS.iter().position(|f| f == c)?;
+        match FIELD_ID_CHARS.get(position + 1) {
+            Some(next) => {
+                *c = *next;
+                return Some(());
+            },
+            None => *c = FIELD_ID_CHARS[0],
+        }
+    }
+    None
+}
+
+// ugly code to strip the --- off the start and end from inline yaml
+fn clean_yaml_md_file(bytes: &[u8]) -> &[u8] {
+    // everything up to the end of the opening --- line, a file without a line break has no yaml
+    let mut bytes = match bytes.iter().position(|b| *b == b'\r' || *b == b'\n') {
+        Some(idx) => &bytes[idx + 1..],
+        None => return &[],
+    };
+
+    if bytes.first() == Some(&b'\n') {
+        bytes = &bytes[1..];
+    }
+
+    while bytes.len() > 1 && bytes[bytes.len() - 1] == b'-' {
+        let n = bytes.len() - 1;
+        bytes = &bytes[..n];
+    }
+
+    bytes
+}
+
+// the note stub, views and translated labels are read from the template on every render, a copy in the
+// vault would only be mistaken for something that changes it
+fn copy_template(template_path: &Path, vault_path: &Path) -> io::Result<()> {
+    fs::create_dir_all(vault_path)?;
+    for entry in fs::read_dir(template_path)? {
+        let entry = entry?;
+        let name = entry.file_name().to_string_lossy().to_string();
+        if name == "stubs" || name == "views.yaml" || (name.starts_with("labels.") && name.ends_with(".yaml")) {
+            continue;
+        }
+        match entry.file_type()?.is_dir() {
+            true => copy_dir_recurse(entry.path(), vault_path.join(&name))?,
+            false => fs::copy(entry.path(), vault_path.join(&name)).map(|_| ())?,
+        }
+    }
+    Ok(())
+}
+
+fn copy_dir_recurse(src: std::path::PathBuf, dst: std::path::PathBuf) -> io::Result<()> {
+    fs::create_dir_all(&dst)?;
+    for entry in fs::read_dir(src)? {
+        let entry = entry?;
+        if entry.file_type()?.is_dir() {
+            copy_dir_recurse(entry.path(), dst.join(entry.file_name()))?;
+        } else {
+            fs::copy(entry.path(), dst.join(entry.file_name()))?;
+        }
+    }
+    Ok(())
+}
+
@@ -0,0 +1,128 @@
+use std::time::Duration;
+
+use crate::Error;
+use crate::policy::Policy;
+use crate::term;
+
+// how much the enrichments (logos, translations, the webhook) may download or wait on during a render,
+// the vault itself never depends on them so hitting a limit skips the enrichment instead of failing
+pub struct Limits {
+    pub max_downloads: Option<usize>,
+    pub max_file_size: u64,
+    pub timeout: Duration,
+}
+
+impl Default for Limits {
+    fn default() -> Self {
+        Limits {
+            max_downloads: None,
+            max_file_size: 16 * 1024 * 1024,
+            timeout: Duration::from_secs(30),
+        }
+    }
+}
+
+impl Limits {
+    #[cfg(feature = "network")]
+    pub fn agent(&self) -> ureq::Agent {
+        ureq::Agent::config_builder()
+            .timeout_global(Some(self.timeout))
+            .build()
+            .into()
+    }
+}
+
+// 500KB, 16MB or a plain number of bytes
+pub fn parse_size(value: &str) -> Result<u64, Error> {
+    let upper = value.trim().to_ascii_uppercase();
+    let (number, multiplier) = match upper.strip_suffix('B').unwrap_or(&upper) {
+        v if v.ends_with('K') => (&v[..v.len() - 1], 1024),
+        v if v.ends_with('M') => (&v[..v.len() - 1], 1024 * 1024),
+        v if v.ends_with('G') => (&v[..v.len() - 1], 1024 * 1024 * 1024),
+        v => (v, 1),
+    };
+    match number.trim().parse::<u64>() {
+        Ok(n) if n > 0 => Ok(n * multiplier),
+        _ => Err(Error::Usage(format!("invalid size: {}, expected something like 500KB or 16MB", value))),
+    }
+}
+
+// the content type and body
+pub type Download = (Option<String>, Vec<u8>);
+
+// shared by every download in a render so the limit counts all of them, and so one timeout
+// or dropped connection skips the rest instead of waiting out the timeout for each of them
+pub struct Downloader<'a> {
+    limits: &'a Limits,
+    pub policy: Policy,
+    #[cfg(feature = "network")]
+    agent: ureq::Agent,
+    count: usize,
+    stopped: bool,
+}
+
+impl<'a> Downloader<'a> {
+    pub fn new(limits: &'a Limits, policy: Policy) -> Downloader<'a> {
+        Downloader {
+            limits,
+            policy,
+            #[cfg(feature = "network")]
+            agent: limits.agent(),
+            count: 0,
+            stopped: false,
+        }
+    }
+
+    // the content type and body, None when it was skipped or failed, which is already printed,
+    // failures only come back as errors with --strict
+    pub fn get(&mut self, url: &str, what: &str) -> Result<Option<Download>, Error> {
+        if self.stopped {
+            term::trace(&format!("skipped {}, downloads were stopped", what));
+            return Ok(None);
+        }
+        if self.limits.max_downloads.is_some_and(|max| self.count >= max) {
+            term::warn(&format!("skipped {} and the rest, reached --max-downloads", what));
+            self.stopped = true;
+            return Ok(None);
+        }
+        self.count += 1;
+        term::step(&format!("downloading {}", what));
+        self.download(url, what)
+    }
+
+    #[cfg(feature = "network")]
+    fn download(&mut self, url: &str, what: &str) -> Result<Option<Download>, Error> {
+        let mut response = match self.agent.get(url).call() {
+            Ok(response) => response,
+            Err(e @ (ureq::Error::Timeout(_) | ureq::Error::HostNotFound | ureq::Error::ConnectionFailed | ureq::Error::Io(_))) => {
+                self.stopped = true;
+                self.policy.fail(&format!("{}: {}, skipping the remaining downloads", what, e))?;
+                return Ok(None);
+            },
+            Err(e) => {
+                self.policy.fail(&format!("{}: {}", what, e))?;
+                return Ok(None);
+            },
+        };
+
+        let content_type = response.headers().get("Content-Type").and_then(|v| v.to_str().ok()).map(str::to_string);
+        match response.body_mut().with_config().limit(self.limits.max_file_size).read_to_vec() {
+            Ok(bytes) => Ok(Some((content_type, bytes))),
+            Err(ureq::Error::BodyExceedsLimit(_)) => {
+                term::warn(&format!("skipped {}, larger than --max-file-size", what));
+                Ok(None)
+            },
+            Err(e) => {
+                self.policy.fail(&format!("{}: {}", what, e))?;
+                Ok(None)
+            },
+        }
+    }
+    // logos are only an enrichment, without a way to download them the render goes on without
+    #[cfg(not(feature = "network"))]
+    fn download(&mut self, _url: &str, what: &str) -> Result<Option<Download>, Error> {
+        self.stopped = true;
+        self.policy.fail(&format!("{}: {}, skipping the remaining downloads", what, crate::missing_feature("downloading", "network")))?;
+        Ok(None)
+    }
+}
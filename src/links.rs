@@ -0,0 +1,119 @@
+use crate::CompanyEntry;
+
+// characters obsidian won't allow in a note name, links containing them could never be
+// clicked through to create the note so they get swapped out and the original kept as the alias
+const BAD_NAME_CHARS: &[char] = &['*', '"', '\\', '/', '<', '>', ':', '|', '?', '#', '^', '[', ']'];
+
+pub fn note_name(target: &str) -> String {
+    target.chars()
+        .map(|c| if BAD_NAME_CHARS.contains(&c) { '-' } else { c })
+        .collect()
+}
+
+// file names are limited to 255 bytes on most filesystems, long names are cut well short of that
+// so suffixes like " - Follow-up.md" and " - Prep.md" still fit
+pub const MAX_NAME_BYTES: usize = 180;
+
+// marks, joiners and modifiers that are drawn as part of the character before them, cutting right
+// before one leaves a bare accent or half an emoji, the indic blocks are covered by where their
+// vowel signs and viramas sit in each block rather than one by one
+fn extends_previous(c: char) -> bool {
+    let c = c as u32;
+    let indic = (0x0900..=0x0DFF).contains(&c) && matches!(c & 0x7F, 0x00..=0x03 | 0x3A..=0x4F | 0x51..=0x57 | 0x62..=0x63);
+    indic || matches!(c,
+        0x0300..=0x036F | 0x0483..=0x0489 | 0x0591..=0x05C7 | 0x0610..=0x061A | 0x064B..=0x065F | 0x0670
+        | 0x06D6..=0x06ED | 0x0E31 | 0x0E34..=0x0E3A | 0x0E47..=0x0E4E | 0x1160..=0x11FF | 0x1AB0..=0x1AFF
+        | 0x1DC0..=0x1DFF | 0x200C..=0x200D | 0x20D0..=0x20FF | 0x3099..=0x309A | 0xFE00..=0xFE0F
+        | 0xFE20..=0xFE2F | 0x1F3FB..=0x1F3FF | 0xE0020..=0xE007F | 0xE0100..=0xE01EF)
+}
+
+fn is_regional_indicator(c: char) -> bool {
+    ('\u{1F1E6}'..='\u{1F1FF}').contains(&c)
+}
+
+// the longest start of text within max_bytes that doesn't split a character or anything drawn as
+// one, like an accented letter, a flag or an emoji joined from several
+pub fn truncate(text: &str, max_bytes: usize) -> &str {
+    if text.len() <= max_bytes {
+        return text;
+    }
+    let mut end = max_bytes;
+    while !text.is_char_boundary(end) {
+        end -= 1;
+    }
+    while end > 0 {
+        let before = text[..end].chars().next_back();
+        let next = text[end..].chars().next();
+        // flags are pairs of regional indicators, an odd run before the cut means it's mid flag
+        let mid_flag = next.is_some_and(is_regional_indicator)
+            && text[..end].chars().rev().take_while(|c| is_regional_indicator(*c)).count() % 2 == 1;
+        if !next.is_some_and(extends_previous) && before != Some('\u{200D}') && !mid_flag {
+            break;
+        }
+        end -= before.map(char::len_utf8).unwrap_or(0);
+    }
+    &text[..end]
+}
+
+// a name cut down to fit in a file name, with … where it was cut
+pub fn short_name(name: &str) -> String {
+    match name.len() > MAX_NAME_BYTES {
+        true => format!("{}…", truncate(name, MAX_NAME_BYTES - '…'.len_utf8()).trim_end()),
+        false => name.to_string(),
+    }
+}
+
+// lowercase letters, numbers and dashes, safe as a file name on any filesystem
+pub fn slug(name: &str) -> String {
+    let slug: String = name.chars()
+        .map(|c| if c.is_alphanumeric() { c.to_ascii_lowercase() } else { '-' })
+        .collect();
+    truncate(slug.trim_matches('-'), MAX_NAME_BYTES).trim_end_matches('-').to_string()
+}
+
+// a link to a company's note, showing the company's name when --note-name named the note something else
+pub fn company_link(company: &CompanyEntry) -> String {
+    match company.note == company.name {
+        true => format!("[[{}]]", company.name),
+        false => format!("[[{}|{}]]", company.note, company.name.replace(['[', ']', '|'], "")),
+    }
+}
+
+pub fn wikilink(target: &str) -> String {
+    let name = note_name(target);
+
+    match name == target {
+        true => format!("[[{}]]", name),
+        false => format!("[[{}|{}]]", name.trim(), target.replace(['[', ']', '|'], "")),
+    }
+}
+
+// wikilinks have to be quoted in frontmatter or yaml reads [[ as a nested list
+pub fn quoted(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+pub fn quoted_link(target: &str) -> String {
+    quoted(&wikilink(target))
+}
+
+pub fn quoted_link_list<S: AsRef<str>>(targets: &[S]) -> String {
+    let links: Vec<String> = targets.iter().map(|t| quoted_link(t.as_ref())).collect();
+    format!("[{}]", links.join(", "))
+}
+
+// [[note]], [[note|alias]], [[note#heading]] and ![[embed.png]] all point at the part before | or #
+pub fn wikilink_targets(text: &str) -> Vec<String> {
+    let mut targets = Vec::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        rest = &rest[start + 2..];
+        let Some(end) = rest.find("]]") else { break };
+        let target = rest[..end].split(['|', '#']).next().unwrap_or("").trim();
+        if !target.is_empty() {
+            targets.push(target.trim_end_matches(".md").to_string());
+        }
+        rest = &rest[end + 2..];
+    }
+    targets
+}
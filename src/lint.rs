@@ -0,0 +1,208 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use yaml_rust2::YamlLoader;
+
+use crate::term;
+use crate::folders::Folders;
+use crate::manifest::Manifest;
+use crate::{Error, fields, links};
+
+// obsidian's own trash folder, orphans are moved here instead of deleted so a fix can be undone
+const TRASH_DIR: &str = ".trash";
+
+struct Note {
+    path: PathBuf,
+    text: String,
+}
+
+// checks a rendered vault for the things that quietly break it in obsidian, returns how many
+// problems are left after any fixes so the caller can pick an exit code
+pub fn lint_vault(vault_path: &Path, fix: bool, class_name: &str, folders: &Folders) -> Result<usize, Error> {
+    if !vault_path.is_dir() {
+        return Err(Error::Other(format!("{} is not a vault directory", vault_path.to_string_lossy())));
+    }
+
+    let mut files = Vec::new();
+    collect_files(vault_path, &mut files)?;
+
+    // anything can be linked to, only the attachments folder is the renderer's to clean up
+    let attachments_dir = vault_path.join(&folders.attachments);
+    let link_keys = link_keys(vault_path);
+
+    let mut notes = Vec::new();
+    let mut attachments = Vec::new();
+    for path in files {
+        match path.extension().is_some_and(|e| e == "md") {
+            true => notes.push(Note { text: fs::read_to_string(&path)?, path }),
+            false => attachments.push(path),
+        }
+    }
+
+    let note_names: Vec<String> = notes.iter()
+        .filter_map(|n| n.path.file_stem())
+        .map(|s| s.to_string_lossy().to_lowercase())
+        .collect();
+
+    let mut remaining = 0;
+    let mut fixed = 0;
+
+    for note in &mut notes {
+        let display = note.path.strip_prefix(vault_path).unwrap_or(&note.path).to_string_lossy().to_string();
+        let is_company = note.path.parent().is_some_and(|p| p == vault_path.join(&folders.companies));
+
+        if let Some(frontmatter) = frontmatter(&note.text)
+            && YamlLoader::load_from_str(frontmatter).is_err()
+        {
+            match fix.then(|| repair_frontmatter(&note.text)).flatten() {
+                Some(repaired) => {
+                    fs::write(&note.path, &repaired)?;
+                    note.text = repaired;
+                    fixed += 1;
+                    term::info(&format!("{}: fixed, quoted frontmatter values", display));
+                },
+                None => {
+                    remaining += 1;
+                    term::info(&format!("{}: frontmatter doesn't parse", display));
+                },
+            }
+        }
+
+        if is_company && !has_fileclass(&note.text) {
+            match fix {
+                true => {
+                    let repaired = add_fileclass(&note.text, class_name);
+                    fs::write(&note.path, &repaired)?;
+                    note.text = repaired;
+                    fixed += 1;
+                    term::info(&format!("{}: fixed, added fileClass", display));
+                },
+                false => {
+                    remaining += 1;
+                    term::info(&format!("{}: missing fileClass", display));
+                },
+            }
+        }
+
+        // obsidian would just create an empty note when clicked, there is nothing safe to fix it with,
+        // except for link fields whose values are meant to be notes that don't exist yet
+        for target in links::wikilink_targets(&without_link_fields(&note.text, &link_keys)) {
+            let name = target.rsplit('/').next().unwrap_or(&target).to_lowercase();
+            let is_attachment = attachments.iter().any(|a| a.file_name().is_some_and(|f| f.to_string_lossy().to_lowercase() == name));
+            if !note_names.contains(&name) && !is_attachment {
+                remaining += 1;
+                term::info(&format!("{}: broken link [[{}]]", display, target));
+            }
+        }
+    }
+
+    for attachment in attachments.iter().filter(|a| a.starts_with(&attachments_dir)) {
+        let Some(file_name) = attachment.file_name().map(|f| f.to_string_lossy().to_string()) else { continue };
+        let relative = attachment.strip_prefix(vault_path).unwrap_or(attachment).to_string_lossy().replace('\\', "/");
+        let referenced = notes.iter().any(|n| n.text.contains(&file_name) || n.text.contains(&relative));
+        if referenced {
+            continue;
+        }
+
+        match fix {
+            true => {
+                let trash_path = vault_path.join(TRASH_DIR).join(&relative);
+                if let Some(parent) = trash_path.parent() {
+                    fs::create_dir_all(parent)?;
+                }
+                fs::rename(attachment, &trash_path)?;
+                fixed += 1;
+                term::info(&format!("{}: fixed, moved unused attachment to {}", relative, TRASH_DIR));
+            },
+            false => {
+                remaining += 1;
+                term::info(&format!("{}: attachment isn't used by any note", relative));
+            },
+        }
+    }
+
+    match (remaining, fixed) {
+        (0, 0) => term::info("no problems found"),
+        (0, _) => term::info(&format!("fixed {} problems", fixed)),
+        _ => term::info(&format!("{} problems found, fixed {}", remaining, fixed)),
+    }
+
+    Ok(remaining)
+}
+
+// skips the obsidian config, our own cache and the trash since none of that is vault content
+fn collect_files(dir: &Path, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in fs::read_dir(dir)? {
+        let entry = entry?;
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        match entry.file_type()?.is_dir() {
+            true => collect_files(&entry.path(), files)?,
+            false => files.push(entry.path()),
+        }
+    }
+    Ok(())
+}
+
+// the frontmatter keys the last render wrote as links, see --link-fields and --field-format
+fn link_keys(vault_path: &Path) -> Vec<String> {
+    let Some(manifest) = Manifest::load(vault_path) else { return Vec::new() };
+    let key_prefix = manifest.config["key_prefix"].as_str().unwrap_or_default();
+    match manifest.config["field_formats"].as_object() {
+        Some(formats) => formats.iter()
+            .filter(|(_, format)| *format == "link")
+            .map(|(field, _)| format!("{}{}", key_prefix, field))
+            .collect(),
+        None => Vec::new(),
+    }
+}
+
+// link fields are written on one line, as a value or a list in brackets
+fn without_link_fields(text: &str, link_keys: &[String]) -> String {
+    let Some(frontmatter) = frontmatter(text) else { return text.to_string() };
+    let kept: Vec<&str> = frontmatter.lines()
+        .filter(|line| !line.split_once(':').is_some_and(|(key, _)| link_keys.iter().any(|k| k == key)))
+        .collect();
+    format!("---\n{}\n{}", kept.join("\n"), &text[4 + frontmatter.len()..])
+}
+
+fn frontmatter(text: &str) -> Option<&str> {
+    let rest = text.strip_prefix("---\n")?;
+    let end = rest.find("\n---")?;
+    Some(&rest[..end + 1])
+}
+
+fn has_fileclass(text: &str) -> bool {
+    frontmatter(text).is_some_and(|f| f.lines().any(|l| l.starts_with("fileClass:")))
+}
+
+fn add_fileclass(text: &str, class_name: &str) -> String {
+    match text.strip_prefix("---\n") {
+        Some(rest) if frontmatter(text).is_some() => format!("---\nfileClass: {}\n{}", class_name, rest),
+        _ => format!("---\nfileClass: {}\n---\n\n{}", class_name, text),
+    }
+}
+
+// the usual way a note breaks is a plain value with ": " in it, quoting those is safe since
+// it doesn't change what the value reads as, anything else is left for a person to look at
+fn repair_frontmatter(text: &str) -> Option<String> {
+    let frontmatter = frontmatter(text)?;
+    let body = &text[4 + frontmatter.len()..];
+
+    let mut repaired = String::new();
+    for line in frontmatter.lines() {
+        match line.split_once(": ") {
+            Some((key, value)) if !key.starts_with([' ', '\t', '-']) && !value.starts_with(['"', '\'', '[', '{']) => {
+                repaired.push_str(&format!("{}: {}\n", key, fields::yaml_string(value)));
+            },
+            _ => {
+                repaired.push_str(line);
+                repaired.push('\n');
+            },
+        }
+    }
+
+    YamlLoader::load_from_str(&repaired).ok()?;
+    Some(format!("---\n{}{}", repaired, body))
+}
@@ -0,0 +1,103 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sha2::{Digest, Sha256};
+
+use crate::{Error, build_info, state};
+
+// what the vault was last rendered from, lets a repeat render of the same input be a no-op,
+// files holds a hash of what was last written into each generated note so edits can be spotted,
+// outputs is every file in the vault as the render left it, handy for diffing and support requests
+#[derive(Default)]
+pub struct Manifest {
+    pub input_hash: String,
+    pub config: serde_json::Value,
+    pub files: BTreeMap<String, String>,
+    pub outputs: BTreeMap<String, String>,
+}
+
+pub fn manifest_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(".fair_renderer/manifest.json")
+}
+
+pub fn hash_input(input_data: &[u8]) -> String {
+    Sha256::digest(input_data).iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn string_map(json: &serde_json::Value) -> BTreeMap<String, String> {
+    match json.as_object() {
+        Some(map) => map.iter()
+            .filter_map(|(path, hash)| Some((path.clone(), hash.as_str()?.to_string())))
+            .collect(),
+        None => BTreeMap::new(),
+    }
+}
+
+// obsidian's own settings, the trash and the renderer's state change on their own so they're left out
+fn hash_dir(root: &Path, relative: PathBuf, outputs: &mut BTreeMap<String, String>) -> Result<(), Error> {
+    for entry in fs::read_dir(root.join(&relative))? {
+        let entry = entry?;
+        let path = relative.join(entry.file_name());
+        if entry.file_name().to_string_lossy().starts_with('.') {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            hash_dir(root, path, outputs)?;
+        } else {
+            let bytes = fs::read(root.join(&path))?;
+            outputs.insert(path.to_string_lossy().replace('\\', "/"), hash_input(&bytes));
+        }
+    }
+    Ok(())
+}
+
+impl Manifest {
+    pub fn load(vault_path: &Path) -> Option<Manifest> {
+        let bytes = fs::read(manifest_path(vault_path)).ok()?;
+        let json: serde_json::Value = serde_json::from_slice(&bytes).ok()?;
+        Some(Manifest {
+            input_hash: json["input_sha256"].as_str()?.to_string(),
+            config: json["config"].clone(),
+            files: string_map(&json["files"]),
+            outputs: string_map(&json["outputs"]),
+        })
+    }
+
+    // outputs are hashed fresh on every save since anything in the vault may have changed since the last one
+    pub fn save(&mut self, vault_path: &Path) -> Result<(), Error> {
+        self.outputs.clear();
+        hash_dir(vault_path, PathBuf::new(), &mut self.outputs)?;
+
+        let path = manifest_path(vault_path);
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::Output { path: parent.to_string_lossy().to_string(), source })?;
+        }
+        let json = serde_json::json!({
+            "version": build_info::VERSION,
+            "commit": build_info::COMMIT,
+            "input_sha256": self.input_hash,
+            "config": self.config,
+            "files": self.files,
+            "outputs": self.outputs,
+        });
+        state::write_atomic(&path, serde_json::to_string_pretty(&json).unwrap_or_default())
+    }
+
+    // true when the file changed since the hash recorded for it, files from before the
+    // manifest tracked them have nothing to compare against and count as unedited
+    pub fn edited(&self, relative_path: &str, current: &str) -> bool {
+        self.files.get(relative_path).is_some_and(|hash| *hash != hash_input(current.as_bytes()))
+    }
+
+    pub fn record(&mut self, relative_path: &str, written: &str) {
+        self.files.insert(relative_path.to_string(), hash_input(written.as_bytes()));
+    }
+
+    // a note that was moved keeps what was last written into it, edits and all
+    pub fn moved(&mut self, from: &str, to: &str) {
+        if let Some(hash) = self.files.remove(from) {
+            self.files.insert(to.to_string(), hash);
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use std::fs;
+use std::path::Path;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::Error;
+
+// the same field names --column uses for spreadsheets, plus the list of entries itself
+const FIELDS: &[&str] = &[
+    "results", "name", "employer_id", "location", "website", "description", "majors", "job_titles", "job_types",
+    "school_years", "work_authorization", "sessions", "booth", "deadline", "logo_url",
+];
+
+// where each field is found in another portal's export, or in a handshake export after a schema
+// change, fields it doesn't mention are read from where handshake puts them
+pub struct Mapping {
+    fields: Vec<(String, Vec<String>)>,
+}
+
+// /employer/name and employer.name are the same path, [] steps into every element of an array
+// so majors[].name or /majors/[]/name both pick out every major's name
+fn parse_path(path: &str) -> Vec<String> {
+    let segments: Vec<String> = match path.strip_prefix('/') {
+        Some(pointer) => pointer.split('/').map(|s| s.replace("~1", "/").replace("~0", "~")).collect(),
+        None => path.split('.').map(str::to_string).collect(),
+    };
+    segments.into_iter()
+        .flat_map(|segment| match segment.strip_suffix("[]") {
+            Some(key) if !key.is_empty() => vec![key.to_string(), "[]".to_string()],
+            _ => vec![segment],
+        })
+        .filter(|s| !s.is_empty())
+        .collect()
+}
+
+fn select<'a>(value: &'a serde_json::Value, path: &[String]) -> Vec<&'a serde_json::Value> {
+    let mut current = vec![value];
+    for segment in path {
+        current = current.into_iter()
+            .flat_map(|v| match (segment.as_str(), v) {
+                ("[]", serde_json::Value::Array(items)) => items.iter().collect(),
+                ("[]", _) => Vec::new(),
+                (index, serde_json::Value::Array(items)) => index.parse::<usize>().ok().and_then(|i| items.get(i)).into_iter().collect(),
+                (key, v) => v.get(key).into_iter().collect(),
+            })
+            .filter(|v| !v.is_null())
+            .collect();
+    }
+    current
+}
+
+fn text(value: &serde_json::Value) -> Option<String> {
+    match value {
+        serde_json::Value::String(s) => Some(s.clone()),
+        serde_json::Value::Number(n) => Some(n.to_string()),
+        serde_json::Value::Bool(b) => Some(b.to_string()),
+        serde_json::Value::Array(items) => Some(items.iter().filter_map(text).collect::<Vec<String>>().join(", ")),
+        _ => None,
+    }
+}
+
+// a path that ends on an array means its elements, one that ends after [] already is them
+fn elements(selected: Vec<&serde_json::Value>) -> Vec<&serde_json::Value> {
+    match selected.as_slice() {
+        [serde_json::Value::Array(items)] => items.iter().collect(),
+        _ => selected,
+    }
+}
+
+// list entries are {"name": ..} objects in handshake's exports, plain strings get wrapped
+fn named(value: &serde_json::Value, key: &str) -> Option<serde_json::Value> {
+    match value {
+        serde_json::Value::Object(_) if value[key].is_string() => Some(value.clone()),
+        serde_json::Value::Object(_) => {
+            let name = value["name"].as_str().or(value["display_name"].as_str()).or(value["label"].as_str())?;
+            let mut value = value.clone();
+            value[key] = name.into();
+            Some(value)
+        },
+        value => text(value).map(|name| serde_json::json!({ "name": name, "display_name": name })),
+    }
+}
+
+impl Mapping {
+    pub fn load(path: &Path) -> Result<Mapping, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => return Err(Error::Usage(format!("could not read {}: {}", path.to_string_lossy(), e))),
+        };
+        let docs = match YamlLoader::load_from_str(&text) {
+            Ok(docs) => docs,
+            Err(e) => return Err(Error::Usage(format!("invalid yaml in {}: {}", path.to_string_lossy(), e))),
+        };
+        let Some(hash) = docs.first().and_then(Yaml::as_hash) else {
+            return Err(Error::Usage(format!("{} should map fields to paths, ex. name: /employer/name", path.to_string_lossy())));
+        };
+
+        let mut fields = Vec::new();
+        for (key, value) in hash {
+            let (Some(field), Some(pointer)) = (key.as_str(), value.as_str()) else {
+                return Err(Error::Usage(format!("{} should map fields to paths, ex. name: /employer/name", path.to_string_lossy())));
+            };
+            if !FIELDS.contains(&field) {
+                return Err(Error::Usage(format!("unknown field {} in {}, expected one of {}", field, path.to_string_lossy(), FIELDS.join(", "))));
+            }
+            fields.push((field.to_string(), parse_path(pointer)));
+        }
+        Ok(Mapping { fields })
+    }
+
+    fn path(&self, field: &str) -> Option<&[String]> {
+        self.fields.iter().find(|(f, _)| f == field).map(|(_, path)| path.as_slice())
+    }
+
+    // rewrites the export into the shape of a handshake one so the rest of the render doesn't need
+    // to know about the mapping, anything the mapping doesn't touch is left where it was
+    pub fn apply(&self, input_data: Vec<u8>) -> Result<Vec<u8>, Error> {
+        let mut json_data: serde_json::Value = match serde_json::from_slice(&input_data) {
+            Ok(data) => data,
+            Err(source) => return Err(Error::Json { what: "input data".to_string(), source }),
+        };
+
+        let entries: Vec<serde_json::Value> = match self.path("results") {
+            Some(path) => elements(select(&json_data, path)).into_iter().cloned().collect(),
+            None => match json_data["results"].as_array() {
+                Some(entries) => entries.clone(),
+                None => return Err(Error::InvalidInput("input data is an invalid format, map results to where the entries are".to_string())),
+            },
+        };
+
+        let mut results = Vec::new();
+        for original in entries {
+            let mut entry = original.clone();
+            if !entry["employer"].is_object() {
+                entry["employer"] = serde_json::json!({});
+            }
+            for (field, path) in &self.fields {
+                let selected = select(&original, path);
+                let first = selected.first().and_then(|v| text(v));
+                match field.as_str() {
+                    "name" | "website" | "logo_url" => entry["employer"][field.as_str()] = first.into(),
+                    "employer_id" => entry["employer"]["id"] = first.and_then(|id| id.parse::<u64>().ok()).into(),
+                    "location" => entry["location_name"] = first.into(),
+                    "description" => entry["company_description"] = first.into(),
+                    "job_titles" => entry["job_titles"] = first.into(),
+                    "work_authorization" => entry["work_authorization_requirements"] = first.into(),
+                    "deadline" => entry["apply_by"] = first.into(),
+                    "majors" | "job_types" | "school_years" => {
+                        entry[field.as_str()] = elements(selected).into_iter().filter_map(|v| named(v, "name")).collect();
+                    },
+                    "sessions" => {
+                        entry["attending_career_fair_sessions"] = elements(selected).into_iter().filter_map(|v| named(v, "display_name")).collect();
+                    },
+                    "booth" => entry["assigned_booth_identifiers"] = elements(selected).into_iter().filter_map(text).collect(),
+                    _ => {},
+                }
+            }
+            results.push(entry);
+        }
+
+        // the entries could have been the whole document, an array with nothing else around it
+        if !json_data.is_object() {
+            json_data = serde_json::json!({});
+        }
+        json_data["results"] = serde_json::Value::Array(results);
+        Ok(serde_json::to_vec(&json_data).unwrap_or_default())
+    }
+}
@@ -0,0 +1,107 @@
+use sha2::{Digest, Sha256};
+
+use crate::{CompanyEntry, Error, links};
+
+pub const PLACEHOLDERS: &[&str] = &["name", "fair", "date", "month", "year", "id"];
+
+// the notes the renderer writes whole that link to companies, named by the scheme like everything else
+pub const INDEX_NOTES: &[&str] = &["Deadlines", "Schedule", "Checklist", "Team", "Board", "Summary", "Roster History", "Starred"];
+
+// --note-name like "2025-10 Career Fair - {{name}}" or "{{year}}{{id}} {{name}}" for vaults with
+// naming rules, company notes, session links and the index notes all go through it
+pub struct Naming {
+    scheme: Option<String>,
+    fair: Option<String>,
+    date: Option<String>,
+}
+
+pub fn check_scheme(scheme: &str) -> Result<(), Error> {
+    let mut rest = scheme;
+    let mut has_name = false;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let Some(end) = after.find("}}") else {
+            return Err(Error::Usage(format!("note name {} has an unclosed {{{{", scheme)));
+        };
+        match &after[..end] {
+            "name" => has_name = true,
+            placeholder if PLACEHOLDERS.contains(&placeholder) => {},
+            placeholder => return Err(Error::Usage(format!("unknown {{{{{}}}}} in note name, expected one of {}", placeholder, PLACEHOLDERS.join(", ")))),
+        }
+        rest = &after[end + 2..];
+    }
+    match has_name {
+        true => Ok(()),
+        false => Err(Error::Usage("note name needs {{name}} so every company gets its own note".to_string())),
+    }
+}
+
+fn short_hash(text: &str) -> String {
+    Sha256::digest(text.as_bytes()).iter().take(3).map(|b| format!("{:02x}", b)).collect()
+}
+
+impl Naming {
+    // the fair's name and its first day come from the input so every render names notes the same
+    pub fn new(scheme: Option<&str>, input_data: &[u8], companies: &[CompanyEntry]) -> Result<Naming, Error> {
+        let Some(scheme) = scheme.filter(|s| *s != "{{name}}") else {
+            return Ok(Naming { scheme: None, fair: None, date: None });
+        };
+
+        let json: serde_json::Value = serde_json::from_slice(input_data).unwrap_or_default();
+        let fair = json["results"].as_array()
+            .and_then(|results| results.iter().find_map(|r| r["career_fair_name"].as_str()))
+            .map(links::note_name);
+        let date = companies.iter()
+            .flat_map(|c| &c.attending_sessions)
+            .filter_map(|s| s.start.as_ref()?.get(..10).map(str::to_string))
+            .min();
+
+        if scheme.contains("{{fair}}") && fair.is_none() {
+            return Err(Error::Usage("--note-name uses {{fair}} but the input has no career_fair_name".to_string()));
+        }
+        if ["{{date}}", "{{month}}", "{{year}}"].iter().any(|p| scheme.contains(p)) && date.is_none() {
+            return Err(Error::Usage("--note-name uses the fair's date but the input has no session dates".to_string()));
+        }
+        Ok(Naming { scheme: Some(scheme.to_string()), fair, date })
+    }
+
+    fn fill(&self, name: &str, id: &str) -> String {
+        let Some(scheme) = &self.scheme else { return name.to_string() };
+        let date = self.date.as_deref().unwrap_or("");
+        scheme
+            .replace("{{fair}}", self.fair.as_deref().unwrap_or(""))
+            .replace("{{date}}", date)
+            .replace("{{month}}", date.get(..7).unwrap_or(""))
+            .replace("{{year}}", date.get(..4).unwrap_or(""))
+            .replace("{{id}}", id)
+            .replace("{{name}}", name)
+    }
+
+    // the employer id is the stable id when there is one, otherwise a hash of the name, a name too
+    // long for a file name is cut short and the note keeps the full one as its link alias
+    pub fn company(&self, company: &CompanyEntry) -> String {
+        let id = company.employer_id.map(|id| id.to_string()).unwrap_or_else(|| short_hash(&company.name));
+        links::short_name(&self.fill(&company.name, &id))
+    }
+
+    pub fn index(&self, name: &str) -> String {
+        self.fill(name, &short_hash(name))
+    }
+
+    pub fn index_file(&self, name: &str) -> String {
+        format!("{}.md", links::note_name(&self.index(name)))
+    }
+
+    pub fn index_notes(&self) -> Vec<String> {
+        INDEX_NOTES.iter().map(|name| self.index_file(name)).collect()
+    }
+
+    pub fn apply(&self, companies: &mut [CompanyEntry]) {
+        for company in companies.iter_mut() {
+            company.note = self.company(company);
+            for session in company.attending_sessions.iter_mut() {
+                session.note = links::short_name(&links::note_name(&self.index(&session.display_name)));
+            }
+        }
+    }
+}
@@ -0,0 +1,120 @@
+use std::fs;
+use std::path::Path;
+
+use crate::fields::FieldFormats;
+use crate::limits::Limits;
+use crate::{CompanyEntry, Error};
+#[cfg(feature = "network")]
+use crate::links;
+
+pub struct RosterChanges {
+    pub added: Vec<String>,
+    pub removed: Vec<String>,
+    // (company, old sessions, new sessions)
+    pub session_changes: Vec<(String, String, String)>,
+}
+
+impl RosterChanges {
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.session_changes.is_empty()
+    }
+
+    pub fn summary(&self) -> String {
+        let mut text = String::new();
+
+        if !self.added.is_empty() {
+            text.push_str(&format!("{} new companies: {}\n", self.added.len(), self.added.join(", ")));
+        }
+        if !self.removed.is_empty() {
+            text.push_str(&format!("{} companies dropped out: {}\n", self.removed.len(), self.removed.join(", ")));
+        }
+        for (name, old, new) in &self.session_changes {
+            text.push_str(&format!("{} changed sessions: {} -> {}\n", name, old, new));
+        }
+
+        text
+    }
+}
+
+// compares the roster about to be rendered with the notes already in the vault,
+// has to run before the notes are updated
+pub fn diff_roster(companies_dir: &Path, companies: &[CompanyEntry], formats: &FieldFormats, key_prefix: &str, class_name: &str) -> RosterChanges {
+    let mut previous: Vec<(String, String)> = Vec::new();
+    let sessions_key = format!("{}sessions", key_prefix);
+
+    if let Ok(entries) = fs::read_dir(companies_dir) {
+        for entry in entries.flatten() {
+            let path = entry.path();
+            if path.extension().is_none_or(|ext| ext != "md") {
+                continue;
+            }
+            let Ok(text) = fs::read_to_string(&path) else { continue };
+            let class_line = format!("fileClass: {}\n", class_name);
+            if !text.contains(&format!("\n{}", class_line)) && !text.starts_with(&format!("---\n{}", class_line)) {
+                continue;
+            }
+            // notes that failed to write under their own name record it in the body instead
+            let name = match text.lines().find_map(|l| l.strip_prefix("**Company name:** ")) {
+                Some(name) => name.trim().to_string(),
+                None => path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+            };
+            let sessions = text.lines()
+                .find_map(|l| l.strip_prefix(sessions_key.as_str())?.strip_prefix(':'))
+                .unwrap_or("")
+                .trim()
+                .to_string();
+            previous.push((name, sessions));
+        }
+    }
+    previous.sort();
+
+    let mut changes = RosterChanges { added: Vec::new(), removed: Vec::new(), session_changes: Vec::new() };
+
+    for company in companies {
+        // compared the way it would be written so list or link formatted sessions still match
+        let sessions = crate::generated_frontmatter(company, formats, key_prefix).into_iter()
+            .find(|(key, _)| *key == sessions_key)
+            .map(|(_, value)| value)
+            .unwrap_or_default();
+
+        match previous.iter().find(|(name, _)| *name == company.note) {
+            Some((_, old)) if *old != sessions => changes.session_changes.push((company.name.clone(), old.clone(), sessions)),
+            Some(_) => {},
+            None => changes.added.push(company.name.clone()),
+        }
+    }
+
+    for (name, _) in &previous {
+        if !companies.iter().any(|c| c.note == *name) {
+            changes.removed.push(name.clone());
+        }
+    }
+
+    changes
+}
+
+// discord reads "content" and slack reads "text", sending both lets one flag work for either
+#[cfg(feature = "network")]
+pub fn send_webhook(url: &str, changes: &RosterChanges, limits: &Limits) -> Result<(), Error> {
+    let mut message = format!("Career fair roster changed:\n{}", changes.summary());
+
+    // discord rejects messages over 2000 characters
+    if let Some((idx, _)) = message.char_indices().nth(1900) {
+        message = format!("{}\n...", links::truncate(&message, idx));
+    }
+
+    let body = serde_json::json!({
+        "content": message,
+        "text": message,
+    });
+
+    match limits.agent().post(url).header("Content-Type", "application/json").send(body.to_string()) {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::Other(format!("webhook failed: {}", e))),
+    }
+}
+
+#[cfg(not(feature = "network"))]
+pub fn send_webhook(_url: &str, _changes: &RosterChanges, _limits: &Limits) -> Result<(), Error> {
+    Err(crate::missing_feature("--webhook", "network"))
+}
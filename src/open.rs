@@ -0,0 +1,100 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use sha2::{Digest, Sha256};
+
+use crate::{Error, state, upload};
+
+fn absolute_path(vault_path: &Path) -> Result<String, Error> {
+    let absolute = match vault_path.canonicalize() {
+        Ok(path) => path.to_string_lossy().to_string(),
+        Err(e) => return Err(Error::Other(format!("could not find {}: {}", vault_path.to_string_lossy(), e))),
+    };
+    // canonicalize gives windows paths the \\?\ prefix, obsidian doesn't understand those
+    Ok(absolute.strip_prefix(r"\\?\").unwrap_or(&absolute).to_string())
+}
+
+// obsidian registers the obsidian:// scheme with the os, so opening the uri with whatever the
+// platform uses for links hands the vault over to it
+pub fn open_vault(vault_path: &Path) -> Result<(), Error> {
+    let uri = format!("obsidian://open?path={}", upload::uri_encode(&absolute_path(vault_path)?));
+
+    let mut command = if cfg!(target_os = "macos") {
+        Command::new("open")
+    } else if cfg!(target_os = "windows") {
+        // start treats its first quoted argument as a window title
+        let mut command = Command::new("cmd");
+        command.args(["/C", "start", ""]);
+        command
+    } else {
+        Command::new("xdg-open")
+    };
+
+    match command.arg(&uri).output() {
+        Ok(output) if output.status.success() => Ok(()),
+        Ok(output) => Err(Error::Other(format!("failed opening {}: {}", uri, String::from_utf8_lossy(&output.stderr).trim()))),
+        Err(e) => Err(Error::Other(format!("failed opening {}: {}", uri, e))),
+    }
+}
+
+// where obsidian keeps its list of known vaults, flatpak and snap installs keep their config
+// inside their sandbox so those are used when they're the ones that exist
+fn obsidian_config() -> Option<PathBuf> {
+    let env_path = |key: &str| std::env::var_os(key).filter(|v| !v.is_empty()).map(PathBuf::from);
+
+    if cfg!(target_os = "windows") {
+        return Some(env_path("APPDATA")?.join("obsidian/obsidian.json"));
+    }
+    let home = env_path("HOME")?;
+    if cfg!(target_os = "macos") {
+        return Some(home.join("Library/Application Support/obsidian/obsidian.json"));
+    }
+
+    let config_dir = env_path("XDG_CONFIG_HOME").unwrap_or_else(|| home.join(".config"));
+    let candidates = [
+        config_dir.join("obsidian/obsidian.json"),
+        home.join(".var/app/md.obsidian.Obsidian/config/obsidian/obsidian.json"),
+        home.join("snap/obsidian/current/.config/obsidian/obsidian.json"),
+    ];
+    let existing = candidates.iter().find(|p| p.exists()).cloned();
+    existing.or_else(|| candidates.into_iter().next())
+}
+
+// adds the vault to obsidian's vault switcher, returns false when it was already there
+// obsidian rewrites this file when it closes so it should be closed while this runs
+pub fn register_vault(vault_path: &Path) -> Result<bool, Error> {
+    let absolute = absolute_path(vault_path)?;
+    let Some(config_path) = obsidian_config() else {
+        return Err(Error::Other("could not find obsidian's config folder, HOME or APPDATA isn't set".to_string()));
+    };
+
+    let mut config: serde_json::Value = match fs::read(&config_path) {
+        Ok(bytes) => match serde_json::from_slice(&bytes) {
+            Ok(config) => config,
+            Err(source) => return Err(Error::Json { what: config_path.to_string_lossy().to_string(), source }),
+        },
+        Err(_) => serde_json::json!({}),
+    };
+    if !config["vaults"].is_object() {
+        config["vaults"] = serde_json::json!({});
+    }
+    let Some(vaults) = config["vaults"].as_object_mut() else { return Ok(false) };
+
+    if vaults.values().any(|v| v["path"].as_str() == Some(absolute.as_str())) {
+        return Ok(false);
+    }
+
+    // obsidian uses 16 random hex characters, a hash of the path keeps it the same across runs
+    let id: String = Sha256::digest(absolute.as_bytes()).iter().take(8).map(|b| format!("{:02x}", b)).collect();
+    let ts = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_millis() as u64).unwrap_or(0);
+    vaults.insert(id, serde_json::json!({ "path": absolute, "ts": ts }));
+
+    if let Some(parent) = config_path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    // obsidian and other runs read it too, so it's never left half written
+    state::write_atomic(&config_path, serde_json::to_string(&config).unwrap_or_default())?;
+    Ok(true)
+}
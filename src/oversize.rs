@@ -0,0 +1,114 @@
+use crate::folders::Folders;
+use crate::labels::Labels;
+use crate::{CompanyEntry, Error, links};
+
+// a description past this is a pasted brochure rather than an intro, obsidian's editor starts to
+// drag on notes that size so it's cut, --max-field-size changes it
+pub const DEFAULT_MAX_FIELD_SIZE: usize = 32 * 1024;
+
+// frontmatter can't link anywhere, so long job titles and locations are only ever cut
+const MAX_FRONTMATTER_BYTES: usize = 4 * 1024;
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Oversized {
+    // the note keeps the start and links to a note of its own holding all of it
+    Move,
+    // the note keeps the start with a line saying it was cut, the rest is only in the input
+    Truncate,
+}
+
+impl Oversized {
+    pub fn parse(mode: &str) -> Result<Oversized, Error> {
+        match mode {
+            "move" => Ok(Oversized::Move),
+            "truncate" => Ok(Oversized::Truncate),
+            _ => Err(Error::Usage(format!("unknown oversized mode: {}, expected move or truncate", mode))),
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Oversized::Move => "move",
+            Oversized::Truncate => "truncate",
+        }
+    }
+}
+
+// where a moved description goes inside the vault, without .md so it works as a link
+pub fn note_path(folders: &Folders, company: &CompanyEntry) -> String {
+    format!("{}/descriptions/{}", folders.attachments, company.note)
+}
+
+// 120KB for the notice, close enough for someone deciding whether to open it
+fn shown_size(bytes: usize) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1}MB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{}KB", b / 1024),
+        b => format!("{} bytes", b),
+    }
+}
+
+// the start of the text up to max bytes, ending at a paragraph when one is near the end, otherwise
+// at a word
+fn cut(text: &str, max: usize) -> &str {
+    let cut = links::truncate(text, max);
+    match (cut.rfind("\n\n"), cut.rfind(' ')) {
+        (Some(paragraph), _) if paragraph > max / 2 => cut[..paragraph].trim_end(),
+        (_, Some(space)) if space > 0 => cut[..space].trim_end(),
+        _ => cut,
+    }
+}
+
+fn cap_frontmatter(value: &mut String) {
+    if value.len() > MAX_FRONTMATTER_BYTES {
+        *value = format!("{}…", cut(value, MAX_FRONTMATTER_BYTES).trim_end_matches([',', ';', ' ']));
+    }
+}
+
+// cuts descriptions longer than max down to size, with move the whole text goes into the company's
+// overflow_note to be written next to the other attachments, returns how many were cut
+pub fn guard_fields(companies: &mut [CompanyEntry], max: usize, mode: Oversized, folders: &Folders, labels: &Labels) -> usize {
+    let mut guarded = 0;
+    for company in companies.iter_mut() {
+        cap_frontmatter(&mut company.job_titles);
+        cap_frontmatter(&mut company.location);
+
+        let longest = company.description.len().max(company.translated_description.as_ref().map_or(0, String::len));
+        if longest <= max {
+            continue;
+        }
+        guarded += 1;
+
+        let notice = match mode {
+            Oversized::Move => format!("[[{}|{}]]", note_path(folders, company), labels.full_description),
+            Oversized::Truncate => format!("*{}*", labels.cut_short.replace("{size}", &shown_size(longest))),
+        };
+        if mode == Oversized::Move {
+            let mut note = format!("# {}\n\n[[{}]]\n\n", company.name, company.note);
+            match (&company.translated_description, &company.description_language) {
+                (Some(translated), Some(lang)) => {
+                    note.push_str(&format!("### {}\n\n{}\n\n", labels.description, translated.trim()));
+                    note.push_str(&format!("### {} ({})\n\n{}\n", labels.original_description, lang, company.description.trim()));
+                },
+                _ => note.push_str(&format!("### {}\n\n{}\n", labels.description, company.description.trim())),
+            }
+            company.overflow_note = Some(note);
+        }
+
+        // the original is only there folded away under a translation, it gets the notice too
+        if company.description.len() > max {
+            company.description = format!("{}\n\n{}", cut(&company.description, max), notice);
+        }
+        if let Some(translated) = company.translated_description.as_mut().filter(|t| t.len() > max) {
+            *translated = format!("{}\n\n{}", cut(translated, max), notice);
+        }
+    }
+    guarded
+}
+
+// the moved descriptions as (path inside the vault, text)
+pub fn overflow_notes(companies: &[CompanyEntry], folders: &Folders) -> Vec<(String, String)> {
+    companies.iter()
+        .filter_map(|c| c.overflow_note.as_ref().map(|note| (format!("{}.md", note_path(folders, c)), note.clone())))
+        .collect()
+}
@@ -0,0 +1,182 @@
+use std::collections::{BTreeMap, HashSet};
+
+use crate::labels::Labels;
+use crate::naming::Naming;
+use crate::{CompanyEntry, Error, contacts, deadlines, fields, generated_frontmatter, links, schedule, team};
+
+pub const EXTENSIONS: &[&str] = &["md", "txt", "mdx"];
+
+// how the notes are written when they aren't going into an obsidian vault, anything but the
+// defaults turns the render into a plain folder of notes for a static site or a printout
+pub struct Output {
+    pub extension: String,
+    pub frontmatter: bool,
+    pub flat: bool,
+}
+
+impl Default for Output {
+    fn default() -> Self {
+        Output { extension: "md".to_string(), frontmatter: true, flat: false }
+    }
+}
+
+impl Output {
+    pub fn is_vault(&self) -> bool {
+        self.extension == "md" && self.frontmatter && !self.flat
+    }
+
+    // note names can have a / in them, fine as a link target in obsidian but not as a file here
+    fn file_name(&self, name: &str) -> String {
+        format!("{}.{}", links::note_name(name), self.extension)
+    }
+}
+
+pub fn check_extension(extension: &str) -> Result<String, Error> {
+    let extension = extension.trim_start_matches('.');
+    match EXTENSIONS.contains(&extension) {
+        true => Ok(extension.to_string()),
+        false => Err(Error::Usage(format!("unknown extension: {}, expected one of {}", extension, EXTENSIONS.join(", ")))),
+    }
+}
+
+// mdx is parsed as jsx, so braces and angle brackets from the input would break the build
+fn escape(text: &str, output: &Output) -> String {
+    match output.extension.as_str() {
+        "mdx" => text.replace('{', "\\{").replace('}', "\\}").replace('<', "&lt;").replace('>', "&gt;"),
+        _ => text.to_string(),
+    }
+}
+
+// wikilinks mean nothing outside obsidian, ones to a company become a relative link to its file
+// and the rest, like sessions, just their text, a .txt gets the text either way
+fn replace_wikilinks(text: &str, notes: &HashSet<&str>, prefix: &str, output: &Output) -> String {
+    let mut result = String::new();
+    let mut rest = text;
+    while let Some(start) = rest.find("[[") {
+        let Some(end) = rest[start..].find("]]") else { break };
+        result.push_str(&rest[..start]);
+        let inner = rest[start + 2..start + end].replace("\\|", "|");
+        let (target, label) = inner.split_once('|').unwrap_or((&inner, &inner));
+        match notes.contains(target) && output.extension != "txt" {
+            true => result.push_str(&format!("[{}](<{}{}>)", label, prefix, output.file_name(target))),
+            false => result.push_str(label),
+        }
+        rest = &rest[start + end + 2..];
+    }
+    result.push_str(rest);
+    result
+}
+
+fn company_text(company: &CompanyEntry, formats: &fields::FieldFormats, labels: &Labels, output: &Output) -> String {
+    let mut text = String::new();
+
+    match output.frontmatter {
+        true => {
+            text.push_str(&format!("---\ntitle: {}\n", fields::yaml_string(&company.name)));
+            for (key, value) in generated_frontmatter(company, formats, "") {
+                text.push_str(&format!("{}: {}\n", key, value));
+            }
+            text.push_str("---\n\n");
+        },
+        false => text.push_str(&format!("# {}\n\n", escape(&company.name, output))),
+    }
+
+    if !company.logo_url.is_empty() && output.extension != "txt" {
+        text.push_str(&format!("![{}]({})\n\n", escape(&company.name, output), company.logo_url));
+    }
+
+    // without frontmatter the fields would be lost, so they go at the top of the body instead
+    if !output.frontmatter {
+        let sessions: Vec<&str> = company.attending_sessions.iter().map(|s| s.display_name.as_str()).collect();
+        let fields = [
+            ("location", company.location.clone()),
+            ("majors", company.majors.join(", ")),
+            ("job_titles", company.job_titles.clone()),
+            ("job_types", company.job_types.join(", ")),
+            ("school_years", company.school_years.join(", ")),
+            ("international", company.work_authorization.clone()),
+            ("sessions", sessions.join(", ")),
+            ("website", company.website.clone()),
+            ("deadline", company.deadline.clone().unwrap_or_default()),
+            ("industry", company.industry.clone().unwrap_or_default()),
+            ("summary", company.summary.clone().unwrap_or_default()),
+        ];
+        for (key, value) in fields.iter().filter(|(_, value)| !value.is_empty()) {
+            text.push_str(&format!("- {}: {}\n", key, escape(value, output)));
+        }
+        text.push('\n');
+    }
+
+    let description = company.translated_description.as_ref().unwrap_or(&company.description);
+    text.push_str(&format!("## {}\n\n{}\n", labels.description, escape(description, output)));
+
+    if !company.positions.is_empty() {
+        text.push_str(&format!("\n## {}\n\n", labels.open_positions));
+        for position in &company.positions {
+            let title = escape(&position.title, output);
+            let title = match (&position.url, output.extension.as_str()) {
+                (Some(url), "txt") => format!("{}: {}", title, url),
+                (Some(url), _) => format!("[{}]({})", title, url),
+                (None, _) => title,
+            };
+            match position.location.is_empty() {
+                true => text.push_str(&format!("- {}\n", title)),
+                false => text.push_str(&format!("- {}, {}\n", title, escape(&position.location, output))),
+            }
+        }
+    }
+
+    if !company.contacts.is_empty() {
+        text.push_str(&format!("\n## {}\n\n", labels.contacts));
+        for contact in &company.contacts {
+            text.push_str(&escape(&contacts::contact_line(contact, output.extension != "txt"), output));
+        }
+    }
+
+    if !company.links.is_empty() {
+        text.push_str(&format!("\n## {}\n\n", labels.links));
+        for (label, url) in &company.links {
+            match output.extension.as_str() {
+                "txt" => text.push_str(&format!("- {}: {}\n", label, url)),
+                _ => text.push_str(&format!("- [{}]({})\n", label, url)),
+            }
+        }
+    }
+    text
+}
+
+// the company notes and the index notes that only need the input, keyed by their path in the
+// output, the ones read back from the notes like the checklist and board are left out since nothing
+// here gets edited, every run simply overwrites the last
+pub fn render_plain(
+    companies: &[CompanyEntry],
+    formats: &fields::FieldFormats,
+    companies_folder: &str,
+    team_members: &[team::Member],
+    naming: &Naming,
+    labels: &Labels,
+    output: &Output,
+) -> BTreeMap<String, String> {
+    let prefix = match output.flat {
+        true => String::new(),
+        false => format!("{}/", companies_folder),
+    };
+    let notes: HashSet<&str> = companies.iter().map(|c| c.note.as_str()).collect();
+
+    let mut files = BTreeMap::new();
+    for company in companies {
+        files.insert(format!("{}{}", prefix, output.file_name(&company.note)), company_text(company, formats, labels, output));
+    }
+
+    let index_notes = [
+        ("Deadlines", deadlines::render_deadlines(companies, false, labels)),
+        ("Schedule", schedule::render_schedule(companies, labels)),
+        ("Team", team::render_team(companies, team_members, labels)),
+    ];
+    for (name, text) in index_notes {
+        let Some(text) = text else { continue };
+        let text = replace_wikilinks(&escape(&text, output), &notes, &prefix, output);
+        files.insert(output.file_name(&naming.index(name)), text);
+    }
+    files
+}
@@ -0,0 +1,172 @@
+use std::collections::BTreeMap;
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Deserializer};
+
+use crate::Error;
+use crate::term::{self, Status};
+
+// how a render treats problems it could carry on past, a missing field, a logo that didn't download,
+// a note that wouldn't write, --strict stops on the first, the default prints them and carries on,
+// --lenient carries on and only mentions them with -v
+#[derive(Clone, Copy, PartialEq, Default)]
+pub enum Policy {
+    Strict,
+    #[default]
+    Warn,
+    Lenient,
+}
+
+impl Policy {
+    pub fn fail(self, msg: &str) -> Result<(), Error> {
+        match self {
+            Policy::Strict => Err(Error::Other(msg.to_string())),
+            Policy::Warn => {
+                term::status(Status::Failed, msg);
+                Ok(())
+            },
+            Policy::Lenient => {
+                term::debug(msg);
+                Ok(())
+            },
+        }
+    }
+}
+
+// a field of the input that can be left out, null or the wrong type without failing the whole entry,
+// what was wrong with it is kept for the error --strict gives
+#[derive(Default)]
+pub enum Loose<T> {
+    #[default]
+    Missing,
+    Invalid(String),
+    Present(T),
+}
+
+impl<T> Loose<T> {
+    pub fn is_present(&self) -> bool {
+        matches!(self, Loose::Present(_))
+    }
+
+    pub fn into_option(self) -> Option<T> {
+        match self {
+            Loose::Present(value) => Some(value),
+            _ => None,
+        }
+    }
+}
+
+impl<T: DeserializeOwned> Loose<T> {
+    // ex. one entry of a list that's read as plain json
+    pub fn from_value(value: &serde_json::Value) -> Loose<T> {
+        match value {
+            serde_json::Value::Null => Loose::Missing,
+            value => match T::deserialize(value) {
+                Ok(value) => Loose::Present(value),
+                Err(e) => Loose::Invalid(e.to_string()),
+            },
+        }
+    }
+}
+
+impl<'de, T: DeserializeOwned> Deserialize<'de> for Loose<T> {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Ok(Loose::from_value(&serde_json::Value::deserialize(deserializer)?))
+    }
+}
+
+// how errors and warnings point at an entry, the same way validate does, index is its place in results
+pub fn entry_label(index: usize, company: Option<&str>) -> String {
+    match company {
+        Some(company) => format!("entry {} ({})", index, company),
+        None => format!("entry {}", index),
+    }
+}
+
+// the first few are named in the warning so they can be found in the input, all of them with -v
+const NAMED_ENTRIES: usize = 5;
+
+// fields that were missing or the wrong type and got an empty value instead, counted so a big
+// export with one field gone prints a line per field rather than one per company, with the entries
+pub struct Defaults {
+    policy: Policy,
+    counts: BTreeMap<&'static str, Vec<String>>,
+    skipped: BTreeMap<&'static str, Vec<String>>,
+    // the entry being read, for the error --strict gives
+    index: usize,
+    company: Option<String>,
+}
+
+impl Defaults {
+    pub fn new(policy: Policy) -> Defaults {
+        Defaults { policy, counts: BTreeMap::new(), skipped: BTreeMap::new(), index: 0, company: None }
+    }
+
+    pub fn entry(&mut self, index: usize, company: Option<&str>) {
+        self.index = index;
+        self.company = company.map(str::to_string);
+    }
+
+    // field is what the warnings call it, path where it is in the entry's json
+    pub fn missing(&mut self, field: &'static str, path: &str) -> Result<(), Error> {
+        if self.policy == Policy::Strict {
+            return Err(self.error(path, "missing"));
+        }
+        let label = self.label();
+        self.counts.entry(field).or_default().push(label);
+        Ok(())
+    }
+
+    // an entry without the field it can't do without, like a company without a name, is left out
+    pub fn unusable(&mut self, field: &'static str, path: &str) -> Result<(), Error> {
+        if self.policy == Policy::Strict {
+            return Err(self.error(path, "missing"));
+        }
+        let label = self.label();
+        self.skipped.entry(field).or_default().push(label);
+        Ok(())
+    }
+
+    // the value, or an empty one counted as missing
+    pub fn take<T: Default>(&mut self, value: Loose<T>, field: &'static str, path: &str) -> Result<T, Error> {
+        match value {
+            Loose::Present(value) => Ok(value),
+            Loose::Invalid(e) if self.policy == Policy::Strict => Err(self.error(path, &e)),
+            _ => {
+                self.missing(field, path)?;
+                Ok(T::default())
+            },
+        }
+    }
+
+    // an entry missing several values of a list is only named once
+    fn label(&self) -> String {
+        entry_label(self.index, self.company.as_deref())
+    }
+
+    fn error(&self, path: &str, problem: &str) -> Error {
+        Error::MissingField { index: self.index, company: self.company.clone(), field: path.to_string(), problem: problem.to_string() }
+    }
+
+    pub fn report(&self) {
+        let mut lines: Vec<String> = self.counts.iter()
+            .map(|(field, entries)| format!("{} entries had no usable {}, left it empty: {}", entries.len(), field, named(entries)))
+            .collect();
+        lines.extend(self.skipped.iter().map(|(field, entries)| format!("left out {} entries with no usable {}: {}", entries.len(), field, named(entries))));
+        for line in lines {
+            match self.policy {
+                Policy::Lenient => term::debug(&line),
+                _ => term::warn(&line),
+            }
+        }
+    }
+}
+
+fn named(entries: &[String]) -> String {
+    let mut entries = entries.to_vec();
+    entries.dedup();
+    match entries.len() > NAMED_ENTRIES && !term::verbose() {
+        true => format!("{} and {} more, -v lists them all", entries[..NAMED_ENTRIES].join(", "), entries.len() - NAMED_ENTRIES),
+        false => entries.join(", "),
+    }
+}
@@ -0,0 +1,138 @@
+use std::fs;
+use std::path::Path;
+
+use crate::policy::Policy;
+use crate::{CompanyEntry, Error, fields, links, update};
+
+// matched against descriptions and job titles, kept broad since fairs cover every major
+const SKILLS: &[&str] = &[
+    "Python", "Java", "C++", "C#", "JavaScript", "TypeScript", "SQL", "Rust", "MATLAB", "Excel",
+    "AWS", "Azure", "Linux", "Git", "CAD", "SolidWorks", "AutoCAD", "Simulink", "PLC", "Six Sigma", "Lean",
+    "machine learning", "data analysis", "statistics", "embedded", "cloud", "networking", "cybersecurity",
+    "project management", "leadership", "communication", "teamwork", "research", "manufacturing",
+    "quality", "supply chain", "sales", "marketing", "accounting", "finance", "healthcare", "laboratory",
+];
+
+const QUESTIONS: &[&str] = &[
+    "What does a typical first year look like for someone in this role?",
+    "Which teams are hiring the most right now?",
+    "What do your strongest interns or new grads have in common?",
+    "What is the timeline after the fair, and who should I follow up with?",
+];
+
+// a frontmatter value the user filled in on a company note, None when it's missing or empty
+pub fn note_field(companies_dir: &Path, company: &CompanyEntry, field: &str) -> Option<String> {
+    let text = fs::read_to_string(companies_dir.join(format!("{}.md", company.note))).ok()?;
+    let value = text.lines()
+        .skip(1)
+        .take_while(|l| *l != "---")
+        .find_map(|l| l.strip_prefix(&format!("{}:", field)))?
+        .trim();
+    (!value.is_empty()).then(|| value.to_string())
+}
+
+// the Priority field from the template is how companies get marked as worth preparing for
+pub fn priority_companies<'a>(companies_dir: &Path, companies: &'a [CompanyEntry]) -> Vec<&'a CompanyEntry> {
+    companies.iter()
+        .filter(|company| {
+            note_field(companies_dir, company, "Priority")
+                .and_then(|v| v.parse::<f64>().ok())
+                .is_some_and(|p| p >= 1.0)
+        })
+        .collect()
+}
+
+// whole words only so "Git" doesn't match "GitHub", case only matters for the short ones
+pub fn extract_skills(text: &str) -> Vec<&'static str> {
+    SKILLS.iter()
+        .filter(|skill| {
+            let haystack = if skill.len() <= 3 { text.to_string() } else { text.to_lowercase() };
+            let needle = if skill.len() <= 3 { skill.to_string() } else { skill.to_lowercase() };
+            haystack.match_indices(&needle).any(|(i, _)| {
+                let before = haystack[..i].chars().next_back();
+                let after = haystack[i + needle.len()..].chars().next();
+                !before.is_some_and(|c| c.is_alphanumeric()) && !after.is_some_and(|c| c.is_alphanumeric() || c == '+' || c == '#')
+            })
+        })
+        .copied()
+        .collect()
+}
+
+// percent of my majors the company is recruiting, None when either side didn't list any
+pub fn match_score(company: &CompanyEntry, my_majors: &[String]) -> Option<u32> {
+    if my_majors.is_empty() || company.majors.is_empty() {
+        return None;
+    }
+    let matched = my_majors.iter()
+        .filter(|m| company.majors.iter().any(|c| c.eq_ignore_ascii_case(m.trim())))
+        .count();
+    Some((matched * 100 / my_majors.len()) as u32)
+}
+
+fn sections(company: &CompanyEntry, skills: &[&str], score: Option<u32>) -> Vec<(&'static str, String)> {
+    let description = company.translated_description.as_ref().unwrap_or(&company.description);
+    let mut overview = format!("# {}\n\n{}\n", links::company_link(company), description.trim());
+    if !company.website.is_empty() {
+        overview.push_str(&format!("\n{}\n", company.website));
+    }
+
+    let mut skills_text = "### Skills\n\n".to_string();
+    match skills.is_empty() {
+        true => skills_text.push_str("None found in the description\n"),
+        false => skills.iter().for_each(|s| skills_text.push_str(&format!("- {}\n", s))),
+    }
+
+    let mut jobs_text = "### Positions\n\n".to_string();
+    let titles: Vec<&str> = company.job_titles.split(',').map(str::trim).filter(|t| !t.is_empty() && *t != "None").collect();
+    for title in &titles {
+        jobs_text.push_str(&format!("- {}\n", title));
+    }
+    if !company.job_types.is_empty() {
+        if !titles.is_empty() {
+            jobs_text.push('\n');
+        }
+        jobs_text.push_str(&format!("{}\n", company.job_types.join(", ")));
+    }
+
+    let mut sections = vec![("overview", overview), ("skills", skills_text), ("positions", jobs_text)];
+    if let Some(score) = score {
+        sections.push(("match", format!("### Match\n\n{}% of my majors\n", score)));
+    }
+    sections
+}
+
+// prep/<company> - Prep.md for every company with a Priority, the questions are only written
+// once since they are meant to be edited and answered
+pub fn write_prep_notes(prep_dir: &Path, companies: &[&CompanyEntry], my_majors: &[String], policy: Policy) -> Result<usize, Error> {
+    fs::create_dir_all(prep_dir)?;
+
+    let mut questions = "\n### Questions\n\n".to_string();
+    for question in QUESTIONS {
+        questions.push_str(&format!("- {}\n  - \n", question));
+    }
+
+    let mut written = 0;
+    for company in companies {
+        let skills = extract_skills(&format!("{}\n{}", company.description, company.job_titles));
+        let score = match_score(company, my_majors);
+
+        let mut raw = vec![("company", fields::RawValue::One(company.note.clone()))];
+        raw.push(("skills", fields::RawValue::Many(skills.iter().map(|s| s.to_string()).collect())));
+        if let Some(score) = score {
+            raw.push(("match_score", fields::RawValue::One(score.to_string())));
+        }
+        let mut formats = fields::FieldFormats::default();
+        formats.set("company", fields::FieldFormat::Link);
+        formats.set("skills", fields::FieldFormat::List);
+        let generated = fields::format_frontmatter(raw, &formats);
+
+        let file_path = prep_dir.join(format!("{} - Prep.md", company.note));
+        match update::write_or_update(&file_path, &generated, &sections(company, &skills, score), Some(&questions)) {
+            Ok(true) => written += 1,
+            Ok(false) => {},
+            Err(e) => policy.fail(&format!("{}: {}", file_path.to_string_lossy(), e))?,
+        }
+    }
+
+    Ok(written)
+}
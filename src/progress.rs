@@ -0,0 +1,86 @@
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+
+use crate::{Error, state};
+
+// the steps a render finished, one per line after the hash of the input it was rendering, appended
+// as they finish so a render that dies halfway leaves behind exactly what it got through, a step that
+// wrote a note is followed by the note's path and the hash the manifest got for it, tab separated
+pub struct Progress {
+    path: PathBuf,
+    done: Vec<String>,
+}
+
+fn progress_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(".fair_renderer/progress")
+}
+
+impl Progress {
+    // with resume the steps an unfinished render of the same input got through are kept,
+    // anything else starts over
+    pub fn start(vault_path: &Path, input_hash: &str, resume: bool) -> Result<Progress, Error> {
+        let path = progress_path(vault_path);
+        let previous = fs::read_to_string(&path).unwrap_or_default();
+        let mut lines = previous.lines();
+
+        let done: Vec<String> = match lines.next() {
+            Some(hash) if resume && hash == input_hash => lines.map(str::to_string).collect(),
+            Some(_) if resume => {
+                crate::term::info("the unfinished render was for different input, starting over");
+                Vec::new()
+            },
+            _ => Vec::new(),
+        };
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::Output { path: parent.to_string_lossy().to_string(), source })?;
+        }
+        let mut text = format!("{}\n", input_hash);
+        for step in &done {
+            text.push_str(&format!("{}\n", step));
+        }
+        state::write_atomic(&path, text)?;
+
+        Ok(Progress { path, done })
+    }
+
+    pub fn is_done(&self, step: &str) -> bool {
+        self.done.iter().any(|d| d.split('\t').next() == Some(step))
+    }
+
+    // the note path and manifest hash a finished step recorded, None when it left the note alone
+    pub fn recorded(&self, step: &str) -> Option<(&str, &str)> {
+        self.done.iter().find_map(|d| match d.split('\t').collect::<Vec<_>>()[..] {
+            [done, path, hash] if done == step => Some((path, hash)),
+            _ => None,
+        })
+    }
+
+    pub fn resumed(&self) -> usize {
+        self.done.len()
+    }
+
+    pub fn mark_done(&mut self, step: &str, recorded: Option<(&str, &str)>) -> Result<(), Error> {
+        let line = match recorded {
+            Some((path, hash)) => format!("{}\t{}\t{}", step, path, hash),
+            None => step.to_string(),
+        };
+        let failed = |source| Error::Output { path: self.path.to_string_lossy().to_string(), source };
+        let mut file = OpenOptions::new().append(true).open(&self.path).map_err(failed)?;
+        writeln!(file, "{}", line).map_err(failed)?;
+        self.done.push(line);
+        Ok(())
+    }
+
+    // a finished render has nothing to resume
+    pub fn finish(self) -> Result<(), Error> {
+        fs::remove_file(&self.path).map_err(|source| Error::Output { path: self.path.to_string_lossy().to_string(), source })?;
+        Ok(())
+    }
+}
+
+// an unfinished render is what --resume picks up
+pub fn unfinished(vault_path: &Path) -> bool {
+    progress_path(vault_path).exists()
+}
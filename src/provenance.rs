@@ -0,0 +1,35 @@
+use crate::fields::{FieldFormat, FieldFormats};
+use crate::{CompanyEntry, term};
+
+// where a fair registration's value was read from, or that it was missing and left empty
+pub fn fair_source(index: usize, path: &str, found: bool) -> String {
+    match found {
+        true => format!("results[{}].{}", index, path),
+        false => format!("results[{}].{} was missing, left empty", index, path),
+    }
+}
+
+// -vv only, one line per frontmatter key with the json it came from and how it was written out,
+// for working out why a value looks the way it does in an export nobody has seen before
+pub fn trace_frontmatter(company: &CompanyEntry, generated: &[(String, String)], formats: &FieldFormats, key_prefix: &str) {
+    if !term::tracing() {
+        return;
+    }
+
+    let mut sources: Vec<(&str, &str)> = company.sources.iter().map(|(key, source)| (*key, source.as_str())).collect();
+    if company.description_language.is_some() {
+        sources.push(("language", "detected from company_description"));
+    }
+
+    term::trace(&format!("frontmatter for {}:", company.name));
+    for (key, source) in sources {
+        let written = generated.iter().any(|(k, _)| k.strip_prefix(key_prefix) == Some(key));
+        let rule = match (formats.get(key), formats.0.iter().any(|(f, _)| f == key)) {
+            (FieldFormat::Tag, _) => "tag from --field-format, merged into tags".to_string(),
+            _ if !written => continue,
+            (format, true) => format!("{} from --field-format", format.name()),
+            (format, false) => format!("{} by default", format.name()),
+        };
+        term::trace(&format!("  {}{}: {}, written as {}", key_prefix, key, source, rule));
+    }
+}
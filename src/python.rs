@@ -0,0 +1,136 @@
+use std::path::PathBuf;
+use std::time::Instant;
+
+use pyo3::exceptions::{PyOSError, PyRuntimeError, PyValueError};
+use pyo3::prelude::*;
+use pyo3::types::{PyDict, PyList};
+use serde_json::{Value, json};
+
+use crate::{CompanyEntry, Error, Fair, VaultRenderer};
+
+// bad input is a ValueError and a vault that can't be written an OSError, like python's own would be
+impl From<Error> for PyErr {
+    fn from(e: Error) -> PyErr {
+        match e {
+            Error::Json { .. } | Error::MissingField { .. } | Error::InvalidInput(_) | Error::Usage(_) => PyValueError::new_err(e.to_string()),
+            Error::Io(_) | Error::Input { .. } | Error::Output { .. } => PyOSError::new_err(e.to_string()),
+            _ => PyRuntimeError::new_err(e.to_string()),
+        }
+    }
+}
+
+#[pyclass(name = "Fair", frozen)]
+struct PyFair(Fair);
+
+#[pymethods]
+impl PyFair {
+    // plain dicts so scripts can filter and sort them without knowing about this module
+    #[getter]
+    fn companies<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyList>> {
+        let companies = self.0.companies.iter().map(|company| to_python(py, &company_json(company))).collect::<PyResult<Vec<_>>>()?;
+        PyList::new(py, companies)
+    }
+
+    fn __len__(&self) -> usize {
+        self.0.companies.len()
+    }
+}
+
+// any export --input takes, as bytes
+#[pyfunction]
+fn parse_fair(json_bytes: &[u8]) -> PyResult<PyFair> {
+    Ok(PyFair(Fair::parse(json_bytes)?))
+}
+
+// the same as VaultRenderer::render with its options as keyword arguments, what it wrote and skipped
+// comes back as the dict --report would save
+#[pyfunction]
+#[pyo3(signature = (fair, vault_path, template=None, update=false, lang=None, note_name=None, class_name=None, key_prefix=None, link_fields=false, mini=false, summarize=false, strict=false, build_info=true))]
+#[allow(clippy::too_many_arguments)]
+fn render_vault<'py>(
+    py: Python<'py>,
+    fair: &PyFair,
+    vault_path: PathBuf,
+    template: Option<PathBuf>,
+    update: bool,
+    lang: Option<String>,
+    note_name: Option<String>,
+    class_name: Option<String>,
+    key_prefix: Option<String>,
+    link_fields: bool,
+    mini: bool,
+    summarize: bool,
+    strict: bool,
+    build_info: bool,
+) -> PyResult<Bound<'py, PyAny>> {
+    let started = Instant::now();
+    let defaults = VaultRenderer::default();
+    let renderer = VaultRenderer {
+        template,
+        update,
+        lang,
+        note_name,
+        class_name: class_name.unwrap_or(defaults.class_name),
+        key_prefix: key_prefix.unwrap_or(defaults.key_prefix),
+        link_fields,
+        mini,
+        summarize,
+        strict,
+        build_info,
+        hooks: defaults.hooks,
+    };
+    let report = renderer.render(&fair.0, &vault_path)?;
+    to_python(py, &report.to_json(started.elapsed(), None))
+}
+
+#[pymodule]
+fn fair_renderer(module: &Bound<'_, PyModule>) -> PyResult<()> {
+    module.add_class::<PyFair>()?;
+    module.add_function(wrap_pyfunction!(parse_fair, module)?)?;
+    module.add_function(wrap_pyfunction!(render_vault, module)?)?;
+    Ok(())
+}
+
+fn company_json(company: &CompanyEntry) -> Value {
+    json!({
+        "name": company.name,
+        "note": company.note,
+        "description": company.description,
+        "location": company.location,
+        "website": company.website,
+        "logo_url": company.logo_url,
+        "work_authorization": company.work_authorization,
+        "job_titles": company.job_titles,
+        "job_types": company.job_types,
+        "majors": company.majors,
+        "school_years": company.school_years,
+        "sessions": company.attending_sessions.iter().map(|s| json!({"name": s.display_name, "start": s.start, "end": s.end})).collect::<Vec<_>>(),
+        "employer_id": company.employer_id,
+        "booth": company.booth,
+        "deadline": company.deadline,
+        "industry": company.industry,
+        "links": company.links.iter().map(|(label, url)| json!({"label": label, "url": url})).collect::<Vec<_>>(),
+        "positions": company.positions.iter().map(|p| json!({"title": p.title, "location": p.location, "url": p.url})).collect::<Vec<_>>(),
+        "contacts": company.contacts.iter().map(|c| json!({"name": c.name, "title": c.title, "email": c.email})).collect::<Vec<_>>(),
+    })
+}
+
+fn to_python<'py>(py: Python<'py>, value: &Value) -> PyResult<Bound<'py, PyAny>> {
+    Ok(match value {
+        Value::Null => py.None().into_bound(py),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any(),
+        Value::Number(n) => match n.as_i64() {
+            Some(n) => n.into_pyobject(py)?.into_any(),
+            None => n.as_f64().unwrap_or_default().into_pyobject(py)?.into_any(),
+        },
+        Value::String(s) => s.into_pyobject(py)?.into_any(),
+        Value::Array(items) => PyList::new(py, items.iter().map(|item| to_python(py, item)).collect::<PyResult<Vec<_>>>()?)?.into_any(),
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, to_python(py, value)?)?;
+            }
+            dict.into_any()
+        },
+    })
+}
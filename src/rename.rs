@@ -0,0 +1,143 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::term::{self, Status};
+use crate::folders::Folders;
+use crate::manifest::Manifest;
+use crate::policy::Policy;
+use crate::{CompanyEntry, Error, links, vault_relative, write_file};
+
+pub struct Rename {
+    old_name: String,
+    new_name: String,
+    old_path: PathBuf,
+}
+
+// handshake keeps the employer id when a company changes its display name, so an existing note
+// with the same id but a different name is the same company under its new name
+pub fn find_renames(companies_dir: &Path, companies: &[CompanyEntry], key_prefix: &str) -> Vec<Rename> {
+    let mut renames = Vec::new();
+    let Ok(entries) = fs::read_dir(companies_dir) else { return renames };
+
+    let id_key = format!("{}employer_id:", key_prefix);
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        if path.extension().is_none_or(|ext| ext != "md") {
+            continue;
+        }
+        let Ok(text) = fs::read_to_string(&path) else { continue };
+        let Some(id) = text.lines()
+            .skip(1)
+            .take_while(|l| *l != "---")
+            .find_map(|l| l.strip_prefix(id_key.as_str()))
+            .and_then(|v| v.trim().trim_matches('"').parse::<u64>().ok())
+        else { continue };
+
+        let old_name = match text.lines().find_map(|l| l.strip_prefix("**Company name:** ")) {
+            Some(name) => name.trim().to_string(),
+            None => path.file_stem().unwrap_or_default().to_string_lossy().to_string(),
+        };
+
+        let Some(company) = companies.iter().find(|c| c.employer_id == Some(id)) else { continue };
+        if company.note != old_name {
+            renames.push(Rename { old_name, new_name: company.note.clone(), old_path: path });
+        }
+    }
+
+    renames
+}
+
+// moves the note and its follow-up and prep notes to the new name and fixes the links our own notes have to it,
+// a name that is already taken is skipped rather than clobbering that note, index_notes are the notes
+// the renderer writes whole, any user note is left for obsidian to deal with, only renames that went
+// through get their links fixed, the others still point at the note under its old name
+pub fn apply_renames(vault_path: &Path, renames: &[Rename], index_notes: &[String], folders: &Folders, manifest: &mut Manifest, policy: Policy) -> Result<(), Error> {
+    let companies_dir = vault_path.join(&folders.companies);
+    let generated_dirs = [&folders.follow_ups, &folders.events, &folders.jobs, &folders.prep];
+    // per company notes that carry the company name in their file name, (folder, suffix)
+    let company_notes = [(&folders.follow_ups, " - Follow-up.md"), (&folders.prep, " - Prep.md")];
+
+    // the manifest's hashes go along with the notes so an edit made under the old name is still spotted
+    let shown = vault_path.to_string_lossy().to_string();
+    let mut moved = |from: &Path, to: &Path| manifest.moved(&vault_relative(&shown, from), &vault_relative(&shown, to));
+
+    let mut renamed = Vec::new();
+    for rename in renames {
+        let new_path = companies_dir.join(format!("{}.md", rename.new_name));
+        if new_path.exists() {
+            term::status(Status::Skipped, &format!("renaming {} to {}, that note already exists", rename.old_name, rename.new_name));
+            continue;
+        }
+        if let Err(e) = fs::rename(&rename.old_path, &new_path) {
+            policy.fail(&format!("renaming {} to {}: {}", rename.old_name, rename.new_name, e))?;
+            continue;
+        }
+        moved(&rename.old_path, &new_path);
+
+        for (dir, suffix) in company_notes {
+            let old_path = vault_path.join(dir).join(format!("{}{}", rename.old_name, suffix));
+            let new_path = vault_path.join(dir).join(format!("{}{}", rename.new_name, suffix));
+            if old_path.exists() && !new_path.exists() {
+                fs::rename(&old_path, &new_path)?;
+                moved(&old_path, &new_path);
+            }
+        }
+
+        term::info(&format!("{} was renamed to {}", rename.old_name, rename.new_name));
+        renamed.push(rename);
+    }
+    if renamed.is_empty() {
+        return Ok(());
+    }
+
+    // the index notes are in the manifest, they're recorded with the new links so the next render
+    // doesn't take the rewrite for the user's edit, one the user did edit stays that way
+    for name in index_notes {
+        let path = vault_path.join(name);
+        let Ok(text) = fs::read_to_string(&path) else { continue };
+        let rewritten = renamed.iter().fold(text.clone(), |text, r| rewrite_links(&text, &r.old_name, &r.new_name));
+        if rewritten != text {
+            write_file(&path, &rewritten)?;
+            if !manifest.edited(name, &text) {
+                manifest.record(name, &rewritten);
+            }
+            term::status(Status::Updated, &path.to_string_lossy());
+        }
+    }
+
+    let mut notes = Vec::new();
+    for dir in generated_dirs {
+        collect_notes(&vault_path.join(dir), &mut notes);
+    }
+    for path in notes.iter().filter(|p| p.extension().is_some_and(|e| e == "md")) {
+        let Ok(text) = fs::read_to_string(path) else { continue };
+        let rewritten = renamed.iter().fold(text.clone(), |text, r| rewrite_links(&text, &r.old_name, &r.new_name));
+        if rewritten != text {
+            write_file(path, &rewritten)?;
+            term::status(Status::Updated, &path.to_string_lossy());
+        }
+    }
+
+    Ok(())
+}
+
+// covers [[old]], [[old|alias]] and [[old#heading]], both as written and the way links::wikilink
+// would have cleaned up the name
+fn rewrite_links(text: &str, old_name: &str, new_name: &str) -> String {
+    let mut text = text.replace(&links::wikilink(old_name), &links::wikilink(new_name));
+    for end in ["]]", "|", "#"] {
+        text = text.replace(&format!("[[{}{}", old_name, end), &format!("[[{}{}", new_name, end));
+    }
+    text
+}
+
+fn collect_notes(dir: &Path, notes: &mut Vec<PathBuf>) {
+    let Ok(entries) = fs::read_dir(dir) else { return };
+    for entry in entries.flatten() {
+        match entry.path().is_dir() {
+            true => collect_notes(&entry.path(), notes),
+            false => notes.push(entry.path()),
+        }
+    }
+}
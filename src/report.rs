@@ -0,0 +1,85 @@
+use std::time::Duration;
+
+use serde_json::json;
+
+use crate::term;
+
+// a note that couldn't be written under its own name, fallback is where it went instead
+pub struct WriteFailure {
+    pub path: String,
+    pub error: String,
+    pub fallback: Option<String>,
+}
+
+// what a render left alone on purpose or couldn't write, printed once at the end so it doesn't
+// get lost between the per file status lines, and with --report saved as json along with what it did write
+#[derive(Default)]
+pub struct RenderReport {
+    pub companies: usize,
+    // vault relative, the notes that were written or changed, ones an update left as they were aren't
+    pub written: Vec<String>,
+    pub skipped: Vec<(String, String)>,
+    pub failed: Vec<WriteFailure>,
+    // input entries --skip-invalid left out, as (entry, reason)
+    pub skipped_entries: Vec<(String, String)>,
+}
+
+impl RenderReport {
+    pub fn wrote(&mut self, path: &str) {
+        self.written.push(path.to_string());
+    }
+
+    pub fn skip(&mut self, path: &str, reason: &str) {
+        self.skipped.push((path.to_string(), reason.to_string()));
+    }
+
+    pub fn fail(&mut self, path: &str, error: &str, fallback: Option<&str>) {
+        self.failed.push(WriteFailure {
+            path: path.to_string(),
+            error: error.to_string(),
+            fallback: fallback.map(str::to_string),
+        });
+    }
+
+    // everything here is a warning, so it goes to stderr with the rest of them
+    pub fn print(&self) {
+        if !self.skipped_entries.is_empty() {
+            term::warn(&format!("skipped {} invalid entries:", self.skipped_entries.len()));
+            for (entry, reason) in &self.skipped_entries {
+                term::warn(&format!("  {}: {}", entry, reason));
+            }
+        }
+        if !self.skipped.is_empty() {
+            term::warn(&format!("skipped {} files:", self.skipped.len()));
+            for (path, reason) in &self.skipped {
+                term::warn(&format!("  {}: {}", path, reason));
+            }
+        }
+        if !self.failed.is_empty() {
+            term::warn(&format!("failed writing {} files:", self.failed.len()));
+            for failure in &self.failed {
+                match &failure.fallback {
+                    Some(fallback) => term::warn(&format!("  {}: {}, written as {} instead", failure.path, failure.error, fallback)),
+                    None => term::warn(&format!("  {}: {}", failure.path, failure.error)),
+                }
+            }
+        }
+    }
+
+    pub fn to_json(&self, duration: Duration, error: Option<&str>) -> serde_json::Value {
+        let fallbacks: Vec<serde_json::Value> = self.failed.iter()
+            .filter_map(|f| f.fallback.as_ref().map(|fallback| json!({"path": f.path, "written_as": fallback})))
+            .collect();
+        json!({
+            "ok": error.is_none(),
+            "error": error,
+            "duration_ms": duration.as_millis() as u64,
+            "companies": self.companies,
+            "written": self.written,
+            "skipped_entries": self.skipped_entries.iter().map(|(entry, reason)| json!({"entry": entry, "reason": reason})).collect::<Vec<_>>(),
+            "skipped": self.skipped.iter().map(|(path, reason)| json!({"path": path, "reason": reason})).collect::<Vec<_>>(),
+            "failed": self.failed.iter().map(|f| json!({"path": f.path, "error": f.error, "fallback": f.fallback})).collect::<Vec<_>>(),
+            "fallbacks": fallbacks,
+        })
+    }
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use crate::labels::Labels;
+use crate::{CompanyEntry, Session, links, prep};
+
+// walking to the next row of booths means going around the end of an aisle, which is roughly
+// this many booths worth of walking
+const ROW_COST: u32 = 5;
+
+// "B7" is row B booth 7, plain numbers are all treated as one row, companies with more than one
+// booth get visited at the first
+fn booth_position(booth: &str) -> Option<(u32, u32)> {
+    let first = booth.split(',').next()?.trim();
+    let row: String = first.chars().take_while(|c| c.is_ascii_alphabetic()).take(3).collect();
+    let number: u32 = first[row.len()..].trim().parse().ok()?;
+    let row = row.to_ascii_uppercase().chars().fold(0, |acc, c| acc * 26 + (c as u32 - 'A' as u32 + 1));
+    Some((row, number))
+}
+
+fn distance(a: (u32, u32), b: (u32, u32)) -> u32 {
+    a.0.abs_diff(b.0) * ROW_COST + a.1.abs_diff(b.1)
+}
+
+// starts at the most important company then keeps walking to whichever remaining company is the
+// best trade of priority against distance, companies without a booth go last in priority order
+fn visiting_order(mut stops: Vec<(&CompanyEntry, f64)>) -> Vec<&CompanyEntry> {
+    stops.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.booth.cmp(&b.0.booth)).then(a.0.name.cmp(&b.0.name)));
+
+    let (mut placed, unplaced): (Vec<_>, Vec<_>) = stops.into_iter()
+        .partition(|(company, _)| company.booth.as_deref().and_then(booth_position).is_some());
+
+    let mut order = Vec::new();
+    let mut position = None;
+    while !placed.is_empty() {
+        let next = match position {
+            None => 0,
+            Some(position) => {
+                let cost = |(company, priority): &(&CompanyEntry, f64)| {
+                    let booth = company.booth.as_deref().and_then(booth_position).unwrap_or_default();
+                    (distance(position, booth) as f64 + 1.0) / priority
+                };
+                (0..placed.len()).min_by(|a, b| cost(&placed[*a]).total_cmp(&cost(&placed[*b]))).unwrap_or(0)
+            },
+        };
+        let (company, _) = placed.remove(next);
+        position = company.booth.as_deref().and_then(booth_position);
+        order.push(company);
+    }
+
+    order.extend(unplaced.into_iter().map(|(company, _)| company));
+    order
+}
+
+// a suggested route per session through the companies the user gave a priority, ticked boxes
+// from the existing checklist stay ticked, returns None when nothing has a priority yet, with
+// everyone (--mini) the companies without one are on it too as if they had a priority of 1
+pub fn render_checklist(companies_dir: &Path, companies: &[CompanyEntry], existing: &str, labels: &Labels, everyone: bool) -> Option<String> {
+    let prioritized: Vec<(&CompanyEntry, f64)> = companies.iter()
+        .filter_map(|company| {
+            let priority = match prep::note_field(companies_dir, company, "Priority").map(|p| p.parse::<f64>()) {
+                Some(Ok(priority)) => priority,
+                _ if everyone => 1.0,
+                _ => return None,
+            };
+            (priority > 0.0).then_some((company, priority))
+        })
+        .collect();
+
+    let mut sessions: Vec<(&Session, Vec<(&CompanyEntry, f64)>)> = Vec::new();
+    for (company, priority) in &prioritized {
+        for session in &company.attending_sessions {
+            match sessions.iter_mut().find(|(s, _)| s.id == session.id && s.display_name == session.display_name) {
+                Some((_, stops)) => stops.push((company, *priority)),
+                None => sessions.push((session, vec![(company, *priority)])),
+            }
+        }
+    }
+
+    if sessions.is_empty() {
+        return None;
+    }
+
+    sessions.sort_by(|a, b| a.0.start.cmp(&b.0.start).then(a.0.display_name.cmp(&b.0.display_name)));
+
+    let checked: Vec<&str> = existing.lines()
+        .filter_map(|l| l.strip_prefix("- [x] [["))
+        .filter_map(|l| l.split("]]").next())
+        .collect();
+
+    let mut text = format!("# {}\n", labels.checklist);
+    for (session, stops) in sessions {
+        text.push_str(&format!("\n## {}\n\n", session.display_name));
+        for company in visiting_order(stops) {
+            let mark = if checked.contains(&company.name.as_str()) { "x" } else { " " };
+            match &company.booth {
+                Some(booth) => text.push_str(&format!("- [{}] {} ({} {})\n", mark, links::company_link(company), labels.booth, booth)),
+                None => text.push_str(&format!("- [{}] {}\n", mark, links::company_link(company))),
+            }
+        }
+    }
+
+    Some(text)
+}
@@ -0,0 +1,106 @@
+use crate::labels::Labels;
+use crate::{CompanyEntry, Error, Session, links};
+
+// only some fairs publish registration numbers and the names aren't consistent between them
+const REGISTERED_KEYS: [&str; 3] = ["registered", "registrations_count", "registered_count"];
+const CAPACITY_KEYS: [&str; 3] = ["capacity", "max_registrations", "registration_limit"];
+const WAITLISTED_KEYS: [&str; 3] = ["waitlisted", "waitlist_count", "waitlisted_count"];
+
+pub fn registered(session_entry: &serde_json::Value) -> Option<u64> {
+    REGISTERED_KEYS.iter().find_map(|key| session_entry[key].as_u64())
+}
+
+pub fn capacity(session_entry: &serde_json::Value) -> Option<u64> {
+    CAPACITY_KEYS.iter().find_map(|key| session_entry[key].as_u64())
+}
+
+pub fn waitlisted(session_entry: &serde_json::Value) -> Option<u64> {
+    WAITLISTED_KEYS.iter().find_map(|key| session_entry[key].as_u64())
+}
+
+// a session with a waitlist is full even if the registered count hasn't caught up yet
+pub fn is_full(session: &Session) -> bool {
+    match (session.registered, session.capacity, session.waitlisted) {
+        (_, _, Some(waitlisted)) if waitlisted > 0 => true,
+        (Some(registered), Some(capacity), _) => registered >= capacity,
+        _ => false,
+    }
+}
+
+// one section per fair session with the companies attending it, returns None when the
+// payload has no sessions at all
+pub fn render_schedule(companies: &[CompanyEntry], labels: &Labels) -> Option<String> {
+    let mut sessions: Vec<(&Session, Vec<&CompanyEntry>)> = Vec::new();
+    for company in companies {
+        for session in &company.attending_sessions {
+            match sessions.iter_mut().find(|(s, _)| s.id == session.id && s.display_name == session.display_name) {
+                Some((_, attending)) => attending.push(company),
+                None => sessions.push((session, vec![company])),
+            }
+        }
+    }
+
+    if sessions.is_empty() {
+        return None;
+    }
+
+    sessions.sort_by(|a, b| a.0.start.cmp(&b.0.start).then(a.0.display_name.cmp(&b.0.display_name)));
+
+    let mut text = format!("# {}\n", labels.schedule);
+    for (session, mut attending) in sessions {
+        match is_full(session) {
+            true => text.push_str(&format!("\n## {} ⚠️ {}\n\n", session.display_name, labels.session_full)),
+            false => text.push_str(&format!("\n## {}\n\n", session.display_name)),
+        }
+
+        let counts: Vec<String> = [
+            (&labels.registered, session.registered),
+            (&labels.capacity, session.capacity),
+            (&labels.waitlisted, session.waitlisted),
+        ].iter()
+            .filter_map(|(label, count)| count.map(|n| format!("{}: {}", label, n)))
+            .collect();
+        if !counts.is_empty() {
+            text.push_str(&format!("{}\n\n", counts.join(" · ")));
+        }
+
+        attending.sort_by(|a, b| a.name.cmp(&b.name));
+        for company in attending {
+            match &company.summary {
+                Some(summary) => text.push_str(&format!("- {}: {}\n", links::company_link(company), summary)),
+                None => text.push_str(&format!("- {}\n", links::company_link(company))),
+            }
+        }
+    }
+
+    Some(text)
+}
+
+// --session, only the companies at that session and only that session on their notes, matched by
+// its whole name ignoring case and otherwise by part of it as long as that's just one session
+pub fn keep_session(companies: &mut Vec<CompanyEntry>, name: &str) -> Result<String, Error> {
+    let mut names: Vec<&str> = Vec::new();
+    for session in companies.iter().flat_map(|c| &c.attending_sessions) {
+        if !names.contains(&session.display_name.as_str()) {
+            names.push(&session.display_name);
+        }
+    }
+
+    let wanted = name.to_lowercase();
+    let matched: Vec<&str> = match names.iter().find(|n| n.to_lowercase() == wanted) {
+        Some(exact) => vec![exact],
+        None => names.iter().filter(|n| n.to_lowercase().contains(&wanted)).copied().collect(),
+    };
+    let session = match matched.as_slice() {
+        [session] => session.to_string(),
+        [] if names.is_empty() => return Err(Error::Usage(format!("no session matches {}, the input has no sessions", name))),
+        [] => return Err(Error::Usage(format!("no session matches {}, expected one of: {}", name, names.join("; ")))),
+        _ => return Err(Error::Usage(format!("{} matches more than one session: {}", name, matched.join("; ")))),
+    };
+
+    companies.retain(|c| c.attending_sessions.iter().any(|s| s.display_name == session));
+    for company in companies.iter_mut() {
+        company.attending_sessions.retain(|s| s.display_name == session);
+    }
+    Ok(session)
+}
@@ -0,0 +1,69 @@
+// the keys profile links have shown up under on employer objects, handshake only fills them in
+// for some employers and not always with a full url
+const PROFILES: &[(&str, &[&str])] = &[
+    ("LinkedIn", &["linkedin_url", "linkedin", "linkedin_profile_url"]),
+    ("Twitter", &["twitter_url", "twitter", "twitter_handle", "x_url"]),
+    ("Facebook", &["facebook_url", "facebook"]),
+    ("Instagram", &["instagram_url", "instagram"]),
+    ("YouTube", &["youtube_url", "youtube"]),
+    ("Glassdoor", &["glassdoor_url"]),
+    ("Careers", &["careers_url", "careers_page_url", "career_site_url", "jobs_url"]),
+];
+
+// so anonymize can scrub them along with the website
+pub const URL_KEYS: &[&str] = &[
+    "linkedin_url", "linkedin", "linkedin_profile_url", "twitter_url", "twitter", "twitter_handle", "x_url",
+    "facebook_url", "facebook", "instagram_url", "instagram", "youtube_url", "youtube", "glassdoor_url",
+    "careers_url", "careers_page_url", "career_site_url", "jobs_url",
+];
+
+// a bare twitter handle is the only thing that isn't a url worth keeping
+fn profile_url(label: &str, value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.starts_with("https://") || value.starts_with("http://") {
+        return Some(value.to_string());
+    }
+    match (label, value.strip_prefix('@').unwrap_or(value)) {
+        ("Twitter", handle) if !handle.is_empty() && handle.chars().all(|c| c.is_ascii_alphanumeric() || c == '_') => {
+            Some(format!("https://twitter.com/{}", handle))
+        },
+        _ => None,
+    }
+}
+
+// (label, url) for every profile the employer has, a careers page that's just the website is left out
+pub fn find_links(employer: &serde_json::Value, website: &str) -> Vec<(String, String)> {
+    let mut links: Vec<(String, String)> = Vec::new();
+
+    for (label, keys) in PROFILES {
+        let url = keys.iter()
+            .filter_map(|key| employer[key].as_str())
+            .find_map(|value| profile_url(label, value));
+        if let Some(url) = url {
+            links.push((label.to_string(), url));
+        }
+    }
+
+    // some payloads group them instead, as [{"type": "linkedin", "url": ..}]
+    for key in ["social_links", "social_profiles"] {
+        for profile in employer[key].as_array().into_iter().flatten() {
+            let Some(url) = profile["url"].as_str().filter(|u| u.starts_with("http")) else { continue };
+            let kind = profile["type"].as_str().or(profile["name"].as_str()).unwrap_or("Link");
+            let label = PROFILES.iter()
+                .map(|(label, _)| *label)
+                .find(|label| label.eq_ignore_ascii_case(kind))
+                .unwrap_or(kind);
+            links.push((label.to_string(), url.to_string()));
+        }
+    }
+
+    let website = website.trim_end_matches('/');
+    let mut seen: Vec<String> = Vec::new();
+    links.retain(|(_, url)| {
+        let url = url.trim_end_matches('/').to_string();
+        let keep = url != website && !seen.contains(&url);
+        seen.push(url);
+        keep
+    });
+    links
+}
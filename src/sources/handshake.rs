@@ -0,0 +1,236 @@
+use serde::Deserialize;
+
+use crate::policy::{Defaults, Loose, Policy};
+use crate::{CompanyEntry, Error, Session, cards, contacts, deadlines, industry, jobs, provenance, schedule, social};
+use super::FairSource;
+
+// the parts of the api's response the notes are built from, anything can be missing, null or the wrong
+// type in an older or hand edited export, which is up to --strict and --lenient rather than serde,
+// the fields handshake has moved around over the years like deadlines, booths and recruiters are
+// looked for in the entry's json by their own modules
+#[derive(Deserialize)]
+struct Export {
+    results: Vec<serde_json::Value>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Entry {
+    employer: Loose<Employer>,
+    company_description: Loose<String>,
+    location_name: Loose<String>,
+    work_authorization_requirements: Loose<String>,
+    job_titles: Loose<String>,
+    job_types: Loose<Vec<Loose<Named>>>,
+    majors: Loose<Vec<Loose<Named>>>,
+    school_years: Loose<Vec<Loose<Named>>>,
+    // kept as json for the registration counts, see schedule
+    attending_career_fair_sessions: Loose<Vec<serde_json::Value>>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Employer {
+    id: Loose<u64>,
+    name: Loose<String>,
+    website: Loose<String>,
+    logo_url: Loose<String>,
+}
+
+// a job type, major or school year
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct Named {
+    name: Loose<String>,
+}
+
+#[derive(Deserialize, Default)]
+#[serde(default)]
+struct SessionEntry {
+    id: Loose<u64>,
+    display_name: Loose<String>,
+    start_date_time: Loose<String>,
+    end_date_time: Loose<String>,
+}
+
+// a career fair's registrations, the export the renderer was written for
+pub struct Handshake;
+
+impl FairSource for Handshake {
+    fn name(&self) -> &'static str {
+        "handshake"
+    }
+
+    fn detect(&self, input_data: &[u8]) -> bool {
+        serde_json::from_slice::<serde_json::Value>(input_data).is_ok_and(|data| data["results"].is_array())
+    }
+
+    fn parse(&self, input_data: &[u8], policy: Policy) -> Result<Vec<CompanyEntry>, Error> {
+        parse_companies(input_data, policy)
+    }
+}
+
+// a job search, one company per employer with its postings as job notes
+pub struct HandshakeJobs;
+
+impl FairSource for HandshakeJobs {
+    fn name(&self) -> &'static str {
+        "handshake-jobs"
+    }
+
+    fn detect(&self, input_data: &[u8]) -> bool {
+        jobs::parse_jobs(input_data).is_some()
+    }
+
+    fn parse(&self, input_data: &[u8], _policy: Policy) -> Result<Vec<CompanyEntry>, Error> {
+        match jobs::parse_jobs(input_data) {
+            Some(jobs) => Ok(jobs::group_by_employer(&jobs)),
+            None => Err(Error::InvalidInput("input data isn't a handshake job search".to_string())),
+        }
+    }
+
+    fn jobs(&self, input_data: &[u8]) -> Option<Vec<jobs::Job>> {
+        jobs::parse_jobs(input_data)
+    }
+}
+
+pub fn parse_companies(input_data: &[u8], policy: Policy) -> Result<Vec<CompanyEntry>, Error> {
+    if input_data.iter().all(u8::is_ascii_whitespace) {
+        return Err(Error::InvalidInput("input file is empty".to_string()));
+    }
+
+    let json_data: serde_json::Value = match serde_json::from_slice(input_data) {
+        Ok(data) => data,
+        Err(source) => return Err(Error::Json { what: "input data".to_string(), source }),
+    };
+
+    let export = match Export::deserialize(&json_data) {
+        Ok(export) => export,
+        Err(e) => return Err(Error::InvalidInput(format!("input data is an invalid format: {}", e))),
+    };
+
+    // missing or odd values become empty ones unless --strict
+    let mut defaults = Defaults::new(policy);
+
+    let mut companies = Vec::new();
+
+    for (i, json_entry) in export.results.iter().enumerate() {
+        // an entry that isn't even an object has no name either
+        let entry = Loose::<Entry>::from_value(json_entry).into_option().unwrap_or_default();
+        let employer = entry.employer.into_option().unwrap_or_default();
+        defaults.entry(i, None);
+        let Some(name) = employer.name.into_option() else {
+            defaults.unusable("name", "employer.name")?;
+            continue;
+        };
+        defaults.entry(i, Some(&name));
+        let provenance_of = [
+            ("location", "location_name", entry.location_name.is_present()),
+            ("majors", "majors[].name", entry.majors.is_present()),
+            ("job_titles", "job_titles", entry.job_titles.is_present()),
+            ("job_types", "job_types[].name", entry.job_types.is_present()),
+            ("school_years", "school_years[].name", entry.school_years.is_present()),
+            ("international", "work_authorization_requirements", entry.work_authorization_requirements.is_present()),
+            ("sessions", "attending_career_fair_sessions[].display_name", entry.attending_career_fair_sessions.is_present()),
+            ("website", "employer.website", employer.website.is_present()),
+        ];
+
+        let description = defaults.take(entry.company_description, "description", "company_description")?;
+        let location = defaults.take(entry.location_name, "location", "location_name")?;
+        let website = defaults.take(employer.website, "website", "employer.website")?;
+        let logo_url = defaults.take(employer.logo_url, "logo_url", "employer.logo_url")?;
+        let employer_id = employer.id.into_option();
+        let work_authorization = defaults.take(entry.work_authorization_requirements, "work_auth", "work_authorization_requirements")?;
+        let job_titles = defaults.take(entry.job_titles, "job_titles", "job_titles")?;
+
+        let job_types = names(&mut defaults, entry.job_types, "job_types")?;
+        let majors = names(&mut defaults, entry.majors, "majors")?;
+        let school_years = names(&mut defaults, entry.school_years, "school_years")?;
+
+        let mut attending_sessions = Vec::new();
+        let sessions = defaults.take(entry.attending_career_fair_sessions, "sessions", "attending_career_fair_sessions")?;
+        for (j, json_session) in sessions.iter().enumerate() {
+            let session = Loose::<SessionEntry>::from_value(json_session).into_option().unwrap_or_default();
+            let Some(display_name) = session.display_name.into_option() else {
+                defaults.missing("session", &format!("attending_career_fair_sessions[{}].display_name", j))?;
+                continue;
+            };
+            attending_sessions.push(Session {
+                id: session.id.into_option(),
+                note: display_name.clone(),
+                display_name,
+                start: session.start_date_time.into_option(),
+                end: session.end_date_time.into_option(),
+                registered: schedule::registered(json_session),
+                capacity: schedule::capacity(json_session),
+                waitlisted: schedule::waitlisted(json_session),
+            });
+        }
+
+        let deadline = deadlines::find_deadline_key(json_entry);
+        let links = social::find_links(&json_entry["employer"], &website);
+        let mut sources: Vec<_> = provenance_of.iter()
+            .map(|(field, path, found)| (*field, provenance::fair_source(i, path, *found)))
+            .collect();
+        if !links.is_empty() {
+            sources.push(("links", provenance::fair_source(i, "employer profile urls", true)));
+        }
+        if employer_id.is_some() {
+            sources.push(("employer_id", provenance::fair_source(i, "employer.id", true)));
+        }
+        if let Some((key, _)) = deadline {
+            sources.push(("deadline", provenance::fair_source(i, key, true)));
+        }
+        let contacts = contacts::find_contacts(json_entry);
+        if !contacts.is_empty() {
+            sources.push(("contacts", provenance::fair_source(i, "recruiters, representatives or contacts", true)));
+        }
+        let industry = industry::payload_industry(&json_entry["employer"]);
+        if industry.is_some() {
+            sources.push(("industry", provenance::fair_source(i, "employer.industry", true)));
+        }
+
+        companies.push(CompanyEntry {
+            note: name.clone(),
+            name,
+            description,
+            location,
+            website,
+            logo_url,
+            work_authorization,
+            job_titles,
+            job_types,
+            majors,
+            school_years,
+            attending_sessions,
+            employer_id,
+            booth: cards::find_booth(json_entry),
+            deadline: deadline.map(|(_, deadline)| deadline),
+            description_language: None,
+            translated_description: None,
+            links,
+            positions: jobs::nested_positions(json_entry),
+            industry,
+            contacts,
+            summary: None,
+            overflow_note: None,
+            sources,
+        });
+    }
+
+    defaults.report();
+    Ok(companies)
+}
+
+// the names out of a list of {"name": ..} objects, one without a name is counted as missing, field is
+// also the list's key
+fn names(defaults: &mut Defaults, list: Loose<Vec<Loose<Named>>>, field: &'static str) -> Result<Vec<String>, Error> {
+    let mut names = Vec::new();
+    for (j, entry) in defaults.take(list, field, field)?.into_iter().enumerate() {
+        match entry.into_option().and_then(|entry| entry.name.into_option()) {
+            Some(name) => names.push(name),
+            None => defaults.missing(field, &format!("{}[{}].name", field, j))?,
+        }
+    }
+    Ok(names)
+}
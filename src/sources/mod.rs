@@ -0,0 +1,47 @@
+use crate::policy::Policy;
+use crate::{CompanyEntry, Error, jobs};
+
+mod handshake;
+
+pub use handshake::parse_companies;
+
+// where the input came from, a new career fair platform is a module with a FairSource and an entry
+// in SOURCES, everything after parsing only ever sees the companies it returns
+pub trait FairSource: Sync {
+    fn name(&self) -> &'static str;
+
+    // whether the input looks like this platform's export, asked in SOURCES order when there's no --format
+    fn detect(&self, input_data: &[u8]) -> bool;
+
+    fn parse(&self, input_data: &[u8], policy: Policy) -> Result<Vec<CompanyEntry>, Error>;
+
+    // sources with postings, like a job search, also get a note per job
+    fn jobs(&self, _input_data: &[u8]) -> Option<Vec<jobs::Job>> {
+        None
+    }
+}
+
+// the more specific ones first, a job search also has a results array
+pub const SOURCES: &[&dyn FairSource] = &[&handshake::HandshakeJobs, &handshake::Handshake];
+
+pub fn find_source(name: &str) -> Result<&'static dyn FairSource, Error> {
+    match SOURCES.iter().find(|s| s.name() == name) {
+        Some(source) => Ok(*source),
+        None => {
+            let names: Vec<&str> = SOURCES.iter().map(|s| s.name()).collect();
+            Err(Error::Usage(format!("unknown format: {}, expected one of {}", name, names.join(", "))))
+        },
+    }
+}
+
+// input nothing recognizes goes to handshake, whose errors say what's wrong with it
+pub fn detect(input_data: &[u8]) -> &'static dyn FairSource {
+    SOURCES.iter().find(|s| s.detect(input_data)).copied().unwrap_or(&handshake::Handshake)
+}
+
+pub fn pick_source(format: Option<&str>, input_data: &[u8]) -> Result<&'static dyn FairSource, Error> {
+    match format {
+        Some(name) => find_source(name),
+        None => Ok(detect(input_data)),
+    }
+}
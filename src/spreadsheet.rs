@@ -0,0 +1,211 @@
+use std::path::Path;
+
+#[cfg(feature = "xlsx")]
+use calamine::{Data, Reader, open_workbook_auto};
+
+use crate::Error;
+
+// the fields a spreadsheet column can fill in, with the headers career offices tend to use for them,
+// a header matches when it's one of these ignoring case, spaces and punctuation
+const FIELDS: &[(&str, &[&str])] = &[
+    ("name", &["name", "company", "companyname", "employer", "employername", "organization"]),
+    ("location", &["location", "city", "headquarters"]),
+    ("website", &["website", "url", "companywebsite"]),
+    ("description", &["description", "companydescription", "about"]),
+    ("majors", &["majors", "major", "majorsrecruited", "disciplines"]),
+    ("job_titles", &["jobtitles", "positions", "positiontitles", "roles"]),
+    ("job_types", &["jobtypes", "positiontypes", "opportunitytypes"]),
+    ("school_years", &["schoolyears", "classlevels", "classstanding", "years"]),
+    ("work_authorization", &["workauthorization", "sponsorship", "visasponsorship", "international"]),
+    ("sessions", &["sessions", "days", "attending"]),
+    ("booth", &["booth", "table", "booths"]),
+    ("deadline", &["deadline", "applyby", "applicationdeadline"]),
+    ("logo_url", &["logo", "logourl"]),
+];
+
+pub fn is_spreadsheet(path: &str) -> bool {
+    let lower = path.to_ascii_lowercase();
+    [".xlsx", ".xlsm", ".xls", ".ods"].iter().any(|ext| lower.ends_with(ext))
+}
+
+fn normalize(header: &str) -> String {
+    header.chars().filter(|c| c.is_alphanumeric()).flat_map(char::to_lowercase).collect()
+}
+
+// specs look like name=Organization Name,majors=Disciplines, later ones win, anything not given
+// is matched by its header
+pub fn parse_columns(specs: &[String]) -> Result<Vec<(String, String)>, Error> {
+    let mut columns: Vec<(String, String)> = Vec::new();
+    for spec in specs.iter().flat_map(|s| s.split(',')) {
+        let Some((field, header)) = spec.split_once('=') else {
+            return Err(Error::Usage(format!("column should look like field=header, got: {}", spec)));
+        };
+        let field = field.trim();
+        if !FIELDS.iter().any(|(f, _)| *f == field) {
+            let fields: Vec<&str> = FIELDS.iter().map(|(f, _)| *f).collect();
+            return Err(Error::Usage(format!("unknown column field {}, expected one of {}", field, fields.join(", "))));
+        }
+        columns.retain(|(f, _)| f != field);
+        columns.push((field.to_string(), header.trim().to_string()));
+    }
+    Ok(columns)
+}
+
+#[cfg(feature = "xlsx")]
+fn cell_text(cell: &Data) -> String {
+    match cell {
+        Data::Empty => String::new(),
+        // dates come through as excel serial numbers otherwise
+        Data::DateTime(date) => serial_date(date.as_f64()),
+        Data::Float(f) if f.fract() == 0.0 => format!("{}", *f as i64),
+        cell => cell.to_string().trim().to_string(),
+    }
+}
+
+// excel counts days from 1899-12-30, turned into YYYY-MM-DD the way the rest of the render writes dates
+#[cfg(feature = "xlsx")]
+fn serial_date(serial: f64) -> String {
+    // days since 0000-03-01, then the usual civil from days conversion
+    let days = serial.floor() as i64 - 25569 + 719468;
+    let era = days.div_euclid(146097);
+    let day_of_era = days.rem_euclid(146097);
+    let year_of_era = (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 { month_index + 3 } else { month_index - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+    format!("{:04}-{:02}-{:02}", year, month, day)
+}
+
+fn list(text: &str) -> Vec<serde_json::Value> {
+    text.split([',', ';', '\n'])
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(|v| serde_json::json!({ "name": v, "display_name": v }))
+        .collect()
+}
+
+// the first sheet becomes a payload shaped like a handshake export so the rest of the render
+// doesn't have to know where it came from, columns that aren't there are left out of it
+#[cfg(feature = "xlsx")]
+pub fn read_spreadsheet(path: &Path, columns: &[(String, String)]) -> Result<Vec<u8>, Error> {
+    let mut workbook = match open_workbook_auto(path) {
+        Ok(workbook) => workbook,
+        Err(e) => return Err(Error::InvalidInput(format!("could not read spreadsheet {}: {}", path.to_string_lossy(), e))),
+    };
+    let range = match workbook.worksheet_range_at(0) {
+        Some(Ok(range)) => range,
+        Some(Err(e)) => return Err(Error::InvalidInput(format!("could not read spreadsheet {}: {}", path.to_string_lossy(), e))),
+        None => return Err(Error::InvalidInput(format!("spreadsheet {} has no sheets", path.to_string_lossy()))),
+    };
+
+    let rows: Vec<Vec<String>> = range.rows().map(|row| row.iter().map(cell_text).collect()).collect();
+    payload(rows, columns)
+}
+
+#[cfg(not(feature = "xlsx"))]
+pub fn read_spreadsheet(path: &Path, _columns: &[(String, String)]) -> Result<Vec<u8>, Error> {
+    Err(crate::missing_feature(&format!("reading {}", path.to_string_lossy()), "xlsx"))
+}
+
+// the delimiter is whichever of , ; and tab the header line has most of, since exports from
+// spreadsheets in some locales use ;
+fn delimiter(header: &str) -> char {
+    [',', ';', '\t'].into_iter().max_by_key(|d| header.matches(*d).count()).unwrap_or(',')
+}
+
+// quoted fields can hold the delimiter, line breaks and "" for a quote
+fn parse_csv(text: &str) -> Vec<Vec<String>> {
+    let delimiter = delimiter(text.lines().next().unwrap_or_default());
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut quoted = false;
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' if quoted && chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            },
+            '"' if quoted => quoted = false,
+            '"' if field.is_empty() => quoted = true,
+            c if quoted => field.push(c),
+            c if c == delimiter => row.push(std::mem::take(&mut field).trim().to_string()),
+            '\r' => {},
+            '\n' => {
+                row.push(std::mem::take(&mut field).trim().to_string());
+                rows.push(std::mem::take(&mut row));
+            },
+            c => field.push(c),
+        }
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field.trim().to_string());
+        rows.push(row);
+    }
+    rows.retain(|row| row.iter().any(|cell| !cell.is_empty()));
+    rows
+}
+
+// a csv goes the same way as a sheet, the first line has the headers
+pub fn read_csv(input_data: &[u8], columns: &[(String, String)]) -> Result<Vec<u8>, Error> {
+    let text = String::from_utf8_lossy(input_data);
+    payload(parse_csv(text.trim_start_matches('\u{feff}')), columns)
+}
+
+fn payload(rows: Vec<Vec<String>>, columns: &[(String, String)]) -> Result<Vec<u8>, Error> {
+    let mut rows = rows.into_iter();
+    let Some(headers) = rows.next() else {
+        return Ok(serde_json::to_vec(&serde_json::json!({ "results": [] })).unwrap_or_default());
+    };
+
+    let mut found: Vec<(&str, usize)> = Vec::new();
+    for (field, aliases) in FIELDS {
+        let index = match columns.iter().find(|(f, _)| f == field) {
+            Some((_, header)) => match headers.iter().position(|h| normalize(h) == normalize(header)) {
+                Some(index) => index,
+                None => return Err(Error::InvalidInput(format!("spreadsheet has no {} column for {}", header, field))),
+            },
+            None => match headers.iter().position(|h| aliases.contains(&normalize(h).as_str())) {
+                Some(index) => index,
+                None => continue,
+            },
+        };
+        found.push((field, index));
+    }
+    if !found.iter().any(|(field, _)| *field == "name") {
+        return Err(Error::InvalidInput("spreadsheet has no company name column, pass --column name=[header]".to_string()));
+    }
+
+    let mut results = Vec::new();
+    for row in rows {
+        let cell = |field: &str| {
+            found.iter().find(|(f, _)| *f == field).map(|(_, i)| row.get(*i).cloned().unwrap_or_default())
+        };
+        if cell("name").is_none_or(|name| name.is_empty()) {
+            continue;
+        }
+
+        let mut entry = serde_json::json!({ "employer": {} });
+        for (field, _) in &found {
+            let Some(text) = cell(field) else { continue };
+            match *field {
+                "name" | "website" | "logo_url" => entry["employer"][*field] = text.into(),
+                "location" => entry["location_name"] = text.into(),
+                "description" => entry["company_description"] = text.into(),
+                "job_titles" => entry["job_titles"] = text.into(),
+                "work_authorization" => entry["work_authorization_requirements"] = text.into(),
+                "majors" | "job_types" | "school_years" => entry[*field] = list(&text).into(),
+                "sessions" => entry["attending_career_fair_sessions"] = list(&text).into(),
+                "booth" => entry["assigned_booth_identifiers"] = text.split([',', ';']).map(str::trim).filter(|b| !b.is_empty()).collect(),
+                "deadline" => entry["apply_by"] = text.into(),
+                _ => {},
+            }
+        }
+        results.push(entry);
+    }
+
+    Ok(serde_json::to_vec(&serde_json::json!({ "results": results })).unwrap_or_default())
+}
@@ -0,0 +1,120 @@
+use std::fs;
+
+use crate::labels::Labels;
+use crate::{CompanyEntry, Error, links};
+
+// a company starred in handshake, by employer id when the list has one since names change
+struct Star {
+    id: Option<u64>,
+    name: String,
+}
+
+// --starred, the employers starred in handshake as its favorites json or a plain list of names,
+// a line each with # for comments
+pub struct Starred {
+    pub path: String,
+    stars: Vec<Star>,
+}
+
+fn json_star(value: &serde_json::Value) -> Option<Star> {
+    if let Some(name) = value.as_str() {
+        return Some(Star { id: None, name: name.trim().to_string() });
+    }
+    let employer = match value["employer"].is_object() {
+        true => &value["employer"],
+        false => value,
+    };
+    let id = employer["id"].as_u64().or(value["employer_id"].as_u64());
+    let name = employer["name"].as_str().or(value["employer_name"].as_str()).unwrap_or_default().trim().to_string();
+    match id.is_none() && name.is_empty() {
+        true => None,
+        false => Some(Star { id, name }),
+    }
+}
+
+// a favorites export has its employers under results like the fair does, a hand written one can
+// be a bare array of names or objects
+fn json_stars(json_data: &serde_json::Value) -> Option<Vec<Star>> {
+    let entries = json_data.as_array()
+        .or(json_data["results"].as_array())
+        .or(json_data["favorites"].as_array())
+        .or(json_data["employers"].as_array())?;
+    Some(entries.iter().filter_map(json_star).collect())
+}
+
+impl Starred {
+    pub fn load(path: &str) -> Result<Starred, Error> {
+        let text = match fs::read_to_string(path) {
+            Ok(text) => text,
+            Err(e) => return Err(Error::Usage(format!("could not read starred list {}: {}", path, e))),
+        };
+        let stars = match serde_json::from_str::<serde_json::Value>(&text) {
+            Ok(json_data) => match json_stars(&json_data) {
+                Some(stars) => stars,
+                None => return Err(Error::Usage(format!("{} has no list of employers, expected an array or one under results", path))),
+            },
+            Err(_) => text.lines()
+                .map(str::trim)
+                .filter(|line| !line.is_empty() && !line.starts_with('#'))
+                .map(|line| Star { id: None, name: line.to_string() })
+                .collect(),
+        };
+        Ok(Starred { path: path.to_string(), stars })
+    }
+
+    fn matches(star: &Star, company: &CompanyEntry) -> bool {
+        match (star.id, company.employer_id) {
+            (Some(a), Some(b)) => a == b,
+            _ => star.name.eq_ignore_ascii_case(&company.name),
+        }
+    }
+
+    pub fn is_starred(&self, company: &CompanyEntry) -> bool {
+        self.stars.iter().any(|star| Starred::matches(star, company))
+    }
+
+    // stars for employers that aren't at this fair, worth knowing about but nothing to render
+    pub fn missing(&self, companies: &[CompanyEntry]) -> Vec<String> {
+        self.stars.iter()
+            .filter(|star| !companies.iter().any(|c| Starred::matches(star, c)))
+            .map(|star| match (star.name.is_empty(), star.id) {
+                (true, Some(id)) => format!("employer {}", id),
+                _ => star.name.clone(),
+            })
+            .collect()
+    }
+
+    // every company gets the field so one unstarred in handshake loses it on the next refresh
+    pub fn star_frontmatter(&self, company: &CompanyEntry, generated: &mut Vec<(String, String)>, key_prefix: &str) {
+        generated.push((format!("{}starred", key_prefix), self.is_starred(company).to_string()));
+    }
+
+    // the starred companies that are at the fair with where and when to find them, then the ones that aren't
+    pub fn render_starred(&self, companies: &[CompanyEntry], labels: &Labels) -> String {
+        let starred: Vec<&CompanyEntry> = companies.iter().filter(|c| self.is_starred(c)).collect();
+        let mut text = format!("# {} ({})\n\n", labels.starred, starred.len());
+        for company in starred {
+            let mut line = format!("- {}", links::company_link(company));
+            if let Some(booth) = &company.booth {
+                line.push_str(&format!(", {} {}", labels.booth, booth));
+            }
+            let sessions: Vec<&str> = company.attending_sessions.iter().map(|s| s.display_name.as_str()).collect();
+            if !sessions.is_empty() {
+                line.push_str(&format!(", {}", sessions.join("; ")));
+            }
+            if let Some(summary) = &company.summary {
+                line.push_str(&format!(": {}", summary));
+            }
+            text.push_str(&format!("{}\n", line));
+        }
+
+        let missing = self.missing(companies);
+        if !missing.is_empty() {
+            text.push_str(&format!("\n## {} ({})\n\n", labels.starred_missing, missing.len()));
+            for name in missing {
+                text.push_str(&format!("- {}\n", name));
+            }
+        }
+        text
+    }
+}
@@ -0,0 +1,59 @@
+use std::fs::{self, File, OpenOptions, TryLockError};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{Error, term};
+
+fn lock_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(".fair_renderer/lock")
+}
+
+// written next to the file and renamed over it, a reader or a run that gets killed midway sees the old
+// file or the new one but never half of one
+pub fn write_atomic(path: &Path, contents: impl AsRef<[u8]>) -> Result<(), Error> {
+    let name = path.file_name().unwrap_or_default().to_string_lossy();
+    let temp_path = path.with_file_name(format!(".{}.{}.tmp", name, std::process::id()));
+    let written = File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(contents.as_ref())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if let Err(source) = written {
+        let _ = fs::remove_file(&temp_path);
+        return Err(Error::Output { path: path.to_string_lossy().to_string(), source });
+    }
+    Ok(())
+}
+
+// held while a run writes into a vault, a second run on it, like a cron job while an --interval one is
+// going, waits for the first to finish instead of both writing the manifest and caches at once,
+// the os lets go of it when the process dies so a killed run never leaves a vault locked
+pub struct VaultLock {
+    _file: File,
+}
+
+impl VaultLock {
+    pub fn acquire(vault_path: &Path) -> Result<Option<VaultLock>, Error> {
+        let path = lock_path(vault_path);
+        let failed = |source| Error::Output { path: path.to_string_lossy().to_string(), source };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(failed)?;
+        }
+        let file = OpenOptions::new().create(true).truncate(false).write(true).open(&path).map_err(failed)?;
+        match file.try_lock() {
+            Ok(()) => {},
+            Err(TryLockError::WouldBlock) => {
+                term::info(&format!("waiting for another fair_renderer run to finish with {}", vault_path.to_string_lossy()));
+                file.lock()?;
+            },
+            // some network filesystems can't lock, the run goes ahead like it did before there was a lock
+            Err(TryLockError::Error(e)) if e.kind() == ErrorKind::Unsupported => {
+                term::debug(&format!("can't lock {}, {}", path.to_string_lossy(), e));
+                return Ok(None);
+            },
+            Err(TryLockError::Error(e)) => return Err(Error::Other(format!("failed locking {}: {}", path.to_string_lossy(), e))),
+        }
+        Ok(Some(VaultLock { _file: file }))
+    }
+}
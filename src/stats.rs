@@ -0,0 +1,43 @@
+use crate::CompanyEntry;
+
+// long lists like majors only show their top ones
+const SHOWN_PER_LIST: usize = 10;
+
+// how many companies have each value, most first and by name for ties
+fn counts<'a>(values: impl Iterator<Item = &'a str>) -> Vec<(&'a str, usize)> {
+    let mut counts: Vec<(&str, usize)> = Vec::new();
+    for value in values.filter(|v| !v.is_empty()) {
+        match counts.iter_mut().find(|(v, _)| *v == value) {
+            Some((_, count)) => *count += 1,
+            None => counts.push((value, 1)),
+        }
+    }
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    counts
+}
+
+fn section(text: &mut String, title: &str, counts: &[(&str, usize)]) {
+    if counts.is_empty() {
+        return;
+    }
+    text.push_str(&format!("\n{}:\n", title));
+    for (value, count) in counts.iter().take(SHOWN_PER_LIST) {
+        text.push_str(&format!("  {:>4}  {}\n", count, value));
+    }
+    if counts.len() > SHOWN_PER_LIST {
+        text.push_str(&format!("  and {} more\n", counts.len() - SHOWN_PER_LIST));
+    }
+}
+
+// a quick look at an input before rendering it, for deciding on --session or --my-majors
+pub fn render_stats(companies: &[CompanyEntry]) -> String {
+    let mut text = format!("{} companies\n", companies.len());
+    text.push_str(&format!("{} with a booth\n", companies.iter().filter(|c| c.booth.is_some()).count()));
+    text.push_str(&format!("{} with a deadline\n", companies.iter().filter(|c| c.deadline.is_some()).count()));
+
+    section(&mut text, "sessions", &counts(companies.iter().flat_map(|c| c.attending_sessions.iter().map(|s| s.display_name.as_str()))));
+    section(&mut text, "industries", &counts(companies.iter().filter_map(|c| c.industry.as_deref())));
+    section(&mut text, "work authorization", &counts(companies.iter().map(|c| c.work_authorization.as_str())));
+    section(&mut text, "majors", &counts(companies.iter().flat_map(|c| c.majors.iter().map(String::as_str))));
+    text
+}
@@ -0,0 +1,103 @@
+use std::path::Path;
+
+use crate::naming::Naming;
+use crate::sources::parse_companies;
+use crate::{CompanyEntry, Error, policy, prep};
+
+// fields a filter can match on without a vault, anything else is read from the company's note
+const FIELDS: &[&str] = &[
+    "name", "location", "major", "job_type", "job_title", "school_year", "session", "booth", "international", "industry",
+];
+
+pub struct Filter {
+    field: String,
+    value: String,
+    // field>=value, only makes sense for numbers like Priority
+    at_least: bool,
+}
+
+// specs look like major=Computer Science or Priority>=2, filters on different fields all have to
+// match and filters on the same field are alternatives, so two majors keeps companies with either
+pub fn parse_filters(specs: &[String], has_vault: bool) -> Result<Vec<Filter>, Error> {
+    let mut filters = Vec::new();
+    for spec in specs {
+        let (field, value, at_least) = match (spec.split_once(">="), spec.split_once('=')) {
+            (Some((field, value)), _) => (field, value, true),
+            (None, Some((field, value))) => (field, value, false),
+            (None, None) => return Err(Error::Other(format!("filter should look like field=value or field>=number, got: {}", spec))),
+        };
+        let field = field.trim();
+        if !FIELDS.contains(&field) && !has_vault {
+            return Err(Error::Other(format!("unknown filter field {}, expected one of {} or a note field with --vault", field, FIELDS.join(", "))));
+        }
+        if at_least && value.trim().parse::<f64>().is_err() {
+            return Err(Error::Other(format!("filter {} should compare against a number", spec)));
+        }
+        filters.push(Filter { field: field.to_string(), value: value.trim().to_string(), at_least });
+    }
+    Ok(filters)
+}
+
+fn matches(company: &CompanyEntry, filter: &Filter, companies_dir: Option<&Path>) -> bool {
+    let is = |value: &String| value.eq_ignore_ascii_case(&filter.value);
+    let contains = |value: &str| value.to_lowercase().contains(&filter.value.to_lowercase());
+    match filter.field.as_str() {
+        "name" => contains(&company.name),
+        "location" => contains(&company.location),
+        "job_title" => contains(&company.job_titles),
+        "international" => contains(&company.work_authorization),
+        "industry" => company.industry.as_ref().is_some_and(is),
+        "major" => company.majors.iter().any(is),
+        "job_type" => company.job_types.iter().any(is),
+        "school_year" => company.school_years.iter().any(is),
+        "session" => company.attending_sessions.iter().any(|s| contains(&s.display_name)),
+        "booth" => company.booth.as_ref().is_some_and(is),
+        field => {
+            let Some(value) = companies_dir.and_then(|dir| prep::note_field(dir, company, field)) else { return false };
+            match filter.at_least {
+                true => value.parse::<f64>().is_ok_and(|v| filter.value.parse::<f64>().is_ok_and(|min| v >= min)),
+                false => value.split(',').any(|v| v.trim().trim_matches(['[', ']', '"']).eq_ignore_ascii_case(&filter.value)),
+            }
+        },
+    }
+}
+
+// filters on different fields all have to match, ones on the same field are alternatives
+pub fn passes(company: &CompanyEntry, filters: &[Filter], companies_dir: Option<&Path>) -> bool {
+    filters.iter().all(|filter| {
+        filters.iter().filter(|f| f.field == filter.field).any(|f| matches(company, f, companies_dir))
+    })
+}
+
+// the input with only the results that pass the filters, everything else about the payload is
+// kept as it was so the subset renders like the original for whoever it's shared with
+pub fn export_subset(input_data: &[u8], filters: &[Filter], companies_dir: Option<&Path>, naming: &Naming) -> Result<(String, usize, usize), Error> {
+    let mut json_data: serde_json::Value = match serde_json::from_slice(input_data) {
+        Ok(data) => data,
+        Err(source) => return Err(Error::Json { what: "input data".to_string(), source }),
+    };
+    let Some(entries) = json_data["results"].as_array_mut() else {
+        return Err(Error::Other("input data is an invalid format".to_string()));
+    };
+
+    let total = entries.len();
+    let mut kept = Vec::new();
+    for entry in entries.drain(..) {
+        // parsed one at a time so each result lines up with the company it became
+        let single = serde_json::json!({ "results": [&entry] });
+        let mut companies = parse_companies(&serde_json::to_vec(&single).unwrap_or_default(), policy::Policy::Lenient)?;
+        naming.apply(&mut companies);
+        let Some(company) = companies.first() else { continue };
+
+        if passes(company, filters, companies_dir) {
+            kept.push(entry);
+        }
+    }
+    let count = kept.len();
+    *entries = kept;
+
+    match serde_json::to_string_pretty(&json_data) {
+        Ok(text) => Ok((text + "\n", count, total)),
+        Err(e) => Err(Error::Other(format!("failed writing subset json: {}", e))),
+    }
+}
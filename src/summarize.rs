@@ -0,0 +1,150 @@
+use std::path::{Path, PathBuf};
+use std::time::Duration;
+
+use crate::policy::Policy;
+use crate::translate::{self, TranslationCache};
+use crate::{CompanyEntry, Error, links, term};
+
+// a summary is for a table cell or a card, anything longer than this is cut at a word
+const MAX_SUMMARY_BYTES: usize = 300;
+
+// a period after one of these doesn't end the sentence, compared lowercase without the period
+const ABBREVIATIONS: &[&str] = &[
+    "inc", "co", "corp", "ltd", "llc", "plc", "st", "mr", "ms", "mrs", "dr", "jr", "sr", "vs", "etc",
+    "e.g", "i.e", "no", "dept", "univ", "approx", "est",
+];
+
+pub trait Summarizer {
+    fn summarize(&self, text: &str) -> Result<Option<String>, Error>;
+}
+
+// the default, the first few sentences of the description, which is usually where a company says
+// what it does before the mission statement starts
+pub struct SentenceSummarizer {
+    pub sentences: usize,
+}
+
+// a period ends a sentence unless it closes an abbreviation, an initial, one like U.S. with
+// periods inside it, or a number like 2.5, or the text goes on in lowercase
+fn ends_sentence(before: &str, after: &str) -> bool {
+    let mut after = after.chars();
+    if after.next().is_some_and(|c| !c.is_whitespace() && !matches!(c, '"' | '\'' | ')' | '”' | '’')) {
+        return false;
+    }
+    if after.find(|c| !c.is_whitespace()).is_some_and(char::is_lowercase) {
+        return false;
+    }
+    let word = before.rsplit(char::is_whitespace).next().unwrap_or_default();
+    let word = word.trim_start_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+    word.chars().count() > 1 && !word.contains('.') && !ABBREVIATIONS.contains(&word.as_str())
+}
+
+fn first_sentences(text: &str, count: usize) -> String {
+    let text = text.split_whitespace().collect::<Vec<_>>().join(" ");
+    let mut found = 0;
+    for (i, c) in text.char_indices() {
+        let after = &text[i + c.len_utf8()..];
+        let end = match c {
+            '!' | '?' => after.chars().next().is_none_or(char::is_whitespace),
+            '.' => ends_sentence(&text[..i], after),
+            // cjk punctuation has no space after it
+            '。' | '！' | '？' => true,
+            _ => false,
+        };
+        if end {
+            found += 1;
+            if found == count {
+                // keeps a closing quote or bracket right after the period
+                let closers = after.len() - after.trim_start_matches(['"', '\'', ')', '”', '’']).len();
+                return text[..i + c.len_utf8() + closers].to_string();
+            }
+        }
+    }
+    text
+}
+
+// cut at the last whole word that fits so a card doesn't end halfway through one
+fn cap(summary: String) -> String {
+    if summary.len() <= MAX_SUMMARY_BYTES {
+        return summary;
+    }
+    let cut = links::truncate(&summary, MAX_SUMMARY_BYTES);
+    let cut = match cut.rfind(' ') {
+        Some(space) if space > 0 => &cut[..space],
+        _ => cut,
+    };
+    format!("{}…", cut.trim_end_matches([',', ';', ':', ' ']))
+}
+
+impl Summarizer for SentenceSummarizer {
+    fn summarize(&self, text: &str) -> Result<Option<String>, Error> {
+        let summary = first_sentences(text, self.sentences.max(1));
+        Ok((!summary.is_empty()).then_some(summary))
+    }
+}
+
+// runs a user supplied command with the description on stdin and reads the summary from stdout,
+// FAIR_RENDERER_SENTENCES holds --summary-sentences, ex. a script calling a local model
+pub struct CommandSummarizer {
+    pub command: String,
+    pub sentences: usize,
+    pub timeout: Duration,
+}
+
+impl Summarizer for CommandSummarizer {
+    fn summarize(&self, text: &str) -> Result<Option<String>, Error> {
+        let sentences = self.sentences.to_string();
+        translate::run_command("summarize", &self.command, &[("FAIR_RENDERER_SENTENCES", &sentences)], text, self.timeout)
+    }
+}
+
+pub fn cache_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(".fair_renderer/summaries.json")
+}
+
+// fills in summary from the description, translated when there is one, a command's summaries are
+// cached the same way translations are and the first sentences stand in when it fails or prints nothing
+pub fn summarize_companies(
+    companies: &mut [CompanyEntry],
+    summarizer: &dyn Summarizer,
+    fallback: &SentenceSummarizer,
+    mut cache: Option<&mut TranslationCache>,
+    policy: Policy,
+) -> Result<(), Error> {
+    // a different --summary-sentences asks the command for a different summary
+    let cache_key = format!("summary {}", fallback.sentences);
+    let mut provider_failed = false;
+    for company in companies.iter_mut() {
+        let description = company.translated_description.as_ref().unwrap_or(&company.description).trim().to_string();
+        if description.is_empty() {
+            continue;
+        }
+
+        let cached = cache.as_ref().and_then(|c| c.get(&description, &cache_key)).cloned();
+        let summary = match (cached, provider_failed) {
+            (Some(cached), _) => Some(cached),
+            (None, true) => None,
+            (None, false) => match summarizer.summarize(&description) {
+                Ok(Some(summary)) => {
+                    term::trace(&format!("summarized {}", company.name));
+                    if let Some(cache) = cache.as_mut() {
+                        cache.insert(&description, &cache_key, summary.clone());
+                    }
+                    Some(summary)
+                },
+                Ok(None) => None,
+                Err(e) => {
+                    provider_failed = true;
+                    policy.fail(&format!("summarizing {}: {}, using the first sentences for the rest", company.name, e))?;
+                    None
+                },
+            },
+        };
+        let summary = match summary {
+            Some(summary) => Some(summary),
+            None => fallback.summarize(&description)?,
+        };
+        company.summary = summary.map(|s| cap(s.split_whitespace().collect::<Vec<_>>().join(" ")));
+    }
+    Ok(())
+}
@@ -0,0 +1,126 @@
+use std::fs;
+use std::path::Path;
+
+use crate::extract::Extracted;
+use crate::labels::Labels;
+
+enum Kind {
+    Boolean,
+    Number,
+    Text,
+}
+
+// what sort of field it is going by what people filled in, not what the fileClass says, since
+// a field added by hand in obsidian has no type anywhere
+fn kind(values: &[&str]) -> Kind {
+    match values {
+        _ if values.iter().all(|v| *v == "true" || *v == "false") => Kind::Boolean,
+        _ if values.iter().all(|v| v.parse::<f64>().is_ok()) => Kind::Number,
+        _ => Kind::Text,
+    }
+}
+
+// follow-ups still holding the recruiter or position marker haven't been written yet
+fn pending_followups(followups_dir: &Path, labels: &Labels) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(followups_dir) else { return Vec::new() };
+    let mut pending: Vec<String> = entries
+        .filter_map(|e| e.ok())
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "md"))
+        .filter(|p| fs::read_to_string(p).is_ok_and(|t| t.contains(&labels.recruiter_marker) || t.contains(&labels.position_marker)))
+        .filter_map(|p| p.file_stem().map(|s| s.to_string_lossy().to_string()))
+        .collect();
+    pending.sort();
+    pending
+}
+
+// one section per field someone filled in, checkboxes like Viewed or Applied are counted,
+// numbers like Priority averaged and ranked, and text like a status grouped by value
+pub fn render_summary(extracted: &[Extracted], followups_dir: &Path, labels: &Labels) -> String {
+    let mut field_names: Vec<&str> = Vec::new();
+    for (name, _) in extracted.iter().flat_map(|e| &e.fields) {
+        if !field_names.contains(&name.as_str()) {
+            field_names.push(name);
+        }
+    }
+
+    let mut text = format!("# {}\n", labels.summary);
+
+    for field in field_names {
+        let filled: Vec<(&str, &str)> = extracted.iter()
+            .filter_map(|e| e.fields.iter().find(|(n, v)| n == field && !v.is_empty()).map(|(_, v)| (e.company.name.as_str(), v.as_str())))
+            .collect();
+        let values: Vec<&str> = filled.iter().map(|(_, v)| *v).collect();
+        if filled.is_empty() {
+            continue;
+        }
+
+        match kind(&values) {
+            Kind::Boolean => {
+                let checked: Vec<&str> = filled.iter().filter(|(_, v)| *v == "true").map(|(name, _)| *name).collect();
+                text.push_str(&format!("\n## {} ({} of {})\n\n", field, checked.len(), extracted.len()));
+                for name in checked {
+                    text.push_str(&format!("- [[{}]]\n", name));
+                }
+            },
+            Kind::Number => {
+                let mut rated: Vec<(&str, f64)> = filled.iter().filter_map(|(name, v)| Some((*name, v.parse::<f64>().ok()?))).collect();
+                let average = rated.iter().map(|(_, v)| v).sum::<f64>() / rated.len() as f64;
+                rated.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(b.0)));
+                text.push_str(&format!("\n## {} ({} of {}, average {:.1})\n\n", field, rated.len(), extracted.len(), average));
+                text.push_str(&format!("| {} | {} |\n|---|---|\n", labels.company, field));
+                for (name, value) in rated {
+                    text.push_str(&format!("| [[{}]] | {} |\n", name, value));
+                }
+            },
+            Kind::Text => {
+                let mut groups: Vec<(&str, Vec<&str>)> = Vec::new();
+                for (name, value) in &filled {
+                    match groups.iter_mut().find(|(v, _)| v.eq_ignore_ascii_case(value)) {
+                        Some((_, names)) => names.push(name),
+                        None => groups.push((value, vec![name])),
+                    }
+                }
+                // free-form notes would just be every note again, only fields with repeated values are summed up
+                if groups.len() == filled.len() {
+                    continue;
+                }
+                groups.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+                text.push_str(&format!("\n## {}\n\n", field));
+                for (value, names) in groups {
+                    let links: Vec<String> = names.iter().map(|n| format!("[[{}]]", n)).collect();
+                    text.push_str(&format!("- {} ({}): {}\n", value, names.len(), links.join(", ")));
+                }
+            },
+        }
+    }
+
+    // read from the notes rather than the fields above since the renderer writes it, guessed or not
+    let mut industries: Vec<(&str, Vec<&str>)> = Vec::new();
+    for e in extracted {
+        let Some(industry) = &e.company.industry else { continue };
+        match industries.iter_mut().find(|(i, _)| i.eq_ignore_ascii_case(industry)) {
+            Some((_, names)) => names.push(&e.company.name),
+            None => industries.push((industry, vec![&e.company.name])),
+        }
+    }
+    if !industries.is_empty() {
+        industries.sort_by(|a, b| b.1.len().cmp(&a.1.len()).then(a.0.cmp(b.0)));
+        text.push_str(&format!("\n## {}\n\n", labels.industries));
+        for (industry, names) in industries {
+            let share = names.len() as f64 * 100.0 / extracted.len() as f64;
+            let links: Vec<String> = names.iter().map(|n| format!("[[{}]]", n)).collect();
+            text.push_str(&format!("- {} ({}, {:.0}%): {}\n", industry, names.len(), share, links.join(", ")));
+        }
+    }
+
+    let pending = pending_followups(followups_dir, labels);
+    if !pending.is_empty() {
+        text.push_str(&format!("\n## {} ({})\n\n", labels.pending_followups, pending.len()));
+        for name in pending {
+            text.push_str(&format!("- [[{}]]\n", name));
+        }
+    }
+
+    text
+}
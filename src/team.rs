@@ -0,0 +1,68 @@
+use crate::{CompanyEntry, links};
+use crate::labels::Labels;
+
+pub struct Member {
+    pub name: String,
+    pub majors: Vec<String>,
+}
+
+fn recruits(company: &CompanyEntry, member: &Member) -> bool {
+    member.majors.iter().any(|m| company.majors.iter().any(|c| c.eq_ignore_ascii_case(m)))
+}
+
+fn company_line(company: &CompanyEntry, labels: &Labels) -> String {
+    match &company.booth {
+        Some(booth) => format!("- {} ({} {})\n", links::company_link(company), labels.booth, booth),
+        None => format!("- {}\n", links::company_link(company)),
+    }
+}
+
+// which companies want everyone on the team and which only some of them, then a split of the
+// booths so each company someone's majors match gets visited by exactly one of them
+pub fn render_team(companies: &[CompanyEntry], members: &[Member], labels: &Labels) -> Option<String> {
+    if members.is_empty() {
+        return None;
+    }
+
+    let matched: Vec<(&CompanyEntry, Vec<usize>)> = companies.iter()
+        .map(|company| (company, (0..members.len()).filter(|i| recruits(company, &members[*i])).collect::<Vec<usize>>()))
+        .filter(|(_, who)| !who.is_empty())
+        .collect();
+
+    let mut text = format!("# {}\n", labels.team);
+
+    text.push_str(&format!("\n## {}\n\n", labels.recruits_all));
+    for (company, _) in matched.iter().filter(|(_, who)| who.len() == members.len()) {
+        text.push_str(&company_line(company, labels));
+    }
+
+    text.push_str(&format!("\n## {}\n\n", labels.recruits_any));
+    let names: Vec<&str> = members.iter().map(|m| m.name.as_str()).collect();
+    text.push_str(&format!("| | {} |\n", names.join(" | ")));
+    text.push_str(&format!("|---|{}\n", "---|".repeat(members.len())));
+    for (company, who) in &matched {
+        let marks: Vec<&str> = (0..members.len()).map(|i| if who.contains(&i) { "x" } else { "" }).collect();
+        text.push_str(&format!("| {} | {} |\n", links::company_link(company).replace('|', "\\|"), marks.join(" | ")));
+    }
+
+    // the companies fewest people can cover get handed out first, each to whoever has the fewest so far
+    let mut order: Vec<&(&CompanyEntry, Vec<usize>)> = matched.iter().collect();
+    order.sort_by_key(|(company, who)| (who.len(), company.booth.clone(), company.name.clone()));
+    let mut coverage: Vec<Vec<&CompanyEntry>> = vec![Vec::new(); members.len()];
+    for (company, who) in order {
+        if let Some(member) = who.iter().min_by_key(|i| (coverage[**i].len(), **i)) {
+            coverage[*member].push(company);
+        }
+    }
+
+    text.push_str(&format!("\n## {}\n", labels.coverage));
+    for (member, assigned) in members.iter().zip(coverage.iter_mut()) {
+        assigned.sort_by(|a, b| a.booth.cmp(&b.booth).then(a.name.cmp(&b.name)));
+        text.push_str(&format!("\n### {}\n\n", member.name));
+        for company in assigned.iter() {
+            text.push_str(&company_line(company, labels));
+        }
+    }
+
+    Some(text)
+}
@@ -0,0 +1,247 @@
+use std::io::IsTerminal;
+use std::sync::Mutex;
+use std::sync::atomic::{AtomicBool, AtomicU8, Ordering};
+#[cfg(feature = "cli")]
+use std::time::Duration;
+use std::time::Instant;
+
+#[cfg(feature = "cli")]
+use indicatif::{ProgressBar, ProgressStyle};
+
+static COLOR: AtomicBool = AtomicBool::new(false);
+static ERR_COLOR: AtomicBool = AtomicBool::new(false);
+// quiet until init, so the library only prints when the command line asked it to
+static LEVEL: AtomicU8 = AtomicU8::new(QUIET);
+static BAR: Mutex<Option<ProgressBar>> = Mutex::new(None);
+// the running phase, when it started and how many of it are done, for the timings and counts -v prints
+static PHASE: Mutex<Option<Phase>> = Mutex::new(None);
+
+struct Phase {
+    name: String,
+    started: Instant,
+    len: Option<usize>,
+    pos: usize,
+}
+
+// --quiet is 0, each -v adds one on top of normal
+pub const QUIET: u8 = 0;
+pub const NORMAL: u8 = 1;
+pub const VERBOSE: u8 = 2;
+pub const TRACE: u8 = 3;
+
+// colors are only used when printing to a terminal, NO_COLOR is the usual opt out convention
+pub fn init(no_color: bool, level: u8) {
+    let enabled = !no_color && std::env::var_os("NO_COLOR").is_none();
+    COLOR.store(enabled && std::io::stdout().is_terminal(), Ordering::Relaxed);
+    ERR_COLOR.store(enabled && std::io::stderr().is_terminal(), Ordering::Relaxed);
+    LEVEL.store(level, Ordering::Relaxed);
+}
+
+fn level_enabled(level: u8) -> bool {
+    LEVEL.load(Ordering::Relaxed) >= level
+}
+
+// lines printed while a bar is up go above it instead of through it
+fn print(line: &str) {
+    match BAR.lock().ok().as_deref().and_then(Option::as_ref) {
+        Some(bar) => bar.suspend(|| println!("{}", line)),
+        None => println!("{}", line),
+    }
+}
+
+// warnings and errors go to stderr so they don't end up mixed into output that's piped somewhere
+fn eprint(line: &str) {
+    match BAR.lock().ok().as_deref().and_then(Option::as_ref) {
+        Some(bar) => bar.suspend(|| eprintln!("{}", line)),
+        None => eprintln!("{}", line),
+    }
+}
+
+// a bar for the part of the render that's running, so a fair with hundreds of companies isn't silent
+// until it's done, a spinner when there's nothing to count, only drawn on a terminal at the normal
+// level since -v already prints a line per file
+pub fn phase(name: &str, len: Option<usize>) {
+    end_phase();
+    if let Ok(mut current) = PHASE.lock() {
+        *current = Some(Phase { name: name.to_string(), started: Instant::now(), len, pos: 0 });
+    }
+    let shown = LEVEL.load(Ordering::Relaxed) == NORMAL && std::io::stderr().is_terminal();
+    let bar = match shown {
+        true => new_bar(name, len),
+        false => None,
+    };
+    if let Ok(mut current) = BAR.lock() {
+        if let Some(previous) = current.take() {
+            previous.finish_and_clear();
+        }
+        *current = bar;
+    }
+}
+
+#[cfg(feature = "cli")]
+fn new_bar(name: &str, len: Option<usize>) -> Option<ProgressBar> {
+    let bar = match len {
+        Some(len) => ProgressBar::new(len as u64).with_style(
+            ProgressStyle::with_template("{prefix:>16} [{bar:30}] {pos}/{len} {wide_msg}").expect("valid template").progress_chars("=> "),
+        ),
+        None => {
+            let bar = ProgressBar::new_spinner().with_style(ProgressStyle::with_template("{prefix:>16} {spinner} {wide_msg}").expect("valid template"));
+            bar.enable_steady_tick(Duration::from_millis(100));
+            bar
+        },
+    };
+    bar.set_prefix(name.to_string());
+    Some(bar)
+}
+
+// the library alone has no bars, the lines they'd be suspended for are printed as they are
+#[cfg(not(feature = "cli"))]
+fn new_bar(_name: &str, _len: Option<usize>) -> Option<ProgressBar> {
+    None
+}
+
+#[cfg(not(feature = "cli"))]
+struct ProgressBar;
+
+#[cfg(not(feature = "cli"))]
+impl ProgressBar {
+    fn suspend<R>(&self, f: impl FnOnce() -> R) -> R {
+        f()
+    }
+
+    fn set_message(&self, _message: String) {}
+
+    fn inc(&self, _delta: u64) {}
+
+    fn finish_and_clear(&self) {}
+}
+
+// what the phase is on right now, ex. the logo being downloaded
+pub fn step(message: &str) {
+    if let Some(bar) = BAR.lock().ok().as_deref().and_then(Option::as_ref) {
+        bar.set_message(message.to_string());
+    }
+}
+
+// one more of the phase's len done, with -v it's a line instead of the bar moving
+pub fn advance(message: &str) {
+    if let Some(bar) = BAR.lock().ok().as_deref().and_then(Option::as_ref) {
+        bar.set_message(message.to_string());
+        bar.inc(1);
+    }
+    let line = PHASE.lock().ok().and_then(|mut current| {
+        let phase = current.as_mut()?;
+        phase.pos += 1;
+        Some(match phase.len {
+            Some(len) => format!("{} {}/{} {}", phase.name, phase.pos, len, message),
+            None => format!("{} {}", phase.name, message),
+        })
+    });
+    if let Some(line) = line {
+        debug(&line);
+    }
+}
+
+pub fn done() {
+    end_phase();
+    if let Some(bar) = BAR.lock().ok().and_then(|mut current| current.take()) {
+        bar.finish_and_clear();
+    }
+}
+
+fn end_phase() {
+    let Some(phase) = PHASE.lock().ok().and_then(|mut current| current.take()) else { return };
+    debug(&format!("{} took {}", phase.name, elapsed(phase.started)));
+}
+
+// ex. 850ms or 12.3s
+pub fn elapsed(started: Instant) -> String {
+    let elapsed = started.elapsed();
+    match elapsed.as_millis() {
+        0..1000 => format!("{}ms", elapsed.as_millis()),
+        _ => format!("{:.1}s", elapsed.as_secs_f64()),
+    }
+}
+
+// for lists that are shortened unless -v asks for all of it
+pub fn verbose() -> bool {
+    level_enabled(VERBOSE)
+}
+
+// for output that's expensive to put together, so it's only built when it will be printed
+pub fn tracing() -> bool {
+    level_enabled(TRACE)
+}
+
+// regular output, hidden by --quiet
+pub fn info(msg: &str) {
+    if level_enabled(NORMAL) {
+        print(msg);
+    }
+}
+
+// something the run carried on past that might still be worth fixing, hidden by --quiet
+pub fn warn(msg: &str) {
+    if level_enabled(NORMAL) {
+        eprint(&paint_err(msg, "33"));
+    }
+}
+
+// -v
+pub fn debug(msg: &str) {
+    if level_enabled(VERBOSE) {
+        print(msg);
+    }
+}
+
+// -vv
+pub fn trace(msg: &str) {
+    if level_enabled(TRACE) {
+        print(&paint(msg, "2"));
+    }
+}
+
+pub enum Status {
+    Written,
+    Updated,
+    Skipped,
+    Failed,
+}
+
+// per file lines only show with -v, failures show unless --quiet and go to stderr
+pub fn status(kind: Status, msg: &str) {
+    let (label, color, level) = match kind {
+        Status::Written => ("written", "32", VERBOSE),
+        Status::Updated => ("updated", "32", VERBOSE),
+        Status::Skipped => ("skipped", "33", VERBOSE),
+        Status::Failed => ("failed", "31", NORMAL),
+    };
+    if !level_enabled(level) {
+        return;
+    }
+    match kind {
+        Status::Failed => eprint(&format!("{} {}", paint_err(&format!("{:>8}", label), color), msg)),
+        _ => print(&format!("{} {}", paint(&format!("{:>8}", label), color), msg)),
+    }
+}
+
+// always printed, even with --quiet, whatever was running stopped so its bar goes too
+pub fn error(msg: &str) {
+    done();
+    eprintln!("{}", paint_err(msg, "31"));
+}
+
+fn paint(text: &str, color: &str) -> String {
+    paint_with(COLOR.load(Ordering::Relaxed), text, color)
+}
+
+fn paint_err(text: &str, color: &str) -> String {
+    paint_with(ERR_COLOR.load(Ordering::Relaxed), text, color)
+}
+
+fn paint_with(enabled: bool, text: &str, color: &str) -> String {
+    match enabled {
+        true => format!("\x1b[{}m{}\x1b[0m", color, text),
+        false => text.to_string(),
+    }
+}
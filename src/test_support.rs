@@ -0,0 +1,225 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+
+use crate::{DEFAULT_TEMPLATE, Error};
+// the upload's request signing, to check against the examples in aws's docs
+pub use crate::upload::{S3Credentials, s3_authorization};
+
+// path to the content type and body of a file the mock serves
+type Files = Arc<Mutex<BTreeMap<String, (String, Vec<u8>)>>>;
+
+// a stand-in for the handshake api on localhost, it answers ?page=N with the Nth recorded page the way
+// the real one pages through a fair's employers, and any other path it was given a file for with that
+// file, so --fetch and logo downloads can be tested without a network or an account
+pub struct MockHandshake {
+    addr: SocketAddr,
+    files: Files,
+    requests: Arc<Mutex<Vec<String>>>,
+    stopped: Arc<AtomicBool>,
+}
+
+struct Shared {
+    pages: Vec<Vec<u8>>,
+    token: Option<String>,
+    files: Files,
+    requests: Arc<Mutex<Vec<String>>>,
+}
+
+impl MockHandshake {
+    // with a token set, pages are only served to requests carrying it as the session cookie
+    pub fn start(pages: Vec<Vec<u8>>, token: Option<&str>) -> Result<MockHandshake, Error> {
+        let listener = TcpListener::bind("127.0.0.1:0")?;
+        let addr = listener.local_addr()?;
+        let files: Files = Arc::new(Mutex::new(BTreeMap::new()));
+        let requests = Arc::new(Mutex::new(Vec::new()));
+        let stopped = Arc::new(AtomicBool::new(false));
+
+        let shared = Shared { pages, token: token.map(str::to_string), files: files.clone(), requests: requests.clone() };
+        let stop = stopped.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                if stop.load(Ordering::Relaxed) {
+                    return;
+                }
+                // one bad request shouldn't take down the server for the rest of the test
+                if let Ok(stream) = stream {
+                    let _ = respond(stream, &shared);
+                }
+            }
+        });
+        Ok(MockHandshake { addr, files, requests, stopped })
+    }
+
+    // a recorded response is a saved page, or a folder of them served in the order of their names
+    pub fn from_recording(path: &Path) -> Result<MockHandshake, Error> {
+        let pages = match path.is_dir() {
+            true => {
+                let mut names: Vec<PathBuf> = fs::read_dir(path)?
+                    .filter_map(|e| e.ok().map(|e| e.path()))
+                    .filter(|p| p.extension().is_some_and(|e| e == "json"))
+                    .collect();
+                names.sort();
+                names.iter().map(fs::read).collect::<Result<Vec<_>, _>>()?
+            },
+            false => vec![fs::read(path)?],
+        };
+        if pages.is_empty() {
+            return Err(Error::Other(format!("no recorded pages in {}", path.to_string_lossy())));
+        }
+        MockHandshake::start(pages, None)
+    }
+
+    // ex. a logo for the entries' logo_url to point at
+    pub fn serve_file(&self, path: &str, content_type: &str, body: Vec<u8>) {
+        if let Ok(mut files) = self.files.lock() {
+            files.insert(path.to_string(), (content_type.to_string(), body));
+        }
+    }
+
+    pub fn url(&self, path: &str) -> String {
+        format!("http://{}{}", self.addr, path)
+    }
+
+    // the request targets it got so far, ex. /api/employers?page=2
+    pub fn requests(&self) -> Vec<String> {
+        self.requests.lock().map(|r| r.clone()).unwrap_or_default()
+    }
+}
+
+impl Drop for MockHandshake {
+    // the listener only notices the flag on its next connection, so it gets one
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+        let _ = TcpStream::connect(self.addr);
+    }
+}
+
+fn respond(mut stream: TcpStream, shared: &Shared) -> Result<(), Error> {
+    let mut reader = BufReader::new(&stream);
+    let mut request_line = String::new();
+    reader.read_line(&mut request_line)?;
+    let target = request_line.split(' ').nth(1).unwrap_or("/").to_string();
+
+    let mut cookie = None;
+    loop {
+        let mut line = String::new();
+        if reader.read_line(&mut line)? == 0 || line.trim().is_empty() {
+            break;
+        }
+        if let Some((name, value)) = line.split_once(':')
+            && name.eq_ignore_ascii_case("cookie")
+        {
+            cookie = Some(value.trim().to_string());
+        }
+    }
+    if let Ok(mut requests) = shared.requests.lock() {
+        requests.push(target.clone());
+    }
+
+    let (path, query) = target.split_once('?').unwrap_or((&target, ""));
+    let file = shared.files.lock().ok().and_then(|files| files.get(path).cloned());
+    let page = query.split('&').find_map(|p| p.strip_prefix("page=")).and_then(|p| p.parse::<usize>().ok()).unwrap_or(1);
+    let signed_in = match &shared.token {
+        Some(token) => cookie.is_some_and(|c| c.split(';').any(|c| c.trim() == format!("_trajectory_session={}", token))),
+        None => true,
+    };
+
+    let (status, content_type, body) = match file {
+        Some((content_type, body)) => ("200 OK", content_type, body),
+        None if !signed_in => ("401 Unauthorized", "application/json".to_string(), b"{\"error\":\"unauthorized\"}".to_vec()),
+        None => match shared.pages.get(page.wrapping_sub(1)) {
+            Some(body) => ("200 OK", "application/json".to_string(), body.clone()),
+            None => ("404 Not Found", "application/json".to_string(), b"{\"error\":\"no such page\"}".to_vec()),
+        },
+    };
+    write!(stream, "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n", status, content_type, body.len())?;
+    stream.write_all(&body)?;
+    Ok(())
+}
+
+// a vault in the temp folder that's deleted again when it goes out of scope
+pub struct TempVault {
+    pub path: PathBuf,
+}
+
+static VAULTS: AtomicUsize = AtomicUsize::new(0);
+
+impl TempVault {
+    // nothing is created until a render, the same as a vault path given to -o
+    pub fn new(name: &str) -> TempVault {
+        let count = VAULTS.fetch_add(1, Ordering::Relaxed);
+        let path = std::env::temp_dir().join(format!("fair_renderer_{}_{}_{}", name, std::process::id(), count));
+        let _ = fs::remove_dir_all(&path);
+        TempVault { path }
+    }
+
+    // the same as fair_renderer render -o <vault> with the default template unless args has a -t
+    pub fn render(&self, args: &[&str]) -> Result<(), Error> {
+        self.run("render", args)
+    }
+
+    pub fn update(&self, args: &[&str]) -> Result<(), Error> {
+        self.run("update", args)
+    }
+
+    fn run(&self, command: &str, args: &[&str]) -> Result<(), Error> {
+        let mut full = vec![command.to_string(), "-q".to_string(), "-o".to_string(), self.path.to_string_lossy().to_string()];
+        if !args.iter().any(|a| *a == "-t" || *a == "--template") {
+            full.extend(["-t".to_string(), DEFAULT_TEMPLATE.to_string()]);
+        }
+        full.extend(args.iter().map(|a| a.to_string()));
+        crate::commands::real_main(full).map(|_| ())
+    }
+
+    // every file the render left, by its path inside the vault, the renderer's own state is left out
+    pub fn files(&self) -> BTreeMap<String, String> {
+        let mut files = BTreeMap::new();
+        collect(&self.path, PathBuf::new(), &mut files);
+        files
+    }
+}
+
+impl Drop for TempVault {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.path);
+    }
+}
+
+fn collect(root: &Path, relative: PathBuf, files: &mut BTreeMap<String, String>) {
+    let Ok(entries) = fs::read_dir(root.join(&relative)) else { return };
+    for entry in entries.flatten() {
+        let path = relative.join(entry.file_name());
+        if path == Path::new(".fair_renderer") {
+            continue;
+        }
+        match entry.path().is_dir() {
+            true => collect(root, path, files),
+            false => {
+                let text = fs::read(entry.path()).map(|b| String::from_utf8_lossy(&b).to_string()).unwrap_or_default();
+                files.insert(path.to_string_lossy().replace('\\', "/"), text);
+            },
+        }
+    }
+}
+
+// what changed between two vaults' files as added, removed and changed paths, empty when they match
+pub fn diff(before: &BTreeMap<String, String>, after: &BTreeMap<String, String>) -> Vec<String> {
+    let mut changes = Vec::new();
+    for (path, text) in after {
+        match before.get(path) {
+            None => changes.push(format!("added {}", path)),
+            Some(old) if old != text => changes.push(format!("changed {}", path)),
+            Some(_) => {},
+        }
+    }
+    for path in before.keys().filter(|p| !after.contains_key(*p)) {
+        changes.push(format!("removed {}", path));
+    }
+    changes
+}
@@ -0,0 +1,250 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{Duration, Instant};
+
+use sha2::{Digest, Sha256};
+
+use crate::policy::Policy;
+use crate::{CompanyEntry, Error, state, term};
+
+// common short words per language, good enough to tell a french description from an english one
+const STOPWORDS: &[(&str, &[&str])] = &[
+    ("en", &["the", "and", "of", "to", "we", "our", "is", "for", "with", "are"]),
+    ("es", &["el", "la", "los", "las", "y", "de", "que", "en", "para", "con", "somos", "nuestra"]),
+    ("fr", &["le", "la", "les", "et", "des", "du", "est", "pour", "nous", "notre", "une", "dans"]),
+    ("de", &["der", "die", "das", "und", "ist", "wir", "mit", "für", "unsere", "ein", "eine", "nicht"]),
+    ("pt", &["o", "os", "as", "e", "da", "do", "que", "para", "com", "uma", "nossa", "somos"]),
+    ("it", &["il", "gli", "e", "di", "che", "per", "con", "una", "siamo", "nostra", "della", "sono"]),
+];
+
+// returns a language code, or None if there isn't enough text to guess
+pub fn detect_language(text: &str) -> Option<&'static str> {
+    // scripts are a much stronger signal than word lists when they show up
+    let mut script_counts = [0usize; 5];
+    for c in text.chars() {
+        match c as u32 {
+            0x3040..=0x30FF => script_counts[0] += 1,
+            0xAC00..=0xD7AF => script_counts[1] += 1,
+            0x4E00..=0x9FFF => script_counts[2] += 1,
+            0x0400..=0x04FF => script_counts[3] += 1,
+            0x0600..=0x06FF => script_counts[4] += 1,
+            _ => {},
+        }
+    }
+    let total_chars = text.chars().filter(|c| c.is_alphabetic()).count().max(1);
+    if script_counts.iter().sum::<usize>() * 4 > total_chars {
+        return Some(match script_counts.iter().enumerate().max_by_key(|(_, n)| **n).map(|(i, _)| i) {
+            Some(0) => "ja",
+            Some(1) => "ko",
+            Some(2) if script_counts[0] > 0 => "ja",
+            Some(2) => "zh",
+            Some(3) => "ru",
+            _ => "ar",
+        });
+    }
+
+    let words: Vec<String> = text
+        .split(|c: char| !c.is_alphabetic())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect();
+    if words.len() < 8 {
+        return None;
+    }
+
+    // real prose is full of these, a stray "et" or "est" in lorem ipsum or a list of names isn't a language
+    let min_hits = (words.len() / 10).max(3);
+    STOPWORDS.iter()
+        .map(|(lang, stopwords)| (*lang, words.iter().filter(|w| stopwords.contains(&w.as_str())).count()))
+        .filter(|(_, score)| *score >= min_hits)
+        // ties go to english since it is listed first
+        .fold(None, |best: Option<(&str, usize)>, (lang, score)| match best {
+            Some((_, best_score)) if best_score >= score => best,
+            _ => Some((lang, score)),
+        })
+        .map(|(lang, _)| lang)
+}
+
+// anything that can turn text from one language into another, the default does nothing
+// so the tool stays fully offline unless a provider is configured
+pub trait Translator {
+    fn translate(&self, text: &str, from: &str, to: &str) -> Result<Option<String>, Error>;
+}
+
+pub struct NoopTranslator;
+
+impl Translator for NoopTranslator {
+    fn translate(&self, _text: &str, _from: &str, _to: &str) -> Result<Option<String>, Error> {
+        Ok(None)
+    }
+}
+
+// runs a user supplied command with the text on stdin and reads the translation from stdout,
+// FAIR_RENDERER_FROM and FAIR_RENDERER_TO hold the language codes, ex. a script calling deepl or argos
+pub struct CommandTranslator {
+    pub command: String,
+    pub timeout: Duration,
+}
+
+impl Translator for CommandTranslator {
+    fn translate(&self, text: &str, from: &str, to: &str) -> Result<Option<String>, Error> {
+        run_command("translate", &self.command, &[("FAIR_RENDERER_FROM", from), ("FAIR_RENDERER_TO", to)], text, self.timeout)
+    }
+}
+
+// a user's command run through the shell with text on stdin, whatever it prints is the result and
+// nothing printed is None, shared by the providers that hand text off to a script
+pub fn run_command(what: &str, command: &str, env: &[(&str, &str)], text: &str, timeout: Duration) -> Result<Option<String>, Error> {
+    let mut command = match cfg!(target_os = "windows") {
+        true => { let mut c = Command::new("cmd"); c.arg("/C").arg(command); c },
+        false => { let mut c = Command::new("sh"); c.arg("-c").arg(command); c },
+    };
+
+    let mut child = command
+        .envs(env.iter().copied())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+
+    // read on their own threads so a command writing a lot can't block on a full pipe while we wait
+    let read_pipe = |pipe: Option<Box<dyn Read + Send>>| std::thread::spawn(move || {
+        let mut bytes = Vec::new();
+        if let Some(mut pipe) = pipe {
+            let _ = pipe.read_to_end(&mut bytes);
+        }
+        bytes
+    });
+    let stdout = read_pipe(child.stdout.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+    let stderr = read_pipe(child.stderr.take().map(|p| Box::new(p) as Box<dyn Read + Send>));
+
+    // stdin gets its own thread too, a command that doesn't read all of it would otherwise hang us
+    // before the timeout starts, and one that exits without reading it is done with the text
+    let stdin = child.stdin.take().map(|mut stdin| {
+        let text = text.to_string();
+        std::thread::spawn(move || match stdin.write_all(text.as_bytes()) {
+            Err(e) if e.kind() != std::io::ErrorKind::BrokenPipe => Err(e),
+            _ => Ok(()),
+        })
+    });
+
+    let started = Instant::now();
+    let status = loop {
+        if let Some(status) = child.try_wait()? {
+            break status;
+        }
+        if started.elapsed() >= timeout {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(Error::Other(format!("{} command timed out after {} seconds", what, timeout.as_secs())));
+        }
+        std::thread::sleep(Duration::from_millis(20));
+    };
+
+    if let Some(Ok(Err(e))) = stdin.map(|writer| writer.join()) {
+        return Err(Error::Other(format!("{} command: writing its input: {}", what, e)));
+    }
+    let stdout = stdout.join().unwrap_or_default();
+    let stderr = stderr.join().unwrap_or_default();
+    if !status.success() {
+        return Err(Error::Other(format!("{} command failed: {}", what, String::from_utf8_lossy(&stderr).trim())));
+    }
+
+    let output = String::from_utf8_lossy(&stdout).trim().to_string();
+    Ok((!output.is_empty()).then_some(output))
+}
+
+// translations are cached by a hash of the text so re-renders don't call the provider again, summaries
+// use the same kind of cache in their own file
+pub struct TranslationCache {
+    path: Option<PathBuf>,
+    entries: BTreeMap<String, String>,
+    // only written back when something was added, a render with no provider leaves the file alone
+    changed: bool,
+}
+
+impl TranslationCache {
+    pub fn open(path: Option<PathBuf>) -> TranslationCache {
+        let entries = path.as_ref()
+            .and_then(|p| fs::read(p).ok())
+            .and_then(|bytes| serde_json::from_slice(&bytes).ok())
+            .unwrap_or_default();
+        TranslationCache { path, entries, changed: false }
+    }
+
+    fn key(text: &str, to: &str) -> String {
+        let hash = Sha256::digest(format!("{}\0{}", to, text).as_bytes());
+        hash.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    pub fn get(&self, text: &str, to: &str) -> Option<&String> {
+        self.entries.get(&TranslationCache::key(text, to))
+    }
+
+    pub fn insert(&mut self, text: &str, to: &str, value: String) {
+        self.entries.insert(TranslationCache::key(text, to), value);
+        self.changed = true;
+    }
+
+    pub fn save(&self) -> Result<(), Error> {
+        let Some(path) = self.path.as_ref().filter(|_| self.changed) else { return Ok(()) };
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent).map_err(|source| Error::Output { path: parent.to_string_lossy().to_string(), source })?;
+        }
+        let json = serde_json::to_string_pretty(&self.entries).unwrap_or_default();
+        state::write_atomic(path, json)
+    }
+}
+
+pub fn cache_path(vault_path: &Path) -> PathBuf {
+    vault_path.join(".fair_renderer/translations.json")
+}
+
+// fills in description_language for descriptions not already in the target language,
+// and translated_description when a provider is set
+pub fn translate_companies(
+    companies: &mut [CompanyEntry],
+    translator: &dyn Translator,
+    target_lang: &str,
+    cache: &mut TranslationCache,
+    policy: Policy,
+) -> Result<(), Error> {
+    let mut provider_failed = false;
+    for company in companies.iter_mut() {
+        let Some(lang) = detect_language(&company.description) else { continue };
+        if lang == target_lang {
+            continue;
+        }
+        company.description_language = Some(lang.to_string());
+
+        let key = TranslationCache::key(&company.description, target_lang);
+        if let Some(cached) = cache.entries.get(&key) {
+            company.translated_description = Some(cached.clone());
+            continue;
+        }
+        if provider_failed {
+            continue;
+        }
+
+        match translator.translate(&company.description, lang, target_lang) {
+            Ok(Some(translated)) => {
+                term::trace(&format!("translated {} from {}", company.name, lang));
+                cache.entries.insert(key, translated.clone());
+                cache.changed = true;
+                company.translated_description = Some(translated);
+            },
+            Ok(None) => {},
+            // a broken or unreachable provider shouldn't sink the whole render or keep it waiting on
+            // every company, the original text is still there
+            Err(e) => {
+                provider_failed = true;
+                policy.fail(&format!("translating {}: {}, skipping the remaining translations", company.name, e))?;
+            },
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,152 @@
+use std::fs;
+use std::path::Path;
+
+use crate::Error;
+use crate::term::{self, Status};
+
+// rewrites the keys the renderer owns inside an existing note's frontmatter while keeping
+// whatever the user filled in for their own fields and leaving the body alone
+// returns None if the note doesn't start with a frontmatter block we can understand
+pub fn update_frontmatter(existing: &str, generated: &[(String, String)]) -> Option<String> {
+    let rest = existing.strip_prefix("---\n")?;
+    let end = rest.find("\n---")?;
+    let (frontmatter, body) = (&rest[..end], &rest[end + 1..]);
+
+    let mut written: Vec<&str> = Vec::new();
+    let mut text = "---\n".to_string();
+    let mut skipping_value = false;
+
+    for line in frontmatter.lines() {
+        // continuation lines of a multi line value belong to whatever key came before them
+        let is_continuation = line.starts_with(' ') || line.starts_with('\t') || line.starts_with("- ");
+        if is_continuation {
+            if !skipping_value {
+                text.push_str(line);
+                text.push('\n');
+            }
+            continue;
+        }
+
+        let key = line.split(':').next().unwrap_or("").trim();
+        match generated.iter().find(|(k, _)| k == key) {
+            Some((k, v)) if !written.contains(&k.as_str()) => {
+                text.push_str(&format!("{}: {}\n", k, v));
+                written.push(k);
+                skipping_value = true;
+            },
+            _ => {
+                text.push_str(line);
+                text.push('\n');
+                skipping_value = false;
+            },
+        }
+    }
+
+    // fields that were added to the renderer since the note was first written
+    for (k, v) in generated {
+        if !written.contains(&k.as_str()) {
+            text.push_str(&format!("{}: {}\n", k, v));
+        }
+    }
+
+    text.push_str(body);
+    Some(text)
+}
+
+// generated parts of the body sit between these so they can be swapped out on a refresh
+// without touching anything the user wrote around them
+pub fn marked_section(name: &str, content: &str) -> String {
+    let mut text = format!("<!-- fair_renderer:{} -->\n{}", name, content);
+    if !content.ends_with('\n') {
+        text.push('\n');
+    }
+    text.push_str(&format!("<!-- /fair_renderer:{} -->\n", name));
+    text
+}
+
+// where the content between a section's markers sits in the note
+fn section_range(text: &str, name: &str) -> Option<std::ops::Range<usize>> {
+    let start_marker = format!("<!-- fair_renderer:{} -->\n", name);
+    let end_marker = format!("<!-- /fair_renderer:{} -->", name);
+
+    let start = text.find(&start_marker)? + start_marker.len();
+    let end = text[start..].find(&end_marker)? + start;
+    Some(start..end)
+}
+
+// everything currently inside the note's marked sections, in the given order
+pub fn section_contents(text: &str, names: &[&str]) -> String {
+    names.iter()
+        .filter_map(|name| section_range(text, name))
+        .map(|range| &text[range])
+        .collect()
+}
+
+// an empty marked section for one added to the renderer after the note was written, placed after
+// the section it should follow or right under the frontmatter, nothing happens if it's already there
+pub fn insert_section(existing: &str, name: &str, after: Option<&str>) -> String {
+    if section_range(existing, name).is_some() {
+        return existing.to_string();
+    }
+    let end = match after.and_then(|after| section_range(existing, after).map(|range| (range, after))) {
+        Some((range, after)) => range.end + format!("<!-- /fair_renderer:{} -->\n", after).len(),
+        None => match existing.strip_prefix("---\n").and_then(|rest| rest.find("\n---\n")) {
+            // past the opening ---\n and the closing \n---\n
+            Some(end) => end + 9,
+            None => return existing.to_string(),
+        },
+    };
+    format!("{}\n{}{}", &existing[..end], marked_section(name, ""), &existing[end..])
+}
+
+// replaces the content of every marked section the note still has, a section whose markers
+// were deleted is treated as the user taking it over and is left alone
+pub fn update_sections(existing: &str, sections: &[(&str, String)]) -> String {
+    let mut text = existing.to_string();
+
+    for (name, content) in sections {
+        let Some(range) = section_range(&text, name) else { continue };
+
+        let mut content = content.clone();
+        if !content.ends_with('\n') {
+            content.push('\n');
+        }
+        text.replace_range(range, &content);
+    }
+
+    text
+}
+
+// writes a generated note, or when it already exists refreshes only its generated frontmatter
+// and marked sections, the stub is only added to a new note since it's there for the user to fill in
+// returns true when the note is new
+pub fn write_or_update(file_path: &Path, generated: &[(String, String)], sections: &[(&str, String)], stub: Option<&str>) -> Result<bool, Error> {
+    if file_path.exists() {
+        let existing = fs::read_to_string(file_path)?;
+        let updated = update_frontmatter(&existing, generated).map(|text| update_sections(&text, sections));
+        if let Some(updated) = updated.filter(|u| *u != existing) {
+            fs::write(file_path, updated)?;
+            term::status(Status::Updated, &file_path.to_string_lossy());
+        }
+        return Ok(false);
+    }
+
+    let mut text = "---\n".to_string();
+    for (key, value) in generated {
+        text.push_str(&format!("{}: {}\n", key, value));
+    }
+    text.push_str("---\n\n");
+    for (i, (name, content)) in sections.iter().enumerate() {
+        if i > 0 {
+            text.push('\n');
+        }
+        text.push_str(&marked_section(name, content));
+    }
+    if let Some(stub) = stub {
+        text.push_str(stub);
+    }
+
+    fs::write(file_path, text)?;
+    term::status(Status::Written, &file_path.to_string_lossy());
+    Ok(true)
+}
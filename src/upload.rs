@@ -0,0 +1,312 @@
+// a build without the network feature can't upload, only the parts other modules use are left working
+#![cfg_attr(not(feature = "network"), allow(dead_code, unused_imports))]
+
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use std::{env, fs};
+
+use base64::Engine;
+#[cfg(feature = "network")]
+use hmac::{Hmac, KeyInit, Mac};
+use sha2::{Digest, Sha256};
+#[cfg(feature = "network")]
+use ureq::http::{Method, Request};
+
+use crate::{Error, term};
+
+// credentials come from the environment so they don't end up in shell history or cron files
+//   webdav: FAIR_RENDERER_WEBDAV_USER, FAIR_RENDERER_WEBDAV_PASSWORD
+//   s3:     AWS_ACCESS_KEY_ID, AWS_SECRET_ACCESS_KEY, AWS_REGION, optional AWS_SESSION_TOKEN
+//           and AWS_ENDPOINT_URL for s3 compatible hosts (minio, r2, etc)
+pub enum UploadTarget {
+    WebDav { base_url: String },
+    S3 { bucket: String, prefix: String },
+}
+
+impl UploadTarget {
+    // webdav:https://host/remote/path or s3://bucket/optional/prefix
+    pub fn parse(spec: &str) -> Result<UploadTarget, Error> {
+        // refused before the render rather than after it
+        if !cfg!(feature = "network") {
+            return Err(crate::missing_feature("--upload", "network"));
+        }
+        if let Some(url) = spec.strip_prefix("webdav:") {
+            if !url.starts_with("http://") && !url.starts_with("https://") {
+                return Err(Error::Usage(format!("webdav upload needs an http(s) url, got: {}", url)));
+            }
+            return Ok(UploadTarget::WebDav { base_url: url.trim_end_matches('/').to_string() });
+        }
+
+        if let Some(rest) = spec.strip_prefix("s3://") {
+            let (bucket, prefix) = rest.split_once('/').unwrap_or((rest, ""));
+            if bucket.is_empty() {
+                return Err(Error::Usage("s3 upload is missing a bucket name".to_string()));
+            }
+            return Ok(UploadTarget::S3 { bucket: bucket.to_string(), prefix: prefix.trim_matches('/').to_string() });
+        }
+
+        Err(Error::Usage(format!("upload target should be webdav:<url> or s3://<bucket>/<prefix>, got: {}", spec)))
+    }
+}
+
+// uploads every file under vault_path but the renderer's own state, returns how many files were sent,
+// timeout is how long a server gets to accept the connection and to answer, sending a big file can take longer
+#[cfg(feature = "network")]
+pub fn upload_vault(vault_path: &Path, target: &UploadTarget, timeout: Duration) -> Result<usize, Error> {
+    let mut files = Vec::new();
+    collect_files(vault_path, PathBuf::new(), &mut files)?;
+
+    let agent: ureq::Agent = ureq::Agent::config_builder()
+        .allow_non_standard_methods(true)
+        .timeout_connect(Some(timeout))
+        .timeout_recv_response(Some(timeout))
+        .timeout_recv_body(Some(timeout))
+        .build()
+        .into();
+
+    match target {
+        UploadTarget::WebDav { base_url } => {
+            let user = env::var("FAIR_RENDERER_WEBDAV_USER").ok();
+            let password = env::var("FAIR_RENDERER_WEBDAV_PASSWORD").unwrap_or_default();
+            let auth = user.map(|u| {
+                format!("Basic {}", base64::engine::general_purpose::STANDARD.encode(format!("{}:{}", u, password)))
+            });
+
+            // folders have to exist before files can be put in them
+            let mut made_dirs: Vec<String> = Vec::new();
+            for relative in &files {
+                let mut dir = String::new();
+                for part in relative.iter().rev().skip(1).collect::<Vec<_>>().into_iter().rev() {
+                    dir = format!("{}/{}", dir, uri_encode(&part.to_string_lossy()));
+                    if !made_dirs.contains(&dir) {
+                        webdav_mkcol(&agent, &format!("{}{}", base_url, dir), auth.as_deref())?;
+                        made_dirs.push(dir.clone());
+                    }
+                }
+
+                let url = format!("{}/{}", base_url, uri_path(relative));
+                let body = fs::read(vault_path.join(relative))?;
+                let mut request = Request::builder().method(Method::PUT).uri(&url);
+                if let Some(auth) = &auth {
+                    request = request.header("Authorization", auth);
+                }
+                send(&agent, request, body, &url).map_err(|e| e.into_error(&url))?;
+            }
+        },
+        UploadTarget::S3 { bucket, prefix } => {
+            let creds = S3Credentials::from_env()?;
+            for relative in &files {
+                let key = match prefix.is_empty() {
+                    true => uri_path(relative),
+                    false => format!("{}/{}", uri_encode_path(prefix), uri_path(relative)),
+                };
+                let body = fs::read(vault_path.join(relative))?;
+                s3_put(&agent, &creds, bucket, &key, body)?;
+            }
+        },
+    }
+
+    Ok(files.len())
+}
+
+#[cfg(not(feature = "network"))]
+pub fn upload_vault(_vault_path: &Path, _target: &UploadTarget, _timeout: Duration) -> Result<usize, Error> {
+    Err(crate::missing_feature("--upload", "network"))
+}
+
+fn collect_files(root: &Path, relative: PathBuf, files: &mut Vec<PathBuf>) -> Result<(), Error> {
+    let mut entries: Vec<fs::DirEntry> = fs::read_dir(root.join(&relative))?.collect::<Result<_, _>>()?;
+    entries.sort_by_key(|e| e.file_name());
+
+    for entry in entries {
+        let path = relative.join(entry.file_name());
+        // the manifest, lock and caches only mean something next to the vault they were written for
+        if path == Path::new(".fair_renderer") {
+            continue;
+        }
+        if entry.file_type()?.is_dir() {
+            collect_files(root, path, files)?;
+        } else {
+            files.push(path);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "network")]
+fn webdav_mkcol(agent: &ureq::Agent, url: &str, auth: Option<&str>) -> Result<(), Error> {
+    let mut request = Request::builder()
+        .method(Method::from_bytes(b"MKCOL").unwrap())
+        .uri(url);
+    if let Some(auth) = auth {
+        request = request.header("Authorization", auth);
+    }
+
+    match send(agent, request, Vec::new(), url) {
+        // 405 means the folder is already there
+        Err(SendError::Status(405)) => Ok(()),
+        other => other.map_err(|e| e.into_error(url)),
+    }
+}
+
+enum SendError {
+    Status(u16),
+    Other(String),
+}
+
+impl SendError {
+    fn into_error(self, url: &str) -> Error {
+        match self {
+            SendError::Status(code) => Error::Other(format!("upload failed for {}: server returned {}", url, code)),
+            SendError::Other(e) => Error::Other(format!("upload failed for {}: {}", url, e)),
+        }
+    }
+}
+
+#[cfg(feature = "network")]
+fn send(agent: &ureq::Agent, request: ureq::http::request::Builder, body: Vec<u8>, url: &str) -> Result<(), SendError> {
+    term::trace(&format!("sending {} bytes to {}", body.len(), url));
+
+    let request = match request.body(body) {
+        Ok(request) => request,
+        Err(e) => return Err(SendError::Other(format!("invalid request for {}: {}", url, e))),
+    };
+
+    match agent.run(request) {
+        Ok(_) => Ok(()),
+        Err(ureq::Error::StatusCode(code)) => Err(SendError::Status(code)),
+        Err(e) => Err(SendError::Other(e.to_string())),
+    }
+}
+
+pub struct S3Credentials {
+    pub access_key: String,
+    pub secret_key: String,
+    pub session_token: Option<String>,
+    pub region: String,
+    pub endpoint: Option<String>,
+}
+
+impl S3Credentials {
+    fn from_env() -> Result<S3Credentials, Error> {
+        let var = |name: &str| env::var(name).map_err(|_| Error::Other(format!("s3 upload needs {} set", name)));
+
+        Ok(S3Credentials {
+            access_key: var("AWS_ACCESS_KEY_ID")?,
+            secret_key: var("AWS_SECRET_ACCESS_KEY")?,
+            session_token: env::var("AWS_SESSION_TOKEN").ok(),
+            region: env::var("AWS_REGION").unwrap_or_else(|_| "us-east-1".to_string()),
+            endpoint: env::var("AWS_ENDPOINT_URL").ok().map(|e| e.trim_end_matches('/').to_string()),
+        })
+    }
+}
+
+// path style put signed with aws signature v4, key is expected to already be uri encoded
+#[cfg(feature = "network")]
+fn s3_put(agent: &ureq::Agent, creds: &S3Credentials, bucket: &str, key: &str, body: Vec<u8>) -> Result<(), Error> {
+    let endpoint = match &creds.endpoint {
+        Some(endpoint) => endpoint.clone(),
+        None => format!("https://s3.{}.amazonaws.com", creds.region),
+    };
+    let host = endpoint.split("://").nth(1).unwrap_or(&endpoint).to_string();
+    let canonical_uri = format!("/{}/{}", uri_encode(bucket), key);
+    let url = format!("{}{}", endpoint, canonical_uri);
+
+    let (_, amz_date) = utc_timestamp();
+    let payload_hash = hex(&Sha256::digest(&body));
+
+    let mut headers = vec![
+        ("host", host),
+        ("x-amz-content-sha256", payload_hash.clone()),
+        ("x-amz-date", amz_date.clone()),
+    ];
+    if let Some(token) = &creds.session_token {
+        headers.push(("x-amz-security-token", token.clone()));
+    }
+
+    let authorization = s3_authorization(creds, "PUT", &canonical_uri, &headers, &payload_hash, &amz_date);
+
+    let mut request = Request::builder().method(Method::PUT).uri(&url);
+    for (k, v) in headers.iter().filter(|(k, _)| *k != "host") {
+        request = request.header(*k, v);
+    }
+    request = request.header("Authorization", authorization);
+
+    send(agent, request, body, &url).map_err(|e| e.into_error(&url))
+}
+
+// the Authorization header for a request without a query string, headers are lowercase and sorted
+// and include host, amz_date is the x-amz-date one
+#[cfg(feature = "network")]
+pub fn s3_authorization(creds: &S3Credentials, method: &str, canonical_uri: &str, headers: &[(&str, String)], payload_hash: &str, amz_date: &str) -> String {
+    let date = &amz_date[..8];
+    let canonical_headers: String = headers.iter().map(|(k, v)| format!("{}:{}\n", k, v.trim())).collect();
+    let signed_headers: Vec<&str> = headers.iter().map(|(k, _)| *k).collect();
+    let signed_headers = signed_headers.join(";");
+
+    let canonical_request = format!("{}\n{}\n\n{}\n{}\n{}", method, canonical_uri, canonical_headers, signed_headers, payload_hash);
+    let scope = format!("{}/{}/s3/aws4_request", date, creds.region);
+    let string_to_sign = format!("AWS4-HMAC-SHA256\n{}\n{}\n{}", amz_date, scope, hex(&Sha256::digest(canonical_request.as_bytes())));
+
+    let mut key = hmac_sha256(format!("AWS4{}", creds.secret_key).as_bytes(), date.as_bytes());
+    for part in [creds.region.as_str(), "s3", "aws4_request"] {
+        key = hmac_sha256(&key, part.as_bytes());
+    }
+    let signature = hex(&hmac_sha256(&key, string_to_sign.as_bytes()));
+
+    format!("AWS4-HMAC-SHA256 Credential={}/{}, SignedHeaders={}, Signature={}", creds.access_key, scope, signed_headers, signature)
+}
+
+#[cfg(feature = "network")]
+fn hmac_sha256(key: &[u8], data: &[u8]) -> Vec<u8> {
+    let mut mac = Hmac::<Sha256>::new_from_slice(key).expect("hmac accepts any key length");
+    mac.update(data);
+    mac.finalize().into_bytes().to_vec()
+}
+
+fn hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+fn uri_path(relative: &Path) -> String {
+    let parts: Vec<String> = relative.iter().map(|p| uri_encode(&p.to_string_lossy())).collect();
+    parts.join("/")
+}
+
+fn uri_encode_path(path: &str) -> String {
+    let parts: Vec<String> = path.split('/').map(uri_encode).collect();
+    parts.join("/")
+}
+
+// percent encodes everything except the unreserved characters, which is what s3 signing expects
+pub fn uri_encode(segment: &str) -> String {
+    let mut encoded = String::new();
+    for b in segment.bytes() {
+        match b {
+            b'A'..=b'Z' | b'a'..=b'z' | b'0'..=b'9' | b'-' | b'.' | b'_' | b'~' => encoded.push(b as char),
+            _ => encoded.push_str(&format!("%{:02X}", b)),
+        }
+    }
+    encoded
+}
+
+// returns (YYYYMMDD, YYYYMMDDTHHMMSSZ) for now in utc
+pub fn utc_timestamp() -> (String, String) {
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = (secs / 86400, secs % 86400);
+
+    // days since epoch to civil date, from Howard Hinnant's date algorithms
+    let z = days as i64 + 719468;
+    let era = z.div_euclid(146097);
+    let doe = z - era * 146097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+
+    let date = format!("{:04}{:02}{:02}", year, month, day);
+    let time = format!("{:02}{:02}{:02}", rem / 3600, (rem % 3600) / 60, rem % 60);
+    (date.clone(), format!("{}T{}Z", date, time))
+}
@@ -0,0 +1,180 @@
+use crate::{Error, jobs};
+
+// the fields a fair entry is read from that should hold plain text
+const TEXT_FIELDS: &[&str] = &["company_description", "location_name", "work_authorization_requirements", "job_titles"];
+const EMPLOYER_TEXT_FIELDS: &[&str] = &["website", "logo_url"];
+const NAME_LISTS: &[&str] = &["job_types", "majors", "school_years"];
+
+fn kind(value: &serde_json::Value) -> &'static str {
+    match value {
+        serde_json::Value::Null => "null",
+        serde_json::Value::Bool(_) => "a boolean",
+        serde_json::Value::Number(_) => "a number",
+        serde_json::Value::String(_) => "a string",
+        serde_json::Value::Array(_) => "an array",
+        serde_json::Value::Object(_) => "an object",
+    }
+}
+
+fn check_text(value: &serde_json::Value, path: &str, problems: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(_) => {},
+        serde_json::Value::Null => problems.push(format!("missing {}", path)),
+        other => problems.push(format!("{} should be a string, got {}", path, kind(other))),
+    }
+}
+
+// an array of {"name": ..} objects
+fn check_names(value: &serde_json::Value, path: &str, problems: &mut Vec<String>) {
+    match value {
+        serde_json::Value::Array(entries) => for (i, entry) in entries.iter().enumerate() {
+            check_text(&entry["name"], &format!("{}[{}].name", path, i), problems);
+        },
+        serde_json::Value::Null => problems.push(format!("missing {}", path)),
+        other => problems.push(format!("{} should be an array, got {}", path, kind(other))),
+    }
+}
+
+fn check_sessions(value: &serde_json::Value, problems: &mut Vec<String>) {
+    let path = "attending_career_fair_sessions";
+    match value {
+        serde_json::Value::Array(sessions) => for (i, session) in sessions.iter().enumerate() {
+            check_text(&session["display_name"], &format!("{}[{}].display_name", path, i), problems);
+            for key in ["start_date_time", "end_date_time"] {
+                if !session[key].is_null() && !session[key].is_string() {
+                    problems.push(format!("{}[{}].{} should be a string, got {}", path, i, key, kind(&session[key])));
+                }
+            }
+        },
+        serde_json::Value::Null => problems.push(format!("missing {}", path)),
+        other => problems.push(format!("{} should be an array, got {}", path, kind(other))),
+    }
+}
+
+fn check_fair_entry(entry: &serde_json::Value) -> Vec<String> {
+    let mut problems = Vec::new();
+    if !entry.is_object() {
+        problems.push(format!("entry should be an object, got {}", kind(entry)));
+        return problems;
+    }
+    match &entry["employer"] {
+        serde_json::Value::Object(_) => {
+            check_text(&entry["employer"]["name"], "employer.name", &mut problems);
+            for key in EMPLOYER_TEXT_FIELDS {
+                check_text(&entry["employer"][key], &format!("employer.{}", key), &mut problems);
+            }
+            if !entry["employer"]["id"].is_null() && !entry["employer"]["id"].is_u64() {
+                problems.push(format!("employer.id should be a number, got {}", kind(&entry["employer"]["id"])));
+            }
+        },
+        serde_json::Value::Null => problems.push("missing employer".to_string()),
+        other => problems.push(format!("employer should be an object, got {}", kind(other))),
+    }
+    for key in TEXT_FIELDS {
+        check_text(&entry[key], key, &mut problems);
+    }
+    for key in NAME_LISTS {
+        check_names(&entry[key], key, &mut problems);
+    }
+    check_sessions(&entry["attending_career_fair_sessions"], &mut problems);
+    problems
+}
+
+// an empty list is fine for the render but almost always means the export lost something, so it's
+// only worth mentioning, never a reason to leave the entry out
+fn empty_lists(entry: &serde_json::Value) -> Vec<String> {
+    NAME_LISTS.iter().chain(&["attending_career_fair_sessions"])
+        .filter(|key| entry[**key].as_array().is_some_and(Vec::is_empty))
+        .map(|key| format!("{} is empty", key))
+        .collect()
+}
+
+fn entry_problems(entry: &serde_json::Value, is_job_search: bool) -> Vec<String> {
+    match is_job_search {
+        true => check_job_entry(entry),
+        false => check_fair_entry(entry),
+    }
+}
+
+fn results(json_data: &serde_json::Value) -> Result<&Vec<serde_json::Value>, Error> {
+    match &json_data["results"] {
+        serde_json::Value::Array(entries) => Ok(entries),
+        serde_json::Value::Null => Err(Error::InvalidInput("input data has no results array".to_string())),
+        other => Err(Error::InvalidInput(format!("results should be an array, got {}", kind(other)))),
+    }
+}
+
+fn parse(input_data: &[u8]) -> Result<serde_json::Value, Error> {
+    if input_data.iter().all(u8::is_ascii_whitespace) {
+        return Err(Error::InvalidInput("input file is empty".to_string()));
+    }
+    match serde_json::from_slice(input_data) {
+        Ok(data) => Ok(data),
+        Err(source) => Err(Error::Json { what: "input data".to_string(), source }),
+    }
+}
+
+// postings only need a title and an employer, everything else has a fallback
+fn check_job_entry(entry: &serde_json::Value) -> Vec<String> {
+    let job = match entry["job"].is_object() {
+        true => &entry["job"],
+        false => entry,
+    };
+    let mut problems = Vec::new();
+    check_text(&job["title"], "title", &mut problems);
+    if !job["employer"]["name"].is_string() && !job["employer_name"].is_string() {
+        problems.push("missing employer.name or employer_name".to_string());
+    }
+    problems
+}
+
+fn entry_name(entry: &serde_json::Value) -> Option<&str> {
+    entry["employer"]["name"].as_str()
+        .or(entry["job"]["employer"]["name"].as_str())
+        .or(entry["employer_name"].as_str())
+        .or(entry["job"]["employer_name"].as_str())
+}
+
+// every problem in the input rather than just the first, one line each with the entry's index and
+// company so it can be found in the json, the input itself only errors when it can't be read at all
+pub fn validate(input_data: &[u8]) -> Result<Vec<String>, Error> {
+    let json_data = parse(input_data)?;
+    let entries = results(&json_data)?;
+    if entries.is_empty() {
+        return Ok(vec!["results is empty".to_string()]);
+    }
+
+    let is_job_search = jobs::parse_jobs(input_data).is_some();
+    let mut lines = Vec::new();
+    for (i, entry) in entries.iter().enumerate() {
+        let mut problems = entry_problems(entry, is_job_search);
+        if !is_job_search {
+            problems.extend(empty_lists(entry));
+        }
+        let name = entry_name(entry).unwrap_or("unnamed");
+        lines.extend(problems.iter().map(|problem| format!("entry {} ({}): {}", i, name, problem)));
+    }
+    Ok(lines)
+}
+
+// (entry, why it was left out)
+pub type Skipped = Vec<(String, String)>;
+
+// --skip-invalid, the input without the entries that have a missing or mistyped field so the rest
+// render even with --strict, along with which ones were left out and why
+pub fn skip_invalid(input_data: &[u8]) -> Result<(Vec<u8>, Skipped), Error> {
+    let mut json_data = parse(input_data)?;
+    let is_job_search = jobs::parse_jobs(input_data).is_some();
+
+    let mut skipped = Vec::new();
+    let mut kept = Vec::new();
+    for (i, entry) in results(&json_data)?.iter().enumerate() {
+        let problems = entry_problems(entry, is_job_search);
+        match problems.is_empty() {
+            true => kept.push(entry.clone()),
+            false => skipped.push((format!("entry {} ({})", i, entry_name(entry).unwrap_or("unnamed")), problems.join(", "))),
+        }
+    }
+    json_data["results"] = serde_json::Value::Array(kept);
+    Ok((serde_json::to_vec(&json_data).unwrap_or_default(), skipped))
+}
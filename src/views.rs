@@ -0,0 +1,191 @@
+use std::fs;
+use std::path::Path;
+
+use yaml_rust2::{Yaml, YamlLoader};
+
+use crate::labels::Labels;
+use crate::subset::{self, Filter};
+use crate::{CompanyEntry, Error, links, prep};
+
+// an index note the template asks for in its views.yaml, ex.
+//
+// Sponsorship-friendly SWE roles:
+//   filter: [international=sponsor, major=Computer Science]
+//   sort: booth
+//   group: session
+//   show: [booth, deadline]
+//
+// filters are written like export-subset's, sort takes a - in front to go from the highest, fields
+// the input doesn't have like Priority are read from the notes
+pub struct View {
+    pub name: String,
+    filters: Vec<Filter>,
+    sort: Option<(String, bool)>,
+    group: Option<String>,
+    show: Vec<String>,
+}
+
+fn strings(value: &Yaml) -> Option<Vec<String>> {
+    match value {
+        Yaml::Array(values) => values.iter().map(|v| v.as_str().map(|s| s.trim().to_string())).collect(),
+        Yaml::String(value) => Some(vec![value.trim().to_string()]),
+        Yaml::BadValue | Yaml::Null => Some(Vec::new()),
+        _ => None,
+    }
+}
+
+fn one(value: &Yaml) -> Option<Option<String>> {
+    match value {
+        Yaml::String(value) => Some(Some(value.trim().to_string())),
+        Yaml::BadValue | Yaml::Null => Some(None),
+        _ => None,
+    }
+}
+
+// the views the template declares, none when it has no views.yaml
+pub fn load(template_path: &Path) -> Result<Vec<View>, Error> {
+    let path = template_path.join("views.yaml");
+    let Ok(text) = fs::read_to_string(&path) else { return Ok(Vec::new()) };
+    let path = path.to_string_lossy();
+    let docs = match YamlLoader::load_from_str(&text) {
+        Ok(docs) => docs,
+        Err(e) => return Err(Error::Template(format!("invalid yaml in {}: {}", path, e))),
+    };
+    let Some(doc) = docs.first() else { return Ok(Vec::new()) };
+    let Some(hash) = doc.as_hash() else {
+        return Err(Error::Template(format!("{} should be a map of note name to its filter, sort, group and show", path)));
+    };
+
+    let mut views = Vec::new();
+    for (name, spec) in hash {
+        let Some(name) = name.as_str().map(str::trim).filter(|n| !n.is_empty()) else {
+            return Err(Error::Template(format!("{} has a view without a name", path)));
+        };
+        let invalid = |key: &str| Error::Template(format!("{}: {} of {} should be {}", path, key, name, match key {
+            "filter" | "show" => "a list",
+            _ => "a field name",
+        }));
+        if let Some(unknown) = spec.as_hash().into_iter().flatten().filter_map(|(k, _)| k.as_str())
+            .find(|k| !["filter", "sort", "group", "show"].contains(k)) {
+            return Err(Error::Template(format!("{}: unknown key {} in {}, expected filter, sort, group or show", path, unknown, name)));
+        }
+
+        let filters = strings(&spec["filter"]).ok_or_else(|| invalid("filter"))?;
+        // the notes are there by the time views are written, so note fields are fine to filter on
+        let filters = match subset::parse_filters(&filters, true) {
+            Ok(filters) => filters,
+            Err(e) => return Err(Error::Template(format!("{}: {} in {}", path, e, name))),
+        };
+        let sort = one(&spec["sort"]).ok_or_else(|| invalid("sort"))?
+            .map(|field| match field.strip_prefix('-') {
+                Some(field) => (field.trim().to_string(), true),
+                None => (field, false),
+            });
+        let group = one(&spec["group"]).ok_or_else(|| invalid("group"))?;
+        let show = strings(&spec["show"]).ok_or_else(|| invalid("show"))?;
+        views.push(View { name: name.to_string(), filters, sort, group, show });
+    }
+    Ok(views)
+}
+
+// every value a company has for a field, sessions and majors can have several
+fn values(company: &CompanyEntry, field: &str, companies_dir: Option<&Path>) -> Vec<String> {
+    let one = |value: Option<&String>| value.filter(|v| !v.is_empty()).cloned().into_iter().collect();
+    match field {
+        "name" => vec![company.name.clone()],
+        "booth" => one(company.booth.as_ref()),
+        "location" => one(Some(&company.location)),
+        "deadline" => one(company.deadline.as_ref()),
+        "industry" => one(company.industry.as_ref()),
+        "international" => one(Some(&company.work_authorization)),
+        "summary" => one(company.summary.as_ref()),
+        // the frontmatter names work too
+        "session" | "sessions" => company.attending_sessions.iter().map(|s| s.display_name.clone()).collect(),
+        "major" | "majors" => company.majors.clone(),
+        "job_type" | "job_types" => company.job_types.clone(),
+        "school_year" | "school_years" => company.school_years.clone(),
+        field => companies_dir.and_then(|dir| prep::note_field(dir, company, field))
+            .map(|value| value.trim_matches(['[', ']']).split(',').map(|v| v.trim().trim_matches('"').to_string()).filter(|v| !v.is_empty()).collect())
+            .unwrap_or_default(),
+    }
+}
+
+// numbers compare as numbers so a Priority of 10 comes after 9, a company without the field goes last
+fn compare(a: Option<&String>, b: Option<&String>, descending: bool) -> std::cmp::Ordering {
+    let ordering = match (a, b) {
+        (None, None) => return std::cmp::Ordering::Equal,
+        (None, Some(_)) => return std::cmp::Ordering::Greater,
+        (Some(_), None) => return std::cmp::Ordering::Less,
+        (Some(a), Some(b)) => match (a.parse::<f64>(), b.parse::<f64>()) {
+            (Ok(a), Ok(b)) => a.total_cmp(&b),
+            _ => a.to_lowercase().cmp(&b.to_lowercase()),
+        },
+    };
+    match descending {
+        true => ordering.reverse(),
+        false => ordering,
+    }
+}
+
+fn line(company: &CompanyEntry, show: &[String], companies_dir: Option<&Path>) -> String {
+    let mut line = format!("- {}", links::company_link(company));
+    for field in show {
+        let shown = values(company, field, companies_dir);
+        if !shown.is_empty() {
+            line.push_str(&format!(", {}", shown.join("; ")));
+        }
+    }
+    format!("{}\n", line)
+}
+
+impl View {
+    pub fn render(&self, companies: &[CompanyEntry], companies_dir: Option<&Path>, labels: &Labels) -> String {
+        let mut matched: Vec<&CompanyEntry> = companies.iter()
+            .filter(|c| subset::passes(c, &self.filters, companies_dir))
+            .collect();
+        matched.sort_by_key(|c| c.name.to_lowercase());
+        if let Some((field, descending)) = &self.sort {
+            // the first value is what it sorts by, the first session or major listed
+            matched.sort_by(|a, b| compare(
+                values(a, field, companies_dir).first(),
+                values(b, field, companies_dir).first(),
+                *descending,
+            ));
+        }
+
+        let mut text = format!("# {} ({})\n\n", self.name, matched.len());
+        let Some(group) = &self.group else {
+            for company in matched {
+                text.push_str(&line(company, &self.show, companies_dir));
+            }
+            return text;
+        };
+
+        // groups in the order they first come up, so a sorted view keeps its order across them
+        let mut groups: Vec<(String, Vec<&CompanyEntry>)> = Vec::new();
+        let mut ungrouped = Vec::new();
+        for company in matched {
+            let company_groups = values(company, group, companies_dir);
+            if company_groups.is_empty() {
+                ungrouped.push(company);
+            }
+            for name in company_groups {
+                match groups.iter_mut().find(|(g, _)| g.eq_ignore_ascii_case(&name)) {
+                    Some((_, members)) => members.push(company),
+                    None => groups.push((name, vec![company])),
+                }
+            }
+        }
+        if !ungrouped.is_empty() {
+            groups.push((labels.ungrouped.clone(), ungrouped));
+        }
+        for (name, members) in groups {
+            text.push_str(&format!("## {} ({})\n\n", name, members.len()));
+            for company in members {
+                text.push_str(&line(company, &self.show, companies_dir));
+            }
+            text.push('\n');
+        }
+        format!("{}\n", text.trim_end())
+    }
+}
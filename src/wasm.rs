@@ -0,0 +1,22 @@
+use wasm_bindgen::prelude::*;
+
+use crate::Fair;
+
+// there's no template folder to read in a browser, so the default template's fileClass is built in
+const FILE_CLASS: &[u8] = include_bytes!("../vault_templates/career_fair_2025_template/classes/company.md");
+
+// a pasted handshake response to the notes a new vault gets, a Map of their paths inside the vault to
+// their text ready to be zipped up, file_class is a template's classes/company.md to use instead of the
+// default one, the same as render_to_memory there are no logos, labels or field formats to pick
+#[wasm_bindgen]
+pub fn render(input: &[u8], file_class: Option<Vec<u8>>) -> Result<js_sys::Map, JsError> {
+    let fair = Fair::parse(input).map_err(|e| JsError::new(&e.to_string()))?;
+    let file_class = file_class.as_deref().unwrap_or(FILE_CLASS);
+    let files = crate::render_notes(&fair.input_data, file_class, None, &Vec::new()).map_err(|e| JsError::new(&e.to_string()))?;
+
+    let map = js_sys::Map::new();
+    for (path, text) in files {
+        map.set(&JsValue::from(path), &JsValue::from(text));
+    }
+    Ok(map)
+}
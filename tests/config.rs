@@ -0,0 +1,51 @@
+use std::fs;
+use std::path::PathBuf;
+use std::process::Command;
+
+#[test]
+fn command_line_wins_over_the_config() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dir = std::env::temp_dir().join(format!("fair_renderer_config_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("fair_renderer.toml"), "output = \"from_config\"\n\n[folder]\ncompanies = \"orgs\"\n").unwrap();
+
+    // -qovault is -q -o vault, only clap knows that
+    let rendered = Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .current_dir(&dir)
+        .arg("render")
+        .arg("-i").arg(root.join("tests/fixtures/fair.json"))
+        .arg("-t").arg(root.join("vault_templates/career_fair_2025_template"))
+        .arg("-qovault")
+        .env_remove("FAIR_RENDERER_OUTPUT")
+        .output()
+        .unwrap();
+    assert!(rendered.status.success(), "{}", String::from_utf8_lossy(&rendered.stderr));
+    assert!(dir.join("vault/orgs").is_dir());
+    assert!(!dir.join("from_config").exists());
+    let _ = fs::remove_dir_all(&dir);
+}
+
+#[test]
+fn global_flags_can_come_before_the_command() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let dir = std::env::temp_dir().join(format!("fair_renderer_global_first_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("defaults.toml"), "[folder]\ncompanies = \"orgs\"\n").unwrap();
+
+    let run = |args: &[&str]| Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .current_dir(&dir)
+        .args(args)
+        .env_remove("FAIR_RENDERER_OUTPUT")
+        .output()
+        .unwrap();
+    let input = root.join("tests/fixtures/fair.json").to_string_lossy().to_string();
+    let template = root.join("vault_templates/career_fair_2025_template").to_string_lossy().to_string();
+    let rendered = run(&["--config", "defaults.toml", "-q", "render", "-i", &input, "-t", &template, "-o", "vault"]);
+    assert!(rendered.status.success(), "{}", String::from_utf8_lossy(&rendered.stderr));
+    assert!(dir.join("vault/orgs").is_dir());
+    let validated = run(&["-v", "validate", &input]);
+    assert!(validated.status.success(), "{}", String::from_utf8_lossy(&validated.stderr));
+    let _ = fs::remove_dir_all(&dir);
+}
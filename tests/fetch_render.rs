@@ -0,0 +1,43 @@
+use std::fs;
+use std::path::PathBuf;
+
+use fair_renderer::test_support::{MockHandshake, TempVault, diff};
+
+// the fixture cut into two pages the way the api sends a bigger fair
+fn paged_fixture() -> Vec<Vec<u8>> {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fair: serde_json::Value = serde_json::from_slice(&fs::read(root.join("tests/fixtures/fair.json")).unwrap()).unwrap();
+    let results = fair["results"].as_array().unwrap();
+    let (first, second) = results.split_at(results.len() / 2);
+    [first, second].iter().map(|results| {
+        let mut page = fair.clone();
+        page["results"] = serde_json::Value::Array(results.to_vec());
+        page["total_pages"] = serde_json::json!(2);
+        serde_json::to_vec(&page).unwrap()
+    }).collect()
+}
+
+#[test]
+fn fetched_pages_render_like_the_saved_export() {
+    let server = MockHandshake::start(paged_fixture(), Some("secret")).unwrap();
+    let url = server.url("/stu/career_fairs/1/employers");
+
+    let fetched = TempVault::new("fetched");
+    fetched.render(&["--fetch", &url, "--token", "secret", "--no-build-info"]).unwrap();
+    assert_eq!(server.requests(), vec!["/stu/career_fairs/1/employers", "/stu/career_fairs/1/employers?page=2"]);
+
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let saved = TempVault::new("saved");
+    saved.render(&["-i", &root.join("tests/fixtures/fair.json").to_string_lossy(), "--no-build-info"]).unwrap();
+    assert!(fetched.files().contains_key("companies/Company 59b40fe8.md"));
+    assert_eq!(diff(&saved.files(), &fetched.files()), Vec::<String>::new());
+}
+
+#[test]
+fn fetch_without_the_token_fails() {
+    let server = MockHandshake::start(paged_fixture(), Some("secret")).unwrap();
+    let vault = TempVault::new("unauthorized");
+    let error = vault.render(&["--fetch", &server.url("/employers"), "--token", "wrong"]).unwrap_err();
+    assert!(error.to_string().contains("401"), "{}", error);
+    assert!(!vault.path.exists());
+}
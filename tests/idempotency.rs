@@ -0,0 +1,50 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+fn render(output: &Path, extra: &[&str]) -> Output {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .arg("-i").arg(root.join("tests/fixtures/fair.json"))
+        .arg("-t").arg(root.join("vault_templates/career_fair_2025_template"))
+        .arg("-o").arg(output)
+        .args(extra)
+        .env_remove("FAIR_RENDERER_INPUT")
+        .output()
+        .unwrap()
+}
+
+fn read_vault(dir: &Path, files: &mut Vec<(PathBuf, String)>) {
+    for entry in fs::read_dir(dir).unwrap().flatten() {
+        match entry.path().is_dir() {
+            true => read_vault(&entry.path(), files),
+            false => files.push((entry.path(), fs::read_to_string(entry.path()).unwrap_or_default())),
+        }
+    }
+    files.sort();
+}
+
+#[test]
+fn rendering_twice_is_a_no_op() {
+    let output = std::env::temp_dir().join(format!("fair_renderer_idempotency_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&output);
+
+    assert!(render(&output, &[]).status.success());
+    let mut first = Vec::new();
+    read_vault(&output, &mut first);
+
+    assert!(render(&output, &[]).status.success());
+    assert!(render(&output, &["--update"]).status.success());
+    let mut second = Vec::new();
+    read_vault(&output, &mut second);
+    assert_eq!(first, second);
+
+    assert!(render(&output, &["--check"]).status.success());
+
+    let note = output.join("companies/Company 59b40fe8.md");
+    let text = fs::read_to_string(&note).unwrap().replace("website: https://example.com/", "website: https://example.org/");
+    fs::write(&note, text).unwrap();
+    assert!(!render(&output, &["--check"]).status.success());
+
+    fs::remove_dir_all(&output).unwrap();
+}
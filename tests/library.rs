@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use fair_renderer::test_support::TempVault;
+use fair_renderer::{CompanyEntry, Fair, Hook, VaultRenderer};
+
+#[test]
+fn the_library_renders_what_the_command_line_does() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fair = Fair::read(&root.join("tests/fixtures/fair.json")).unwrap();
+    assert!(fair.companies.iter().any(|c| c.name == "Company 59b40fe8"));
+
+    let renderer = VaultRenderer {
+        template: Some(root.join("vault_templates/career_fair_2025_template")),
+        build_info: false,
+        ..VaultRenderer::default()
+    };
+    let from_library = TempVault::new("library");
+    let report = renderer.render(&fair, &from_library.path).unwrap();
+    assert_eq!(report.companies, fair.companies.len());
+    assert!(report.written.iter().any(|path| path == "companies/Company 59b40fe8.md"));
+
+    let from_cli = TempVault::new("cli");
+    from_cli.render(&["-i", &root.join("tests/fixtures/fair.json").to_string_lossy(), "--no-build-info"]).unwrap();
+    assert_eq!(fair_renderer::test_support::diff(&from_cli.files(), &from_library.files()), Vec::<String>::new());
+
+    // rendering the same fair again finds the vault up to date, an update leaves the notes as they are
+    assert!(renderer.render(&fair, &from_library.path).unwrap().written.is_empty());
+    let updater = VaultRenderer { update: true, ..renderer };
+    let report = updater.render(&fair, &from_library.path).unwrap();
+    assert!(!report.written.iter().any(|path| path.starts_with("companies/")), "{:?}", report.written);
+}
+
+struct Visited;
+
+impl Hook for Visited {
+    fn company(&self, company: &mut CompanyEntry) {
+        company.location = company.location.to_uppercase();
+    }
+
+    fn note(&self, path: &str, text: &mut String) {
+        if path.starts_with("companies/") && !text.contains("## Visited") {
+            text.push_str("\n## Visited\n- [ ] stopped by the booth\n");
+        }
+    }
+}
+
+#[test]
+fn hooks_change_companies_and_notes() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let fair = Fair::read(&root.join("tests/fixtures/fair.json")).unwrap();
+    let mut renderer = VaultRenderer {
+        template: Some(root.join("vault_templates/career_fair_2025_template")),
+        ..VaultRenderer::default()
+    };
+    renderer.hooks.push(Arc::new(Visited));
+
+    let vault = TempVault::new("hooks");
+    renderer.render(&fair, &vault.path).unwrap();
+    let note = &vault.files()["companies/Company 59b40fe8.md"];
+    assert!(note.contains("location: 6020 MAIN STREET, SPRINGFIELD"), "{}", note);
+    assert!(note.ends_with("## Visited\n- [ ] stopped by the booth\n"), "{}", note);
+
+    // the section the hook added is already there on update, so the note is left as it is
+    let updater = VaultRenderer { update: true, ..renderer };
+    let report = updater.render(&fair, &vault.path).unwrap();
+    assert!(!report.written.iter().any(|path| path.starts_with("companies/")), "{:?}", report.written);
+    assert!(updater.render_to_memory(&fair).unwrap()["companies/Company 59b40fe8.md"].contains("## Visited"));
+}
@@ -0,0 +1,149 @@
+use std::fs;
+use std::path::PathBuf;
+
+// inputs that used to panic while slicing or unwrapping, they have to come back as errors now
+#[test]
+fn malformed_fileclass_is_an_error() {
+    for input in [&b""[..], b"-", b"---", b"---\n", b"\n", b"\r", b"---\n---", b"\xff\xfe\n\xff", b"---\nfields: 3\n---"] {
+        assert!(fair_renderer::parse_fileclass(input).is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn template_fileclass_parses() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let bytes = fs::read(root.join("vault_templates/career_fair_2025_template/classes/company.md")).unwrap();
+    let (user_fields, _) = fair_renderer::parse_fileclass(&bytes).unwrap();
+    assert_eq!(user_fields, ["Viewed", "Priority"]);
+}
+
+#[test]
+fn malformed_input_is_an_error() {
+    for input in [&b""[..], b"{", b"[]", b"{}", b"{\"results\": 3}", b"{\"results\": [1]}", b"{\"results\": [{}]}", b"\xff"] {
+        assert!(fair_renderer::parse_input(input).is_err(), "{:?}", input);
+    }
+}
+
+#[test]
+fn empty_input_says_so() {
+    for input in [&b""[..], b"  \n"] {
+        assert_eq!(fair_renderer::parse_input(input).unwrap_err().to_string(), "input file is empty");
+    }
+}
+
+#[test]
+fn empty_results_parse() {
+    assert!(fair_renderer::parse_input(b"{\"results\": []}").unwrap().is_empty());
+}
+
+// excel on windows saves csv and json as windows-1252, é is the single byte 0xe9 and the quotes 0x93 0x94
+#[test]
+fn windows_1252_input_parses() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let input = fs::read(root.join("tests/fixtures/fair.json")).unwrap();
+    let input = String::from_utf8(input).unwrap().replacen("Company 59b40fe8", "Caf\u{e9} \u{201c}59b40fe8\u{201d}", 1);
+    let input: Vec<u8> = input.chars().map(|c| match c {
+        '\u{201c}' => 0x93,
+        '\u{201d}' => 0x94,
+        c => c as u8,
+    }).collect();
+    let companies = fair_renderer::parse_input(&input).unwrap();
+    assert_eq!(companies[0].name, "Café “59b40fe8”");
+}
+
+// notepad on windows starts utf-8 files with a byte order mark
+#[test]
+fn byte_order_mark_is_skipped() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let input = fs::read(root.join("tests/fixtures/fair.json")).unwrap();
+    let with_bom = [b"\xef\xbb\xbf".as_slice(), &input].concat();
+    let fair = fair_renderer::Fair::parse(&with_bom).unwrap();
+    assert_eq!(fair.companies.len(), fair_renderer::Fair::parse(&input).unwrap().companies.len());
+}
+
+#[test]
+fn yaml_list_of_entries_renders() {
+    let dir = std::env::temp_dir().join(format!("fair_renderer_yaml_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("list.yaml"), "# kept by hand\n- employer:\n    name: Acme\n  location_name: Springfield\n  majors:\n    - name: Physics\n").unwrap();
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .args(["-q", "-i", "list.yaml", "-o", "vault", "-t"])
+        .arg(root.join("vault_templates/career_fair_2025_template"))
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let note = fs::read_to_string(dir.join("vault/companies/Acme.md")).unwrap_or_default();
+    let _ = fs::remove_dir_all(&dir);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(note.contains("location: Springfield\nmajors: Physics\n"), "{}", note);
+}
+
+#[test]
+fn missing_field_says_which_company() {
+    let error = fair_renderer::parse_input(b"{\"results\": [{\"employer\": {\"name\": \"Acme\"}, \"company_description\": 3}]}").unwrap_err();
+    match &error {
+        fair_renderer::Error::MissingField { index, company, field, .. } => {
+            assert_eq!((*index, company.as_deref(), field.as_str()), (0, Some("Acme"), "company_description"));
+        },
+        _ => panic!("expected a missing field, got {}", error),
+    }
+    assert!(error.to_string().starts_with("entry 0 (Acme): company_description: invalid type"), "{}", error);
+}
+
+#[test]
+fn unnamed_entry_error_gives_its_index() {
+    let error = fair_renderer::parse_input(b"{\"results\": [{\"employer\": {}}]}").unwrap_err();
+    assert_eq!(error.to_string(), "entry 0: employer.name: missing");
+}
+
+#[test]
+fn missing_input_file_is_named() {
+    let dir = std::env::temp_dir().join(format!("fair_renderer_missing_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .args(["render", "-i", "not_there.json", "-o", "vault"])
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let _ = fs::remove_dir_all(&dir);
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("failed reading not_there.json"), "{}", stderr);
+    assert_eq!(output.status.code(), Some(3));
+}
+
+#[test]
+fn bad_export_spec_is_a_usage_error() {
+    let dir = std::env::temp_dir().join(format!("fair_renderer_bad_export_{}", std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let output = std::process::Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+        .args(["render", "--export", "bogus:x", "-o", "vault", "-i"])
+        .arg(root.join("tests/fixtures/fair.json"))
+        .arg("-t").arg(root.join("vault_templates/career_fair_2025_template"))
+        .current_dir(&dir)
+        .output()
+        .unwrap();
+    let rendered = dir.join("vault").exists();
+    let _ = fs::remove_dir_all(&dir);
+    assert_eq!(output.status.code(), Some(2), "{}", String::from_utf8_lossy(&output.stderr));
+    assert!(!rendered);
+}
+
+// the renderer's fields get ids the fileClass doesn't use yet, even when a to z are all taken
+#[test]
+fn new_fileclass_fields_get_unused_ids() {
+    let mut fileclass = String::from("---\nfields:\n");
+    for c in 'a'..='z' {
+        fileclass.push_str(&format!("  - name: Mine {}\n    type: Input\n    options: {{}}\n    path: \"\"\n    id: {}bcdef\n", c, c));
+    }
+    fileclass.push_str("version: \"2.29\"\n---\n");
+    let (_, text) = fair_renderer::parse_fileclass(fileclass.as_bytes()).unwrap();
+    let ids: Vec<&str> = text.lines().filter_map(|line| line.trim().strip_prefix("id: ")).collect();
+    assert!(ids.len() > 26, "{}", text);
+    assert!(ids.iter().all(|id| id.len() == 6 && id.chars().all(|c| c.is_ascii_alphanumeric())), "{:?}", ids);
+    assert_eq!(ids.iter().collect::<std::collections::BTreeSet<_>>().len(), ids.len(), "{:?}", ids);
+}
@@ -0,0 +1,57 @@
+use std::fs;
+use std::path::PathBuf;
+
+// each generated file gets its own snapshot so a diff points straight at the note that changed
+fn assert_render(fixture: &str) {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let input = fs::read(root.join("tests/fixtures").join(fixture)).unwrap();
+    let template = root.join("vault_templates/career_fair_2025_template");
+
+    let files = fair_renderer::render_to_memory(&input, &template).unwrap();
+    let prefix = fixture.trim_end_matches(".json");
+
+    let names: Vec<&str> = files.keys().map(String::as_str).collect();
+    insta::assert_snapshot!(format!("{}__files", prefix), names.join("\n"));
+
+    for (path, text) in &files {
+        let name = path.trim_end_matches(".md").replace(['/', ' '], "_");
+        insta::assert_snapshot!(format!("{}__{}", prefix, name), text);
+    }
+}
+
+#[test]
+fn fair_export() {
+    assert_render("fair.json");
+}
+
+#[test]
+fn job_search_export() {
+    assert_render("job_search.json");
+}
+
+#[test]
+fn render_is_deterministic() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let input = fs::read(root.join("tests/fixtures/fair.json")).unwrap();
+    let template = root.join("vault_templates/career_fair_2025_template");
+
+    let first = fair_renderer::render_to_memory(&input, &template).unwrap();
+    let second = fair_renderer::render_to_memory(&input, &template).unwrap();
+    assert_eq!(first, second);
+}
+
+#[test]
+fn long_description_moves_to_its_own_note() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let mut input: serde_json::Value = serde_json::from_slice(&fs::read(root.join("tests/fixtures/fair.json")).unwrap()).unwrap();
+    let brochure = "We care deeply about our people and our planet. ".repeat(2000);
+    input["results"][0]["company_description"] = brochure.clone().into();
+    let template = root.join("vault_templates/career_fair_2025_template");
+
+    let files = fair_renderer::render_to_memory(&serde_json::to_vec(&input).unwrap(), &template).unwrap();
+    let (moved, full) = files.iter().find(|(path, _)| path.starts_with("attachments/descriptions/")).unwrap();
+    assert!(full.contains(brochure.trim()));
+    let note = &files[&format!("companies/{}", moved.trim_start_matches("attachments/descriptions/"))];
+    assert!(note.len() < brochure.len() / 2);
+    assert!(note.contains(&format!("[[{}|Full description]]", moved.trim_end_matches(".md"))));
+}
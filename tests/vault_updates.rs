@@ -0,0 +1,225 @@
+use std::fs;
+use std::path::PathBuf;
+
+use fair_renderer::test_support::TempVault;
+use fair_renderer::{Fair, VaultRenderer};
+
+fn fixture() -> serde_json::Value {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    serde_json::from_slice(&fs::read(root.join("tests/fixtures/fair.json")).unwrap()).unwrap()
+}
+
+// the fixture with one of its entries changed
+fn edited(edit: impl Fn(&mut serde_json::Value)) -> Fair {
+    let mut fair = fixture();
+    for entry in fair["results"].as_array_mut().unwrap() {
+        if entry["employer"]["name"] == "Company 59b40fe8" {
+            edit(entry);
+        }
+    }
+    Fair::parse(&serde_json::to_vec(&fair).unwrap()).unwrap()
+}
+
+fn renderer(update: bool) -> VaultRenderer {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    VaultRenderer {
+        template: Some(root.join("vault_templates/career_fair_2025_template")),
+        update,
+        build_info: false,
+        ..VaultRenderer::default()
+    }
+}
+
+#[test]
+fn renamed_company_keeps_its_index_notes_up_to_date() {
+    let vault = TempVault::new("rename");
+    renderer(false).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+
+    let renamed = edited(|entry| entry["employer"]["name"] = "Company Renamed".into());
+    let report = renderer(true).render(&renamed, &vault.path).unwrap();
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    let files = vault.files();
+    assert!(files.contains_key("companies/Company Renamed.md"));
+    assert!(!files.contains_key("companies/Company 59b40fe8.md"));
+    assert!(files["Deadlines.md"].contains("[[Company Renamed]]"), "{}", files["Deadlines.md"]);
+
+    // the rewritten index notes aren't taken for the user's edits on the next update either
+    let moved = edited(|entry| {
+        entry["employer"]["name"] = "Company Renamed".into();
+        entry["apply_by"] = "2025-11-02T05:00:00.000Z".into();
+    });
+    let report = renderer(true).render(&moved, &vault.path).unwrap();
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    assert!(vault.files()["Deadlines.md"].contains("2025-11-02"));
+}
+
+#[test]
+fn edit_to_a_renamed_note_is_still_the_users() {
+    let vault = TempVault::new("rename_edited");
+    renderer(false).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+    let old_path = vault.path.join("companies/Company 59b40fe8.md");
+    let note = fs::read_to_string(&old_path).unwrap().replace("### Description\n\n", "### Description\n\nmy own words\n");
+    fs::write(&old_path, &note).unwrap();
+
+    // the edit moves with the note and is still spotted under its new name
+    let renamed = edited(|entry| entry["employer"]["name"] = "Company Renamed".into());
+    let report = renderer(true).render(&renamed, &vault.path).unwrap();
+    let note_path = vault.path.join("companies/Company Renamed.md");
+    assert!(report.skipped.iter().any(|(path, _)| path.ends_with("Company Renamed.md")), "{:?}", report.skipped);
+    assert!(fs::read_to_string(&note_path).unwrap().contains("my own words"));
+}
+
+#[test]
+fn skipped_rename_leaves_links_alone() {
+    let vault = TempVault::new("rename_taken");
+    renderer(false).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+    // a note of the user's already has the new name
+    fs::write(vault.path.join("companies/Company Renamed.md"), "mine\n").unwrap();
+    fs::create_dir_all(vault.path.join("follow_ups")).unwrap();
+    fs::write(vault.path.join("follow_ups/Company 59b40fe8 - Follow-up.md"), "about [[Company 59b40fe8]]\n").unwrap();
+
+    let renamed = edited(|entry| entry["employer"]["name"] = "Company Renamed".into());
+    renderer(true).render(&renamed, &vault.path).unwrap();
+    let files = vault.files();
+    assert_eq!(files["companies/Company Renamed.md"], "mine\n");
+    assert!(files.contains_key("companies/Company 59b40fe8.md"));
+    assert_eq!(files["follow_ups/Company 59b40fe8 - Follow-up.md"], "about [[Company 59b40fe8]]\n");
+}
+
+#[test]
+fn roster_history_matches_custom_note_names() {
+    let vault = TempVault::new("roster");
+    let custom = |update: bool| VaultRenderer { note_name: Some("Fair - {{name}}".to_string()), ..renderer(update) };
+    let mut fair = fixture();
+    custom(false).render(&Fair::parse(&serde_json::to_vec(&fair).unwrap()).unwrap(), &vault.path).unwrap();
+
+    fair["results"].as_array_mut().unwrap().retain(|entry| entry["employer"]["name"] != "Company 59b40fe8");
+    custom(true).render(&Fair::parse(&serde_json::to_vec(&fair).unwrap()).unwrap(), &vault.path).unwrap();
+    let history = &vault.files()["Fair - Roster History.md"];
+    assert!(history.contains("- 1 companies dropped out: Fair - Company 59b40fe8\n"), "{}", history);
+    assert!(!history.contains("new companies"), "{}", history);
+}
+
+#[test]
+fn lint_passes_a_fresh_vault() {
+    let vault = TempVault::new("lint");
+    vault.render(&["-i", &PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fair.json").to_string_lossy(), "--link-fields"]).unwrap();
+    fs::write(vault.path.join("board.canvas"), "{}").unwrap();
+
+    let lint = std::process::Command::new(env!("CARGO_BIN_EXE_fair_renderer")).arg("lint").arg(&vault.path).arg("--fix").output().unwrap();
+    assert!(lint.status.success(), "{}", String::from_utf8_lossy(&lint.stdout));
+    let files = vault.files();
+    // the template's views.yaml stays in the template, the user's own files are left alone
+    assert!(!files.contains_key("views.yaml") && files.contains_key("board.canvas"), "{:?}", files.keys());
+}
+
+#[test]
+fn parallel_renders_of_a_new_vault_wait_for_each_other() {
+    let root = PathBuf::from(env!("CARGO_MANIFEST_DIR"));
+    let vault = TempVault::new("parallel");
+    let runs: Vec<_> = (0..3).map(|_| {
+        std::process::Command::new(env!("CARGO_BIN_EXE_fair_renderer"))
+            .arg("render").arg("-q")
+            .arg("-i").arg(root.join("tests/fixtures/fair.json"))
+            .arg("-t").arg(root.join("vault_templates/career_fair_2025_template"))
+            .arg("-o").arg(&vault.path)
+            .stdout(std::process::Stdio::null())
+            .stderr(std::process::Stdio::piped())
+            .spawn()
+            .unwrap()
+    }).collect();
+    for run in runs {
+        let output = run.wait_with_output().unwrap();
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+    assert!(vault.files().contains_key("companies/Company 59b40fe8.md"));
+}
+
+#[test]
+fn update_merges_into_an_edited_fileclass() {
+    let vault = TempVault::new("fileclass");
+    renderer(false).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+
+    // job_types made a Multi with an option of the user's own, a field of theirs added and international deleted
+    let class_path = vault.path.join("classes/company.md");
+    let class = fs::read_to_string(&class_path).unwrap()
+        .replace("  - name: job_types\n    type: Input\n    options: {}\n", "  - name: job_types\n    type: Multi\n    options:\n      valuesList:\n        \"1\": Co-op\n")
+        .replace("    id: ms1eK1\n", "    id: ms1eK1\n  - name: Contacted\n    type: Boolean\n    options: {}\n    path: \"\"\n    id: usr001\n")
+        .replace("  - name: international\n    type: Input\n    options: {}\n    path: \"\"\n    id: fbcdef\n", "");
+    assert!(class.contains("usr001") && class.contains("Co-op") && !class.contains("name: international"));
+    fs::write(&class_path, &class).unwrap();
+
+    renderer(true).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+    let merged = fs::read_to_string(&class_path).unwrap();
+    assert!(merged.contains("  - name: Contacted\n    type: Boolean\n"), "{}", merged);
+    assert!(merged.contains("  - name: job_types\n    type: Multi\n"), "{}", merged);
+    assert!(merged.find("Co-op").unwrap() < merged.find("Internship").unwrap(), "{}", merged);
+    assert!(merged.contains("    id: dbcdef\n"), "{}", merged);
+    assert!(merged.contains("  - name: international\n"), "{}", merged);
+}
+
+#[test]
+fn update_refreshes_marked_sections_and_keeps_the_users_text() {
+    let vault = TempVault::new("sections");
+    renderer(false).render(&Fair::parse(&serde_json::to_vec(&fixture()).unwrap()).unwrap(), &vault.path).unwrap();
+
+    // a value filled in and lines of the user's between the sections and at the end
+    let note_path = vault.path.join("companies/Company 59b40fe8.md");
+    let note = fs::read_to_string(&note_path).unwrap()
+        .replace("Priority: \n", "Priority: 3\n")
+        .replace("<!-- /fair_renderer:logo -->\n", "<!-- /fair_renderer:logo -->\nmet them at the booth\n")
+        + "\n## Notes\nmet the recruiter\n";
+    fs::write(&note_path, &note).unwrap();
+
+    let changed = edited(|entry| {
+        entry["company_description"] = "A brand new description.".into();
+        entry["employer"]["logo_url"] = "https://example.com/new_logo".into();
+    });
+    let report = renderer(true).render(&changed, &vault.path).unwrap();
+    assert!(report.skipped.is_empty(), "{:?}", report.skipped);
+    let updated = fs::read_to_string(&note_path).unwrap();
+    assert!(updated.contains("<!-- fair_renderer:description -->\n### Description\n\nA brand new description.\n"), "{}", updated);
+    assert!(!updated.contains("incididunt enim amet"), "{}", updated);
+    assert!(updated.contains("example.com/new_logo\" style=\"width: 80px;\">\n<!-- /fair_renderer:logo -->\nmet them at the booth\n"), "{}", updated);
+    assert!(updated.contains("Priority: 3\n"), "{}", updated);
+    assert!(updated.ends_with("\n## Notes\nmet the recruiter\n"), "{}", updated);
+}
+
+#[test]
+fn resuming_doesnt_take_a_skipped_edit_for_ours() {
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fair.json").to_string_lossy().to_string();
+    let vault = TempVault::new("resume_edited");
+    vault.render(&["-i", &input, "--no-build-info"]).unwrap();
+    let note_path = vault.path.join("companies/Company 59b40fe8.md");
+    let note = fs::read_to_string(&note_path).unwrap().replace("### Description\n\n", "### Description\n\nmy own words\n");
+    fs::write(&note_path, &note).unwrap();
+
+    // an update that died after skipping the edited note, a skip is recorded without a hash
+    let manifest: serde_json::Value = serde_json::from_slice(&fs::read(vault.path.join(".fair_renderer/manifest.json")).unwrap()).unwrap();
+    let progress = format!("{}\ncompany:Company 59b40fe8\n", manifest["input_sha256"].as_str().unwrap());
+    fs::write(vault.path.join(".fair_renderer/progress"), progress).unwrap();
+
+    vault.update(&["-i", &input, "--no-build-info", "--resume"]).unwrap();
+    vault.update(&["-i", &input, "--no-build-info"]).unwrap();
+    assert!(fs::read_to_string(&note_path).unwrap().contains("my own words"));
+}
+
+#[test]
+fn logos_embedded_outside_the_company_notes_are_kept() {
+    let input = PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("tests/fixtures/fair.json").to_string_lossy().to_string();
+    let vault = TempVault::new("prune_logos");
+    vault.render(&["-i", &input, "--no-build-info"]).unwrap();
+    let logos = vault.path.join("attachments/logos");
+    fs::create_dir_all(&logos).unwrap();
+    fs::write(logos.join("mine.png"), "png").unwrap();
+    fs::write(logos.join("stale.png"), "png").unwrap();
+    fs::write(vault.path.join("Booth map.md"), "![[mine.png|200]] next to ![[notstale.png]]\n").unwrap();
+
+    // without --download-logos the logos folder isn't the update's to clean up
+    vault.update(&["-i", &input, "--no-build-info"]).unwrap();
+    assert!(logos.join("stale.png").exists());
+
+    vault.update(&["-i", &input, "--no-build-info", "--download-logos", "--max-downloads", "0"]).unwrap();
+    assert!(logos.join("mine.png").exists());
+    assert!(!logos.join("stale.png").exists());
+}